@@ -0,0 +1,114 @@
+//! Benchmarks the generator and each part of every day on the puzzle input
+//! in `input/2020`.
+//!
+//! The benchmarks are named `day<NN>/generator` and `day<NN>/part<N>`, so a
+//! single day or part can be selected with a filter:
+//!
+//! ```text
+//! cargo bench --bench solutions -- day15/part2
+//! ```
+//!
+//! Parts marked as slow in the registry are benchmarked on a reduced input
+//! instead, named `day<NN>/part<N>-reduced`. Parts without a recorded answer
+//! are skipped, as they are not known to finish on the puzzle input.
+//!
+//! The group `day17-strategies` compares the ways of evolving the pocket
+//! dimension in part 2 against each other, named `<evolution>/<strategy>`.
+
+use advent_of_code_2020::day17::{self, Evolution, Strategy};
+use advent_of_code_2020::registry::Solution;
+use advent_of_code_2020::{solver, solvers};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, SamplingMode};
+use std::fs;
+use std::time::Duration;
+
+/// Days with a part that takes a second or more on the puzzle input.
+const SLOW_DAYS: &[u8] = &[15, 23];
+
+/// Inputs for the parts marked as slow, as `(day, part, input)`. They are
+/// small enough for the brute force solvers to finish in a few milliseconds.
+const REDUCED_INPUTS: &[(u8, u8, &str)] = &[(13, 2, "6\n1789,37,47,1889\n")];
+
+fn read_input(solution: &Solution) -> String {
+    fs::read_to_string(solution.input_path())
+        .unwrap_or_else(|err| panic!("cannot read input {}: {}", solution.input_path(), err))
+}
+
+fn bench_solutions(c: &mut Criterion) {
+    for solution in solvers() {
+        let input = read_input(solution);
+        let generated = (solution.generator)(&input)
+            .unwrap_or_else(|err| panic!("invalid input for day {}: {}", solution.day, err));
+
+        let mut group = c.benchmark_group(format!("day{:02}", solution.day));
+        if SLOW_DAYS.contains(&solution.day) {
+            group
+                .sampling_mode(SamplingMode::Flat)
+                .sample_size(10)
+                .measurement_time(Duration::from_secs(60));
+        }
+        group.bench_function("generator", |b| {
+            b.iter(|| (solution.generator)(black_box(&input)))
+        });
+        for (index, part) in solution.parts.iter().enumerate() {
+            let number = index as u8 + 1;
+            if part.slow {
+                let reduced = REDUCED_INPUTS
+                    .iter()
+                    .find(|(day, part, _)| (*day, *part) == (solution.day, number));
+                match reduced {
+                    Some((_, _, input)) => {
+                        let generated = (solution.generator)(input).unwrap_or_else(|err| {
+                            panic!("invalid reduced input for day {}: {}", solution.day, err)
+                        });
+                        group.bench_function(format!("part{}-reduced", number), |b| {
+                            b.iter(|| (part.run)(black_box(&*generated)))
+                        });
+                    }
+                    None => eprintln!(
+                        "skipping day{:02}/part{}: slow and no reduced input",
+                        solution.day, number
+                    ),
+                }
+                continue;
+            }
+            if solution.expected(number).is_none() {
+                eprintln!(
+                    "skipping day{:02}/part{}: no answer recorded",
+                    solution.day, number
+                );
+                continue;
+            }
+            group.bench_function(format!("part{}", number), |b| {
+                b.iter(|| (part.run)(black_box(&*generated)))
+            });
+        }
+        group.finish();
+    }
+}
+
+fn bench_day17_strategies(c: &mut Criterion) {
+    let input = read_input(solver(17).expect("day 17 is registered"));
+    let initial_slice = day17::parse_conway_cubes(&input);
+
+    let mut group = c.benchmark_group("day17-strategies");
+    for evolution in [Evolution::Full, Evolution::Symmetric] {
+        for strategy in [Strategy::ProbeNeighbors, Strategy::CountNeighbors] {
+            let name = format!("{:?}/{:?}", evolution, strategy);
+            group.bench_function(name, |b| {
+                b.iter(|| {
+                    day17::num_active_after_boot::<4>(
+                        black_box(&initial_slice),
+                        6,
+                        evolution,
+                        strategy,
+                    )
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_solutions, bench_day17_strategies);
+criterion_main!(benches);
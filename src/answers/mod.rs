@@ -0,0 +1,112 @@
+//! # Recorded answers
+//!
+//! The answers to the puzzle inputs in `input/2020` are recorded in the data
+//! file `answers/2020.toml`. It contains a table for each day with the answer
+//! of each part as string:
+//!
+//! ```toml
+//! [day1]
+//! part1 = "788739"
+//! part2 = "178724430"
+//! ```
+//!
+//! Only this subset of TOML is supported: tables named `day<N>`, keys named
+//! `part<N>`, string or integer values and comments on lines of their own.
+
+use crate::parse::{end_of, parse_number, ParseError, ParseErrorKind};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// The content of the data file `answers/2020.toml`.
+pub const RECORDED_ANSWERS: &str = include_str!("../../answers/2020.toml");
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, u8), String>,
+}
+
+impl FromStr for Answers {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut answers = BTreeMap::new();
+        let mut day = None;
+        for line in input.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(table) = line.strip_prefix('[') {
+                let name = table.strip_suffix(']').ok_or_else(|| {
+                    ParseError::at(input, end_of(line), ParseErrorKind::Missing("']'"))
+                })?;
+                let number = name.strip_prefix("day").ok_or_else(|| {
+                    ParseError::at(input, name, ParseErrorKind::Invalid("table name"))
+                })?;
+                day = Some(parse_number(input, number)?);
+                continue;
+            }
+            let assign = line.find('=').ok_or_else(|| {
+                ParseError::at(input, end_of(line), ParseErrorKind::Missing("'='"))
+            })?;
+            let key = line[..assign].trim();
+            let number = key
+                .strip_prefix("part")
+                .ok_or_else(|| ParseError::at(input, key, ParseErrorKind::Invalid("key")))?;
+            let part = parse_number(input, number)?;
+            let value = line[assign + 1..].trim();
+            let answer = value
+                .strip_prefix('"')
+                .and_then(|quoted| quoted.strip_suffix('"'))
+                .unwrap_or(value);
+            if answer.is_empty() {
+                return Err(ParseError::at(
+                    input,
+                    end_of(line),
+                    ParseErrorKind::Missing("answer"),
+                ));
+            }
+            let day = day.ok_or_else(|| {
+                ParseError::at(input, line, ParseErrorKind::Missing("table of the day"))
+            })?;
+            answers.insert((day, part), answer.to_string());
+        }
+        Ok(Self { answers })
+    }
+}
+
+impl Answers {
+    /// Returns the answers recorded in `answers/2020.toml`.
+    pub fn recorded() -> &'static Answers {
+        static RECORDED: OnceLock<Answers> = OnceLock::new();
+        RECORDED.get_or_init(|| {
+            Answers::from_str(RECORDED_ANSWERS)
+                .unwrap_or_else(|err| panic!("invalid recorded answers: {}", err))
+        })
+    }
+
+    /// Returns the answer to the given part of the given day.
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+}
+
+/// Returns the recorded answer to the given part of the given day, for tests
+/// that check a solver against the puzzle input.
+#[cfg(test)]
+pub(crate) fn recorded_answer(day: u8, part: u8) -> &'static str {
+    Answers::recorded()
+        .get(day, part)
+        .unwrap_or_else(|| panic!("no answer recorded for day {} part {}", day, part))
+}
+
+#[cfg(test)]
+mod tests;
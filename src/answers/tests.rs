@@ -0,0 +1,38 @@
+use super::*;
+
+#[test]
+fn parse_answers_with_comments_and_integer_values() {
+    let answers = Answers::from_str(
+        "# answers\n\n[day1]\npart1 = \"514579\"\n\n[day13]\npart1 = 295\n# part2 unknown\n",
+    )
+    .unwrap();
+
+    assert_eq!(answers.len(), 2);
+    assert_eq!(answers.get(1, 1), Some("514579"));
+    assert_eq!(answers.get(13, 1), Some("295"));
+    assert_eq!(answers.get(13, 2), None);
+}
+
+#[test]
+fn parse_answers_without_table() {
+    let error = Answers::from_str("part1 = \"42\"\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (1, 1));
+    assert_eq!(error.kind(), &ParseErrorKind::Missing("table of the day"));
+}
+
+#[test]
+fn parse_answers_with_invalid_key() {
+    let error = Answers::from_str("[day2]\nanswer = \"42\"\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (2, 1));
+    assert_eq!(error.kind(), &ParseErrorKind::Invalid("key"));
+}
+
+#[test]
+fn recorded_answers_contain_every_solved_part() {
+    let answers = Answers::recorded();
+
+    assert_eq!(answers.len(), 49);
+    assert_eq!(answers.get(25, 1), Some("181800"));
+}
@@ -0,0 +1,40 @@
+//! # Unique assignment by elimination
+//!
+//! Several puzzles ask to match each item of one kind to exactly one item of
+//! another kind, given the set of candidates each item could be matched with.
+//! Such puzzles are designed so that at every step at least one item has only
+//! a single candidate left. Assigning that candidate and removing it from the
+//! candidates of all other items eventually leads to the unique assignment.
+
+use hashbrown::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Determines the unique assignment of keys to values by elimination.
+///
+/// Repeatedly takes a key that has exactly one candidate left, assigns that
+/// candidate to it and removes the candidate from all other keys.
+///
+/// Returns `None` if at some point there is no key with exactly one
+/// candidate left.
+pub fn assign_by_elimination<K, V>(mut candidates: HashMap<K, HashSet<V>>) -> Option<HashMap<K, V>>
+where
+    K: Clone + Eq + Hash,
+    V: Eq + Hash,
+{
+    let mut assignment = HashMap::with_capacity(candidates.len());
+    while !candidates.is_empty() {
+        let key = candidates
+            .iter()
+            .find(|(_, values)| values.len() == 1)
+            .map(|(key, _)| key.clone())?;
+        let value = candidates.remove(&key)?.into_iter().next()?;
+        for values in candidates.values_mut() {
+            values.remove(&value);
+        }
+        assignment.insert(key, value);
+    }
+    Some(assignment)
+}
+
+#[cfg(test)]
+mod tests;
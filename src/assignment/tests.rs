@@ -0,0 +1,37 @@
+use super::*;
+use std::iter::FromIterator;
+
+fn candidates(entries: &[(&'static str, &[u32])]) -> HashMap<&'static str, HashSet<u32>> {
+    entries
+        .iter()
+        .map(|(key, values)| (*key, HashSet::from_iter(values.iter().copied())))
+        .collect()
+}
+
+#[test]
+fn assign_by_elimination_of_solvable_candidates() {
+    let candidates = candidates(&[("a", &[1, 2, 3]), ("b", &[2]), ("c", &[2, 3])]);
+
+    let assignment = assign_by_elimination(candidates);
+
+    assert_eq!(
+        assignment,
+        Some(HashMap::from_iter(vec![("a", 1), ("b", 2), ("c", 3)]))
+    );
+}
+
+#[test]
+fn assign_by_elimination_of_ambiguous_candidates() {
+    let candidates = candidates(&[("a", &[1, 2]), ("b", &[1, 2])]);
+
+    let assignment = assign_by_elimination(candidates);
+
+    assert_eq!(assignment, None);
+}
+
+#[test]
+fn assign_by_elimination_of_no_candidates() {
+    let assignment = assign_by_elimination(HashMap::<&str, HashSet<u32>>::new());
+
+    assert_eq!(assignment, Some(HashMap::new()));
+}
@@ -0,0 +1,928 @@
+//! # Cellular automata
+//!
+//! Several puzzles simulate life-like cellular automata: the seating system
+//! (day 11), the Conway cubes (day 17) and the lobby floor (day 24). They only
+//! differ in their [`Rule`], which decides whether a cell is alive in the next
+//! generation, and in their [`Topology`], which defines the cells and which of
+//! them are neighbors.
+//!
+//! Six topologies are provided:
+//!
+//! * [`Graph`]: numbered cells with precomputed lists of neighbors, which
+//!   evaluates only the cells next to a change
+//! * [`DenseGrid`]: the cells of a bounded [`Grid`] with the eight adjacent
+//!   cells as neighbors
+//! * [`LineOfSight`]: the cells of a bounded [`Grid`] with the first cell seen
+//!   in each of the eight directions as neighbors
+//! * [`SparseLattice`]: an unbounded N-dimensional lattice with the `3^N - 1`
+//!   surrounding cells as neighbors
+//! * [`SymmetricLattice`]: a [`SparseLattice`] that stores only one of each
+//!   class of cells which are symmetric in some of the dimensions
+//! * [`HexLattice`]: an unbounded hexagonal lattice in axial coordinates with
+//!   six neighbors
+//!
+//! The unbounded lattices store the live cells of a generation in a set and
+//! implement [`Topology`] via the [`Lattice`] trait.
+
+use crate::grid::{Direction, Grid, Position};
+use fxhash::FxBuildHasher;
+use hashbrown::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::hash::Hash;
+use std::mem;
+
+/// Decides whether a cell is alive in the next generation.
+pub trait Rule {
+    fn is_alive_next(&self, alive: bool, live_neighbors: usize) -> bool;
+}
+
+/// A rule given by the numbers of live neighbors for which a dead cell comes
+/// alive (birth) and for which a live cell stays alive (survival), like
+/// `B3/S23` of Conway's Game of Life.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LifeLike {
+    birth: u64,
+    survival: u64,
+}
+
+impl LifeLike {
+    /// # Panics
+    ///
+    /// Panics if a number of neighbors is 64 or more.
+    pub const fn new(birth: &[usize], survival: &[usize]) -> Self {
+        Self {
+            birth: Self::mask(birth),
+            survival: Self::mask(survival),
+        }
+    }
+
+    const fn mask(counts: &[usize]) -> u64 {
+        let mut mask = 0;
+        let mut i = 0;
+        while i < counts.len() {
+            assert!(counts[i] < 64, "number of neighbors is out of range");
+            mask |= 1 << counts[i];
+            i += 1;
+        }
+        mask
+    }
+}
+
+impl Rule for LifeLike {
+    fn is_alive_next(&self, alive: bool, live_neighbors: usize) -> bool {
+        let mask = if alive { self.survival } else { self.birth };
+        u32::try_from(live_neighbors)
+            .ok()
+            .and_then(|count| 1u64.checked_shl(count))
+            .is_some_and(|bit| mask & bit != 0)
+    }
+}
+
+/// The cells of an automaton and their neighborhood.
+pub trait Topology {
+    type Cell: Copy;
+
+    /// The states of all cells in one generation.
+    type Generation: Clone + PartialEq;
+
+    /// Calls `f` for every cell that can be alive in the generation following
+    /// the given one.
+    fn for_each_candidate(&self, generation: &Self::Generation, f: impl FnMut(Self::Cell));
+
+    fn is_alive(&self, generation: &Self::Generation, cell: Self::Cell) -> bool;
+
+    fn live_neighbors(&self, generation: &Self::Generation, cell: Self::Cell) -> usize;
+
+    /// Sets all cells of the generation to dead.
+    fn clear(&self, generation: &mut Self::Generation);
+
+    fn set_alive(&self, generation: &mut Self::Generation, cell: Self::Cell);
+
+    /// Returns the number of live cells.
+    fn population(&self, generation: &Self::Generation) -> usize;
+
+    /// Applies the rule to every cell of the current generation and stores
+    /// the result in `next`.
+    fn next_generation(
+        &self,
+        rule: &impl Rule,
+        current: &Self::Generation,
+        next: &mut Self::Generation,
+    ) {
+        self.clear(next);
+        self.for_each_candidate(current, |cell| {
+            let alive = self.is_alive(current, cell);
+            if rule.is_alive_next(alive, self.live_neighbors(current, cell)) {
+                self.set_alive(next, cell);
+            }
+        });
+    }
+}
+
+/// The cells of a grid with the up to eight adjacent cells as neighbors.
+///
+/// Only the cells set to `true` in the grid given on construction take part
+/// in the automaton, all other cells stay dead forever.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DenseGrid {
+    cells: Grid<bool>,
+}
+
+impl DenseGrid {
+    pub fn new(cells: Grid<bool>) -> Self {
+        Self { cells }
+    }
+}
+
+impl Topology for DenseGrid {
+    type Cell = Position;
+    type Generation = Grid<bool>;
+
+    fn for_each_candidate(&self, _generation: &Self::Generation, f: impl FnMut(Self::Cell)) {
+        self.cells
+            .positions()
+            .filter(|position| self.cells[*position])
+            .for_each(f);
+    }
+
+    fn is_alive(&self, generation: &Self::Generation, cell: Self::Cell) -> bool {
+        generation[cell]
+    }
+
+    fn live_neighbors(&self, generation: &Self::Generation, cell: Self::Cell) -> usize {
+        generation
+            .neighbors(cell)
+            .filter(|(_, alive)| **alive)
+            .count()
+    }
+
+    fn clear(&self, generation: &mut Self::Generation) {
+        generation.fill(false);
+    }
+
+    fn set_alive(&self, generation: &mut Self::Generation, cell: Self::Cell) {
+        generation[cell] = true;
+    }
+
+    fn population(&self, generation: &Self::Generation) -> usize {
+        generation.count(&true)
+    }
+}
+
+/// The cells of a grid with the first cell seen in each of the eight
+/// directions as neighbors.
+///
+/// Only the cells set to `true` in the grid given on construction take part
+/// in the automaton. The view passes all other cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineOfSight {
+    cells: Grid<bool>,
+    visible: Grid<Vec<Position>>,
+}
+
+impl LineOfSight {
+    pub fn new(cells: Grid<bool>) -> Self {
+        let visible = cells.map_with_position(|position, _| {
+            Direction::ALL
+                .iter()
+                .filter_map(|direction| {
+                    cells
+                        .ray(position, *direction)
+                        .find(|(_, is_cell)| **is_cell)
+                        .map(|(visible, _)| visible)
+                })
+                .collect()
+        });
+        Self { cells, visible }
+    }
+
+    /// Returns the cells seen from the given cell.
+    pub fn visible(&self, cell: Position) -> &[Position] {
+        &self.visible[cell]
+    }
+}
+
+impl Topology for LineOfSight {
+    type Cell = Position;
+    type Generation = Grid<bool>;
+
+    fn for_each_candidate(&self, _generation: &Self::Generation, f: impl FnMut(Self::Cell)) {
+        self.cells
+            .positions()
+            .filter(|position| self.cells[*position])
+            .for_each(f);
+    }
+
+    fn is_alive(&self, generation: &Self::Generation, cell: Self::Cell) -> bool {
+        generation[cell]
+    }
+
+    fn live_neighbors(&self, generation: &Self::Generation, cell: Self::Cell) -> usize {
+        self.visible[cell]
+            .iter()
+            .filter(|visible| generation[**visible])
+            .count()
+    }
+
+    fn clear(&self, generation: &mut Self::Generation) {
+        generation.fill(false);
+    }
+
+    fn set_alive(&self, generation: &mut Self::Generation, cell: Self::Cell) {
+        generation[cell] = true;
+    }
+
+    fn population(&self, generation: &Self::Generation) -> usize {
+        generation.count(&true)
+    }
+}
+
+/// Cells numbered from 0 with precomputed lists of neighbors, like the seats
+/// of a seat layout with the seats seen from each seat.
+///
+/// A generation stores the states of the cells as bits and remembers which
+/// cells changed. Only those cells and their neighbors are evaluated for the
+/// next generation, which requires that every cell is a neighbor of its
+/// neighbors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph {
+    /// The neighbors of cell `i` are `neighbors[starts[i]..starts[i + 1]]`.
+    starts: Vec<usize>,
+    neighbors: Vec<u32>,
+}
+
+impl Graph {
+    /// Creates a graph from the neighbors of each cell in the order of the
+    /// cells.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a neighbor is not a cell of the graph. Debug builds also
+    /// panic if a cell is not a neighbor of one of its neighbors.
+    pub fn new<N>(neighbors: impl IntoIterator<Item = N>) -> Self
+    where
+        N: IntoIterator<Item = usize>,
+    {
+        let mut starts = vec![0];
+        let mut flat = Vec::new();
+        for cell_neighbors in neighbors {
+            flat.extend(
+                cell_neighbors.into_iter().map(|neighbor| {
+                    u32::try_from(neighbor).expect("number of cells is out of range")
+                }),
+            );
+            starts.push(flat.len());
+        }
+        let len = starts.len() - 1;
+        assert!(
+            flat.iter().all(|neighbor| (*neighbor as usize) < len),
+            "neighbor is not a cell of the graph"
+        );
+        let graph = Self {
+            starts,
+            neighbors: flat,
+        };
+        debug_assert!(
+            (0..len).all(|cell| graph
+                .neighbors(cell)
+                .iter()
+                .all(|neighbor| graph.neighbors(*neighbor as usize).contains(&(cell as u32)))),
+            "neighbor relation is not symmetric"
+        );
+        graph
+    }
+
+    /// Returns the number of cells.
+    pub fn len(&self) -> usize {
+        self.starts.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn neighbors(&self, cell: usize) -> &[u32] {
+        &self.neighbors[self.starts[cell]..self.starts[cell + 1]]
+    }
+}
+
+const BITS: usize = u64::BITS as usize;
+
+/// Only the neighbors of changed cells are marked as candidates if less than
+/// one in this many cells changed, otherwise all cells are evaluated.
+const SPARSE_CHANGES: usize = 16;
+
+/// The states of the cells of a [`Graph`] packed into bits, together with
+/// the cells that changed in the step producing this generation and the
+/// numbers of live neighbors of all cells, which are kept up to date from
+/// the changes.
+///
+/// Two generations are equal if their cells have the same states.
+#[derive(Debug, Clone)]
+pub struct BitCells {
+    len: usize,
+    bits: Vec<u64>,
+    changed: Vec<u32>,
+    /// Empty until counted in the first step.
+    live_neighbors: Vec<u32>,
+    candidates: Vec<u64>,
+}
+
+impl BitCells {
+    /// Creates a generation from the states of the cells. All cells count as
+    /// changed.
+    pub fn new(alive: impl IntoIterator<Item = bool>) -> Self {
+        let alive = alive.into_iter().collect::<Vec<_>>();
+        let mut cells = Self {
+            len: alive.len(),
+            bits: vec![0; alive.len().div_ceil(BITS)],
+            changed: Vec::new(),
+            live_neighbors: Vec::new(),
+            candidates: Vec::new(),
+        };
+        for (cell, alive) in alive.into_iter().enumerate() {
+            cells.set(cell, alive);
+        }
+        cells.mark_all_changed();
+        cells
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, cell: usize) -> bool {
+        self.bits[cell / BITS] & (1 << (cell % BITS)) != 0
+    }
+
+    fn set(&mut self, cell: usize, alive: bool) {
+        let bit = 1 << (cell % BITS);
+        if alive {
+            self.bits[cell / BITS] |= bit;
+        } else {
+            self.bits[cell / BITS] &= !bit;
+        }
+    }
+
+    /// Returns the number of live cells.
+    pub fn count_alive(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Marks all cells as changed and the numbers of live neighbors as
+    /// unknown.
+    fn mark_all_changed(&mut self) {
+        self.changed.clear();
+        self.changed.extend(0..self.len as u32);
+        self.live_neighbors.clear();
+    }
+}
+
+impl PartialEq for BitCells {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.bits == other.bits
+    }
+}
+
+impl Topology for Graph {
+    type Cell = usize;
+    type Generation = BitCells;
+
+    fn for_each_candidate(&self, _generation: &Self::Generation, f: impl FnMut(Self::Cell)) {
+        (0..self.len()).for_each(f);
+    }
+
+    fn is_alive(&self, generation: &Self::Generation, cell: Self::Cell) -> bool {
+        generation.get(cell)
+    }
+
+    fn live_neighbors(&self, generation: &Self::Generation, cell: Self::Cell) -> usize {
+        self.neighbors(cell)
+            .iter()
+            .filter(|neighbor| generation.get(**neighbor as usize))
+            .count()
+    }
+
+    fn clear(&self, generation: &mut Self::Generation) {
+        generation.bits.iter_mut().for_each(|word| *word = 0);
+        generation.mark_all_changed();
+    }
+
+    fn set_alive(&self, generation: &mut Self::Generation, cell: Self::Cell) {
+        generation.set(cell, true);
+    }
+
+    fn population(&self, generation: &Self::Generation) -> usize {
+        generation.count_alive()
+    }
+
+    /// Evaluates only the cells that changed in the current generation and
+    /// their neighbors, as the state of every other cell stays the same.
+    fn next_generation(
+        &self,
+        rule: &impl Rule,
+        current: &Self::Generation,
+        next: &mut Self::Generation,
+    ) {
+        next.len = current.len;
+        next.bits.clone_from(&current.bits);
+        next.changed.clear();
+        if current.live_neighbors.len() == current.len {
+            next.live_neighbors.clone_from(&current.live_neighbors);
+        } else {
+            next.live_neighbors.clear();
+            next.live_neighbors
+                .extend((0..self.len()).map(|cell| self.live_neighbors(current, cell) as u32));
+        }
+
+        let candidates = &mut next.candidates;
+        candidates.clear();
+        if current.changed.len() * SPARSE_CHANGES < current.len {
+            candidates.resize(current.bits.len(), 0);
+            let mut mark = |cell: usize| candidates[cell / BITS] |= 1 << (cell % BITS);
+            for &changed in &current.changed {
+                mark(changed as usize);
+                for neighbor in self.neighbors(changed as usize) {
+                    mark(*neighbor as usize);
+                }
+            }
+        } else {
+            candidates.resize(current.bits.len(), !0);
+        }
+
+        for (index, mut word) in next.candidates.iter().copied().enumerate() {
+            while word != 0 {
+                let cell = index * BITS + word.trailing_zeros() as usize;
+                word &= word - 1;
+                if cell >= current.len {
+                    break;
+                }
+                let alive = current.get(cell);
+                let live_neighbors = next.live_neighbors[cell] as usize;
+                if rule.is_alive_next(alive, live_neighbors) != alive {
+                    next.bits[cell / BITS] ^= 1 << (cell % BITS);
+                    next.changed.push(cell as u32);
+                }
+            }
+        }
+
+        for &changed in &next.changed {
+            let delta = if next.get(changed as usize) {
+                1
+            } else {
+                u32::MAX
+            };
+            for neighbor in self.neighbors(changed as usize) {
+                let count = &mut next.live_neighbors[*neighbor as usize];
+                *count = count.wrapping_add(delta);
+            }
+        }
+    }
+}
+
+/// The live cells of a generation on an unbounded lattice.
+pub type LiveCells<C> = HashSet<C, FxBuildHasher>;
+
+/// The numbers of live neighbors of cells on an unbounded lattice, saturating
+/// at `u8::MAX`.
+pub type NeighborCounts<C> = HashMap<C, u8, FxBuildHasher>;
+
+/// An unbounded lattice on which a generation is the set of its live cells.
+pub trait Lattice {
+    type Cell: Copy + Eq + Hash;
+
+    fn for_each_neighbor(&self, cell: Self::Cell, f: impl FnMut(Self::Cell));
+
+    /// Returns the number of cells the given cell stands for, which is more
+    /// than one on lattices that store only one of several symmetric cells.
+    fn weight(&self, _cell: Self::Cell) -> usize {
+        1
+    }
+
+    /// Stores the number of live neighbors of every cell with at least one
+    /// live neighbor in `counts`, replacing its previous contents.
+    fn count_live_neighbors(
+        &self,
+        generation: &LiveCells<Self::Cell>,
+        counts: &mut NeighborCounts<Self::Cell>,
+    ) {
+        counts.clear();
+        for cell in generation {
+            self.for_each_neighbor(*cell, |neighbor| {
+                let count = counts.entry(neighbor).or_insert(0);
+                *count = count.saturating_add(1);
+            });
+        }
+    }
+
+    /// Applies the rule like [`Topology::next_generation`], but visits only
+    /// the live cells, accumulating the numbers of live neighbors in `counts`
+    /// instead of probing the neighbors of every candidate.
+    fn next_generation_by_counting(
+        &self,
+        rule: &impl Rule,
+        current: &LiveCells<Self::Cell>,
+        next: &mut LiveCells<Self::Cell>,
+        counts: &mut NeighborCounts<Self::Cell>,
+    ) {
+        self.count_live_neighbors(current, counts);
+        next.clear();
+        for (cell, count) in counts.iter() {
+            if rule.is_alive_next(current.contains(cell), usize::from(*count)) {
+                next.insert(*cell);
+            }
+        }
+        if rule.is_alive_next(true, 0) {
+            next.extend(current.iter().filter(|cell| !counts.contains_key(*cell)));
+        }
+    }
+}
+
+impl<L> Topology for L
+where
+    L: Lattice,
+{
+    type Cell = L::Cell;
+    type Generation = LiveCells<L::Cell>;
+
+    /// The live cells and their neighbors, as only those can be alive in the
+    /// next generation of a rule without birth from zero neighbors.
+    fn for_each_candidate(&self, generation: &Self::Generation, f: impl FnMut(Self::Cell)) {
+        let mut candidates =
+            LiveCells::with_capacity_and_hasher(generation.len() * 4, FxBuildHasher::default());
+        for cell in generation {
+            candidates.insert(*cell);
+            self.for_each_neighbor(*cell, |neighbor| {
+                candidates.insert(neighbor);
+            });
+        }
+        candidates.into_iter().for_each(f);
+    }
+
+    fn is_alive(&self, generation: &Self::Generation, cell: Self::Cell) -> bool {
+        generation.contains(&cell)
+    }
+
+    fn live_neighbors(&self, generation: &Self::Generation, cell: Self::Cell) -> usize {
+        let mut count = 0;
+        self.for_each_neighbor(cell, |neighbor| {
+            if generation.contains(&neighbor) {
+                count += 1;
+            }
+        });
+        count
+    }
+
+    fn clear(&self, generation: &mut Self::Generation) {
+        generation.clear();
+    }
+
+    fn set_alive(&self, generation: &mut Self::Generation, cell: Self::Cell) {
+        generation.insert(cell);
+    }
+
+    fn population(&self, generation: &Self::Generation) -> usize {
+        generation.iter().map(|cell| self.weight(*cell)).sum()
+    }
+}
+
+/// Returns the offsets of the `3^N - 1` cells surrounding a cell in an
+/// N-dimensional lattice.
+pub fn neighbor_offsets<const N: usize>() -> Vec<[i64; N]> {
+    let mut offsets = vec![[0; N]];
+    for dim in 0..N {
+        offsets = offsets
+            .into_iter()
+            .flat_map(|offset| {
+                (-1..=1).map(move |delta| {
+                    let mut offset = offset;
+                    offset[dim] = delta;
+                    offset
+                })
+            })
+            .collect();
+    }
+    offsets.retain(|offset| offset.iter().any(|delta| *delta != 0));
+    offsets
+}
+
+/// An unbounded N-dimensional lattice where every cell has the `3^N - 1`
+/// surrounding cells as neighbors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseLattice<const N: usize> {
+    offsets: Vec<[i64; N]>,
+}
+
+impl<const N: usize> Default for SparseLattice<N> {
+    fn default() -> Self {
+        Self {
+            offsets: neighbor_offsets(),
+        }
+    }
+}
+
+impl<const N: usize> SparseLattice<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<const N: usize> Lattice for SparseLattice<N> {
+    type Cell = [i64; N];
+
+    fn for_each_neighbor(&self, cell: Self::Cell, mut f: impl FnMut(Self::Cell)) {
+        for offset in &self.offsets {
+            let mut neighbor = cell;
+            for (coord, delta) in neighbor.iter_mut().zip(offset) {
+                *coord += delta;
+            }
+            f(neighbor);
+        }
+    }
+}
+
+/// An unbounded N-dimensional lattice like [`SparseLattice`] for generations
+/// that are symmetric under mirroring and swapping the dimensions from
+/// `first_symmetric` on.
+///
+/// A generation stores only one representative of each class of symmetric
+/// cells: the cell whose symmetric coordinates are non-negative and sorted in
+/// descending order. The neighbors of a cell are mapped to their
+/// representatives and every representative is weighted by the number of
+/// cells in its class.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymmetricLattice<const N: usize> {
+    offsets: Vec<[i64; N]>,
+    first_symmetric: usize,
+}
+
+impl<const N: usize> SymmetricLattice<N> {
+    /// # Panics
+    ///
+    /// Panics if `first_symmetric` is greater than `N`.
+    pub fn new(first_symmetric: usize) -> Self {
+        assert!(
+            first_symmetric <= N,
+            "first symmetric dimension is out of range"
+        );
+        Self {
+            offsets: neighbor_offsets(),
+            first_symmetric,
+        }
+    }
+
+    pub fn first_symmetric(&self) -> usize {
+        self.first_symmetric
+    }
+
+    /// Returns the representative of the class of the given cell.
+    pub fn representative(&self, mut cell: [i64; N]) -> [i64; N] {
+        let symmetric = &mut cell[self.first_symmetric..];
+        symmetric.iter_mut().for_each(|coord| *coord = coord.abs());
+        symmetric.sort_unstable_by(|a, b| b.cmp(a));
+        cell
+    }
+
+    /// Calls `f` for every cell in the class of the given cell.
+    pub fn for_each_image(&self, cell: [i64; N], f: impl FnMut([i64; N])) {
+        let mut images = LiveCells::default();
+        images.insert(cell);
+        for dim in self.first_symmetric..N {
+            let mirrored = images
+                .iter()
+                .map(|image| {
+                    let mut image = *image;
+                    image[dim] = -image[dim];
+                    image
+                })
+                .collect::<Vec<_>>();
+            images.extend(mirrored);
+        }
+        for dim in self.first_symmetric + 1..N {
+            let swapped = images
+                .iter()
+                .flat_map(|image| {
+                    (self.first_symmetric..dim).map(move |other| {
+                        let mut image = *image;
+                        image.swap(other, dim);
+                        image
+                    })
+                })
+                .collect::<Vec<_>>();
+            images.extend(swapped);
+        }
+        images.into_iter().for_each(f);
+    }
+}
+
+impl<const N: usize> Lattice for SymmetricLattice<N> {
+    type Cell = [i64; N];
+
+    fn for_each_neighbor(&self, cell: Self::Cell, mut f: impl FnMut(Self::Cell)) {
+        for offset in &self.offsets {
+            let mut neighbor = cell;
+            for (coord, delta) in neighbor.iter_mut().zip(offset) {
+                *coord += delta;
+            }
+            f(self.representative(neighbor));
+        }
+    }
+
+    /// Counts the live neighbors of the representatives only. A live
+    /// representative `c` has `a` neighbors in the class of a representative
+    /// `x` if and only if each cell of the class of `x` has
+    /// `a * weight(c) / weight(x)` neighbors in the class of `c`.
+    fn count_live_neighbors(
+        &self,
+        generation: &LiveCells<Self::Cell>,
+        counts: &mut NeighborCounts<Self::Cell>,
+    ) {
+        let mut weighted = HashMap::<_, usize, FxBuildHasher>::default();
+        for cell in generation {
+            let weight = self.weight(*cell);
+            self.for_each_neighbor(*cell, |neighbor| {
+                *weighted.entry(neighbor).or_insert(0) += weight;
+            });
+        }
+        counts.clear();
+        counts.extend(weighted.into_iter().map(|(cell, weighted)| {
+            let count = weighted / self.weight(cell);
+            (cell, u8::try_from(count).unwrap_or(u8::MAX))
+        }));
+    }
+
+    /// Returns the number of distinct cells obtained by mirroring and
+    /// permuting the symmetric coordinates of the given representative.
+    fn weight(&self, cell: Self::Cell) -> usize {
+        let symmetric = &cell[self.first_symmetric..];
+        let factorial = |n: usize| (1..=n).product::<usize>();
+        let mut permutations = factorial(symmetric.len());
+        for run in symmetric.chunk_by(|a, b| a == b) {
+            permutations /= factorial(run.len());
+        }
+        let mirrors = 1 << symmetric.iter().filter(|coord| **coord != 0).count();
+        permutations * mirrors
+    }
+}
+
+/// Offsets of the six neighbors in axial coordinates `[q, r]`, clockwise
+/// starting with east.
+pub const HEX_NEIGHBOR_OFFSETS: [[i32; 2]; 6] =
+    [[1, 0], [0, 1], [-1, 1], [-1, 0], [0, -1], [1, -1]];
+
+/// An unbounded lattice of hexagons in axial coordinates `[q, r]`, where the
+/// `q` axis points east and the `r` axis points southeast.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HexLattice;
+
+impl Lattice for HexLattice {
+    type Cell = [i32; 2];
+
+    fn for_each_neighbor(&self, [q, r]: Self::Cell, mut f: impl FnMut(Self::Cell)) {
+        for [dq, dr] in &HEX_NEIGHBOR_OFFSETS {
+            f([q + dq, r + dr]);
+        }
+    }
+}
+
+/// A cycle in the sequence of generations of an automaton.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The number of the first generation that is part of the cycle.
+    pub start: usize,
+    /// The number of generations after which a generation repeats. An
+    /// automaton that has become stable has a period of 1.
+    pub period: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Automaton<T, R>
+where
+    T: Topology,
+{
+    topology: T,
+    rule: R,
+    current: T::Generation,
+    next: T::Generation,
+    generation: usize,
+}
+
+impl<T, R> Automaton<T, R>
+where
+    T: Topology,
+    R: Rule,
+{
+    pub fn new(topology: T, rule: R, initial: T::Generation) -> Self {
+        Self {
+            topology,
+            rule,
+            next: initial.clone(),
+            current: initial,
+            generation: 0,
+        }
+    }
+
+    pub fn topology(&self) -> &T {
+        &self.topology
+    }
+
+    /// Returns the number of generations evolved since the initial one.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Returns the states of the cells in the current generation.
+    pub fn cells(&self) -> &T::Generation {
+        &self.current
+    }
+
+    /// Returns the number of live cells in the current generation.
+    pub fn population(&self) -> usize {
+        self.topology.population(&self.current)
+    }
+
+    /// Evolves the next generation and returns whether it differs from the
+    /// previous one.
+    pub fn step(&mut self) -> bool {
+        self.topology
+            .next_generation(&self.rule, &self.current, &mut self.next);
+        mem::swap(&mut self.current, &mut self.next);
+        self.generation += 1;
+        self.current != self.next
+    }
+
+    /// Evolves the given number of generations.
+    pub fn run(&mut self, generations: usize) {
+        (0..generations).for_each(|_| {
+            self.step();
+        });
+    }
+
+    /// Evolves generations until a generation equals its predecessor and
+    /// returns the number of generations evolved.
+    ///
+    /// This never returns for an automaton that oscillates or grows forever,
+    /// which can be detected with [`find_cycle`](Automaton::find_cycle).
+    pub fn run_until_stable(&mut self) -> usize {
+        let start = self.generation;
+        while self.step() {}
+        self.generation - start
+    }
+
+    /// Searches the generations following the current one for a cycle using
+    /// Brent's algorithm, evolving at most `limit` generations.
+    ///
+    /// The automaton itself is not changed.
+    pub fn find_cycle(&self, limit: usize) -> Option<Cycle> {
+        let mut scratch = self.next.clone();
+        let mut advance = |generation: &mut T::Generation| {
+            self.topology
+                .next_generation(&self.rule, generation, &mut scratch);
+            mem::swap(generation, &mut scratch);
+        };
+
+        let mut power = 1;
+        let mut period = 1;
+        let mut tortoise = self.current.clone();
+        let mut hare = self.current.clone();
+        advance(&mut hare);
+        let mut evolved = 1;
+        while tortoise != hare {
+            if evolved >= limit {
+                return None;
+            }
+            if power == period {
+                tortoise = hare.clone();
+                power *= 2;
+                period = 0;
+            }
+            advance(&mut hare);
+            period += 1;
+            evolved += 1;
+        }
+
+        let mut tortoise = self.current.clone();
+        let mut hare = self.current.clone();
+        (0..period).for_each(|_| advance(&mut hare));
+        let mut start = 0;
+        while tortoise != hare {
+            advance(&mut tortoise);
+            advance(&mut hare);
+            start += 1;
+        }
+        Some(Cycle {
+            start: self.generation + start,
+            period,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
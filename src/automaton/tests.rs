@@ -0,0 +1,233 @@
+use super::*;
+use std::str::FromStr;
+
+const GAME_OF_LIFE: LifeLike = LifeLike::new(&[3], &[2, 3]);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Alive(bool);
+
+impl TryFrom<char> for Alive {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Alive(false)),
+            '#' => Ok(Alive(true)),
+            _ => Err(value),
+        }
+    }
+}
+
+fn cells(map: &str) -> Grid<bool> {
+    Grid::<Alive>::from_str(map).unwrap().map(|alive| alive.0)
+}
+
+fn dense_game_of_life(map: &str) -> Automaton<DenseGrid, LifeLike> {
+    let initial = cells(map);
+    let topology = DenseGrid::new(Grid::new(initial.width(), initial.height(), true));
+    Automaton::new(topology, GAME_OF_LIFE, initial)
+}
+
+fn live_cells<C>(cells: impl IntoIterator<Item = C>) -> LiveCells<C>
+where
+    C: Eq + Hash,
+{
+    cells.into_iter().collect()
+}
+
+#[test]
+fn life_like_rule_births_and_survives_by_number_of_neighbors() {
+    assert!(GAME_OF_LIFE.is_alive_next(false, 3));
+    assert!(!GAME_OF_LIFE.is_alive_next(false, 2));
+    assert!(GAME_OF_LIFE.is_alive_next(true, 2));
+    assert!(GAME_OF_LIFE.is_alive_next(true, 3));
+    assert!(!GAME_OF_LIFE.is_alive_next(true, 4));
+    assert!(!GAME_OF_LIFE.is_alive_next(true, 80));
+}
+
+#[test]
+fn block_is_stable_on_dense_grid() {
+    let mut automaton = dense_game_of_life("....\n.##.\n.##.\n....\n");
+
+    let generations = automaton.run_until_stable();
+
+    assert_eq!(generations, 1);
+    assert_eq!(automaton.population(), 4);
+    assert_eq!(
+        automaton.find_cycle(10),
+        Some(Cycle {
+            start: 1,
+            period: 1
+        })
+    );
+}
+
+#[test]
+fn blinker_oscillates_on_dense_grid() {
+    let mut automaton = dense_game_of_life(".....\n..#..\n..#..\n..#..\n.....\n");
+
+    automaton.run(1);
+
+    assert_eq!(
+        automaton.cells(),
+        &cells(".....\n.....\n.###.\n.....\n.....\n")
+    );
+    assert_eq!(
+        automaton.find_cycle(10),
+        Some(Cycle {
+            start: 1,
+            period: 2
+        })
+    );
+}
+
+#[test]
+fn graph_evolves_like_dense_grid() {
+    let initial = cells(".....\n.#...\n..##.\n.##..\n.....\n");
+    let mut dense = Automaton::new(
+        DenseGrid::new(Grid::new(initial.width(), initial.height(), true)),
+        GAME_OF_LIFE,
+        initial.clone(),
+    );
+    let numbers =
+        initial.map_with_position(|position, _| position.row * initial.width() + position.col);
+    let graph = Graph::new(
+        numbers
+            .positions()
+            .map(|position| numbers.neighbors(position).map(|(_, number)| *number)),
+    );
+    let mut indexed = Automaton::new(graph, GAME_OF_LIFE, BitCells::new(initial.iter().copied()));
+
+    for _ in 0..6 {
+        dense.step();
+        indexed.step();
+
+        let cells = dense.cells().iter().copied().collect::<Vec<_>>();
+        assert_eq!(indexed.cells(), &BitCells::new(cells));
+    }
+}
+
+#[test]
+fn find_cycle_gives_up_after_limit() {
+    let automaton = Automaton::new(
+        SparseLattice::<2>::new(),
+        GAME_OF_LIFE,
+        live_cells(vec![[1, 0], [2, 1], [0, 2], [1, 2], [2, 2]]),
+    );
+
+    assert_eq!(automaton.find_cycle(20), None);
+}
+
+#[test]
+fn glider_moves_diagonally_on_sparse_lattice() {
+    let mut automaton = Automaton::new(
+        SparseLattice::<2>::new(),
+        GAME_OF_LIFE,
+        live_cells(vec![[1, 0], [2, 1], [0, 2], [1, 2], [2, 2]]),
+    );
+
+    automaton.run(4);
+
+    assert_eq!(automaton.generation(), 4);
+    assert_eq!(
+        automaton.cells(),
+        &live_cells(vec![[2, 1], [3, 2], [1, 3], [2, 3], [3, 3]])
+    );
+}
+
+#[test]
+fn neighbor_offsets_in_1_to_4_dimensions() {
+    assert_eq!(neighbor_offsets::<1>(), vec![[-1], [1]]);
+    assert_eq!(neighbor_offsets::<2>().len(), 8);
+    assert_eq!(neighbor_offsets::<3>().len(), 26);
+    assert_eq!(neighbor_offsets::<4>().len(), 80);
+}
+
+#[test]
+fn symmetric_lattice_maps_cells_to_representatives() {
+    let lattice = SymmetricLattice::<4>::new(2);
+
+    assert_eq!(lattice.representative([-1, 2, -1, 3]), [-1, 2, 3, 1]);
+    assert_eq!(lattice.representative([0, 0, 0, -2]), [0, 0, 2, 0]);
+}
+
+#[test]
+fn symmetric_lattice_weighs_representatives_by_their_images() {
+    let lattice = SymmetricLattice::<5>::new(2);
+
+    for cell in [
+        [0, 0, 0, 0, 0],
+        [1, 1, 1, 0, 0],
+        [0, 0, 2, 1, 0],
+        [0, 0, 1, 1, 1],
+    ] {
+        let mut images = Vec::new();
+        lattice.for_each_image(cell, |image| images.push(image));
+
+        assert_eq!(lattice.weight(cell), images.len(), "{:?}", cell);
+        assert!(images
+            .iter()
+            .all(|image| lattice.representative(*image) == cell));
+    }
+}
+
+#[test]
+fn symmetric_lattice_evolves_like_sparse_lattice() {
+    let initial = live_cells(vec![[1, 0, 0], [2, 1, 0], [0, 2, 0], [1, 2, 0], [2, 2, 0]]);
+    let mut sparse = Automaton::new(SparseLattice::<3>::new(), GAME_OF_LIFE, initial.clone());
+    let mut symmetric = Automaton::new(SymmetricLattice::<3>::new(2), GAME_OF_LIFE, initial);
+
+    for _ in 0..4 {
+        sparse.step();
+        symmetric.step();
+
+        assert_eq!(symmetric.population(), sparse.population());
+    }
+}
+
+#[test]
+fn counting_neighbors_evolves_like_probing_neighbors() {
+    let initial = live_cells(vec![[1, 0, 0], [2, 1, 0], [0, 2, 0], [1, 2, 0], [2, 2, 0]]);
+    let lattice = SymmetricLattice::<3>::new(2);
+    let mut probed = initial.clone();
+    let mut counted = initial;
+    let mut next = LiveCells::default();
+    let mut counts = NeighborCounts::default();
+
+    for _ in 0..4 {
+        lattice.next_generation(&GAME_OF_LIFE, &probed, &mut next);
+        mem::swap(&mut probed, &mut next);
+        lattice.next_generation_by_counting(&GAME_OF_LIFE, &counted, &mut next, &mut counts);
+        mem::swap(&mut counted, &mut next);
+
+        assert_eq!(counted, probed);
+    }
+}
+
+#[test]
+fn line_of_sight_skips_cells_not_taking_part() {
+    let seats = cells("#.#..#\n......\n#....#\n");
+
+    let topology = LineOfSight::new(seats);
+
+    assert_eq!(
+        topology.visible(Position::new(0, 0)),
+        &[Position::new(0, 2), Position::new(2, 0)]
+    );
+    assert_eq!(
+        topology.visible(Position::new(0, 2)),
+        &[
+            Position::new(0, 5),
+            Position::new(2, 0),
+            Position::new(0, 0)
+        ]
+    );
+}
+
+#[test]
+fn every_cell_has_six_neighbors_on_hex_lattice() {
+    let generation = live_cells(HEX_NEIGHBOR_OFFSETS.iter().copied());
+
+    assert_eq!(HexLattice.live_neighbors(&generation, [0, 0]), 6);
+    assert_eq!(HexLattice.live_neighbors(&generation, [1, 0]), 2);
+}
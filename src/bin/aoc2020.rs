@@ -0,0 +1,400 @@
+//! Runs the puzzle solvers of Advent of Code 2020 on arbitrary input files.
+//!
+//! ```text
+//! aoc2020 --day <1-25> [--part <1|2>] [--input <path|->]
+//! aoc2020 verify [--day <1-25>] [--timeout <seconds>] [--json] [--include-slow]
+//! ```
+//!
+//! Without `--part` all parts of the given day are run. The input defaults to
+//! `input/2020/day<N>.txt`. If the input is `-` it is read from stdin. Input
+//! that cannot be parsed, as well as a solver that panics on it, is reported
+//! as an error with exit status 1.
+//!
+//! The `verify` mode runs the solvers of all days, or of the given day only,
+//! on their puzzle input and compares the answers with the answers recorded
+//! in `answers/2020.toml`. It exits with a non-zero status if any part fails,
+//! times out or runs into an error. Parts known to be slow are skipped unless
+//! `--include-slow` is given.
+
+#![deny(unsafe_code)]
+#![warn(
+    bare_trait_objects,
+    missing_copy_implementations,
+    missing_debug_implementations,
+    rust_2018_idioms,
+    trivial_casts,
+    trivial_numeric_casts,
+    unused_extern_crates,
+    unused_import_braces,
+    unused_qualifications
+)]
+
+use advent_of_code_2020::answers::Answers;
+use advent_of_code_2020::registry::Solution;
+use advent_of_code_2020::verify::{verify, verify_including_slow};
+use advent_of_code_2020::{solver, solvers};
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "usage: aoc2020 --day <1-25> [--part <1|2>] [--input <path|->]
+       aoc2020 verify [--day <1-25>] [--timeout <seconds>] [--json] [--include-slow]";
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Options {
+    day: u8,
+    part: Option<u8>,
+    input: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct VerifyOptions {
+    day: Option<u8>,
+    timeout: Duration,
+    json: bool,
+    include_slow: bool,
+}
+
+fn parse_day(value: &str) -> Result<u8, String> {
+    value
+        .parse::<u8>()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .ok_or_else(|| format!("not a valid day {:?}", value))
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Options, String> {
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("missing value for {}", name))
+        };
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(parse_day(&value("--day")?)?),
+            "--part" | "-p" => {
+                let value = value("--part")?;
+                part = Some(
+                    value
+                        .parse::<u8>()
+                        .ok()
+                        .filter(|part| (1..=2).contains(part))
+                        .ok_or_else(|| format!("not a valid part {:?}", value))?,
+                );
+            }
+            "--input" | "-i" => input = Some(value("--input")?),
+            _ => return Err(format!("unrecognized argument {:?}", arg)),
+        }
+    }
+    Ok(Options {
+        day: day.ok_or("missing --day")?,
+        part,
+        input,
+    })
+}
+
+fn parse_verify_args(args: impl IntoIterator<Item = String>) -> Result<VerifyOptions, String> {
+    let mut options = VerifyOptions {
+        day: None,
+        timeout: DEFAULT_TIMEOUT,
+        json: false,
+        include_slow: false,
+    };
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("missing value for {}", name))
+        };
+        match arg.as_str() {
+            "--day" | "-d" => options.day = Some(parse_day(&value("--day")?)?),
+            "--timeout" | "-t" => {
+                let value = value("--timeout")?;
+                options.timeout = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|secs| secs.is_finite() && *secs > 0.)
+                    .map(Duration::from_secs_f64)
+                    .ok_or_else(|| format!("not a valid timeout {:?}", value))?;
+            }
+            "--json" => options.json = true,
+            "--include-slow" => options.include_slow = true,
+            _ => return Err(format!("unrecognized argument {:?}", arg)),
+        }
+    }
+    Ok(options)
+}
+
+fn read_input(options: &Options, solution: &Solution) -> io::Result<String> {
+    match options.input.as_deref() {
+        Some("-") => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+        Some(path) => fs::read_to_string(path),
+        None => fs::read_to_string(solution.input_path()),
+    }
+}
+
+/// Runs `f`, returning the message of the panic if it panics.
+fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|message| (*message).to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| String::from("unknown cause"))
+    })
+}
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return;
+    }
+    if args.first().map(String::as_str) == Some("verify") {
+        run_verify(args.into_iter().skip(1));
+        return;
+    }
+    let options = parse_args(args).unwrap_or_else(|err| {
+        eprintln!("error: {}\n{}", err, USAGE);
+        process::exit(2)
+    });
+    let solution = solver(options.day).unwrap_or_else(|| {
+        eprintln!("error: there is no solution for day {}", options.day);
+        process::exit(2)
+    });
+    let input = read_input(&options, solution).unwrap_or_else(|err| {
+        eprintln!("error: cannot read input: {}", err);
+        process::exit(1)
+    });
+    let parts = match options.part {
+        Some(part) => vec![part],
+        None => (1..=solution.parts.len() as u8).collect(),
+    };
+    let parts = parts
+        .into_iter()
+        .map(|number| {
+            solution
+                .part(number)
+                .map(|part| (number, part))
+                .unwrap_or_else(|| {
+                    eprintln!("error: there is no part {} on day {}", number, options.day);
+                    process::exit(2)
+                })
+        })
+        .collect::<Vec<_>>();
+
+    // panics of the generator and the solvers are reported as errors below
+    panic::set_hook(Box::new(|_| {}));
+    let start = Instant::now();
+    let generated = match catch_panic(|| (solution.generator)(&input)) {
+        Ok(Ok(generated)) => generated,
+        Ok(Err(err)) => {
+            eprintln!("error: invalid input for day {}: {}", options.day, err);
+            process::exit(1)
+        }
+        Err(message) => {
+            eprintln!(
+                "error: generator of day {} panicked: {}",
+                options.day, message
+            );
+            process::exit(1)
+        }
+    };
+    let generator_elapsed = start.elapsed();
+    println!(
+        "Day {}: {} (generator: {:?})",
+        solution.day, solution.title, generator_elapsed
+    );
+    for (number, part) in parts {
+        let start = Instant::now();
+        let answer = catch_panic(|| (part.run)(&*generated)).unwrap_or_else(|message| {
+            eprintln!(
+                "error: solver of day {} part {} panicked: {}",
+                options.day, number, message
+            );
+            process::exit(1)
+        });
+        let elapsed = start.elapsed();
+        println!("  Part {}: {} (elapsed: {:?})", number, answer, elapsed);
+    }
+}
+
+fn run_verify(args: impl IntoIterator<Item = String>) {
+    let options = parse_verify_args(args).unwrap_or_else(|err| {
+        eprintln!("error: {}\n{}", err, USAGE);
+        process::exit(2)
+    });
+    let solutions = match options.day {
+        Some(day) => solvers()
+            .iter()
+            .filter(|solution| solution.day == day)
+            .copied()
+            .collect::<Vec<_>>(),
+        None => solvers().to_vec(),
+    };
+    let report = if options.include_slow {
+        verify_including_slow(&solutions, Answers::recorded(), options.timeout)
+    } else {
+        verify(&solutions, Answers::recorded(), options.timeout)
+    };
+    if options.json {
+        print!("{}", report.to_json());
+    } else {
+        println!("{}", report);
+    }
+    if !report.is_success() {
+        process::exit(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parse_args_with_day_part_and_input() {
+        let options = parse_args(args("--day 7 --part 2 --input notes.txt"));
+
+        assert_eq!(
+            options,
+            Ok(Options {
+                day: 7,
+                part: Some(2),
+                input: Some("notes.txt".into()),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_args_with_short_names_and_stdin() {
+        let options = parse_args(args("-d 25 -i -"));
+
+        assert_eq!(
+            options,
+            Ok(Options {
+                day: 25,
+                part: None,
+                input: Some("-".into()),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_args_without_day() {
+        assert_eq!(parse_args(args("--part 1")), Err("missing --day".into()));
+    }
+
+    #[test]
+    fn parse_args_with_invalid_day() {
+        for day in &["0", "26", "x", "-1"] {
+            assert_eq!(
+                parse_args(args(&format!("--day {}", day))),
+                Err(format!("not a valid day {:?}", day))
+            );
+        }
+    }
+
+    #[test]
+    fn parse_args_with_invalid_part() {
+        for part in &["0", "3", "x"] {
+            assert_eq!(
+                parse_args(args(&format!("--day 1 --part {}", part))),
+                Err(format!("not a valid part {:?}", part))
+            );
+        }
+    }
+
+    #[test]
+    fn parse_args_with_missing_value() {
+        assert_eq!(
+            parse_args(args("--day 1 --input")),
+            Err("missing value for --input".into())
+        );
+        assert_eq!(
+            parse_args(args("--day")),
+            Err("missing value for --day".into())
+        );
+    }
+
+    #[test]
+    fn parse_args_with_unrecognized_argument() {
+        assert_eq!(
+            parse_args(args("--day 1 --json")),
+            Err("unrecognized argument \"--json\"".into())
+        );
+    }
+
+    #[test]
+    fn parse_verify_args_with_defaults() {
+        assert_eq!(
+            parse_verify_args(args("")),
+            Ok(VerifyOptions {
+                day: None,
+                timeout: DEFAULT_TIMEOUT,
+                json: false,
+                include_slow: false,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_verify_args_with_all_flags() {
+        let options = parse_verify_args(args("--day 13 --timeout 2.5 --json --include-slow"));
+
+        assert_eq!(
+            options,
+            Ok(VerifyOptions {
+                day: Some(13),
+                timeout: Duration::from_millis(2500),
+                json: true,
+                include_slow: true,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_verify_args_with_invalid_timeout() {
+        for timeout in &["0", "-1", "inf", "NaN", "x"] {
+            assert_eq!(
+                parse_verify_args(args(&format!("--timeout {}", timeout))),
+                Err(format!("not a valid timeout {:?}", timeout))
+            );
+        }
+    }
+
+    #[test]
+    fn parse_verify_args_with_unrecognized_argument() {
+        assert_eq!(
+            parse_verify_args(args("--part 1")),
+            Err("unrecognized argument \"--part\"".into())
+        );
+    }
+
+    #[test]
+    fn catch_panic_returns_the_panic_message() {
+        panic::set_hook(Box::new(|_| {}));
+        let literal = catch_panic(|| -> u8 { panic!("no solution") });
+        let formatted = catch_panic(|| -> u8 { panic!("no solution for {}", 42) });
+        let _ = panic::take_hook();
+
+        assert_eq!(literal, Err("no solution".into()));
+        assert_eq!(formatted, Err("no solution for 42".into()));
+        assert_eq!(catch_panic(|| 42), Ok(42));
+    }
+}
@@ -0,0 +1,312 @@
+//! Interactive step debugger for the boot code of day 8.
+//!
+//! ```text
+//! day08-debug [--max-steps <n>] <program-file>
+//! ```
+//!
+//! The program is assembled with the extended instruction set, so it may use
+//! comments, labels and the additional instructions besides plain boot code.
+//! Type `help` at the prompt for the list of commands.
+
+#![deny(unsafe_code)]
+#![warn(
+    bare_trait_objects,
+    missing_copy_implementations,
+    missing_debug_implementations,
+    rust_2018_idioms,
+    trivial_casts,
+    trivial_numeric_casts,
+    unused_extern_crates,
+    unused_import_braces,
+    unused_qualifications
+)]
+
+use advent_of_code_2020::day08::debugger::{Debugger, StopReason};
+use advent_of_code_2020::day08::ext::{self, ExtendedInstruction};
+use advent_of_code_2020::day08::{Address, Limits, Register, GENERAL_REGISTERS};
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::process;
+use std::str::FromStr;
+
+const USAGE: &str = "usage: day08-debug [--max-steps <n>] <program-file>";
+
+const HELP: &str = "\
+commands:
+  s, step [n]          run the next n instructions (default 1)
+  rs, reverse-step [n] undo the last n instructions (default 1)
+  c, continue          run until a breakpoint, watchpoint, loop or the end
+  u, until <address>   run until the instruction at the address is next
+  b, break [address]   set a breakpoint (default pc) or list breakpoints
+  d, delete <address>  delete the breakpoint at the address
+  w, watch [register]  stop when the register changes (default acc)
+  unwatch [register]   stop watching the register (default acc)
+  r, registers         show the registers
+  l, list [n]          show n instructions around pc (default 5)
+  o, output            show the output written so far
+  reset                start the program over
+  h, help              show this help
+  q, quit              exit the debugger";
+
+/// The number of steps `continue` and `until` run at most unless changed by
+/// `--max-steps`.
+const DEFAULT_MAX_STEPS: usize = 1_000_000;
+
+const DEFAULT_LIST_CONTEXT: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Options {
+    max_steps: usize,
+    path: String,
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Options, String> {
+    let mut max_steps = DEFAULT_MAX_STEPS;
+    let mut path = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--max-steps" | "-m" => {
+                let value = args.next().ok_or("missing value for --max-steps")?;
+                max_steps = value
+                    .parse()
+                    .map_err(|_| format!("not a valid number of steps {:?}", value))?;
+            }
+            _ if path.is_none() && !arg.starts_with('-') => path = Some(arg),
+            _ => return Err(format!("unrecognized argument {:?}", arg)),
+        }
+    }
+    Ok(Options {
+        max_steps,
+        path: path.ok_or("missing program file")?,
+    })
+}
+
+fn parse_address(value: Option<&str>) -> Result<Address, String> {
+    let value = value.ok_or("missing address")?;
+    value
+        .parse()
+        .map_err(|_| format!("not a valid address {:?}", value))
+}
+
+fn parse_count(value: Option<&str>) -> Result<usize, String> {
+    value.map_or(Ok(1), |value| {
+        value
+            .parse()
+            .map_err(|_| format!("not a valid count {:?}", value))
+    })
+}
+
+fn parse_register(value: Option<&str>) -> Result<Register, String> {
+    value.map_or(Ok(Register::Acc), |value| {
+        Register::from_str(value).map_err(|_| format!("not a valid register {:?}", value))
+    })
+}
+
+fn describe(reason: StopReason) -> String {
+    match reason {
+        StopReason::Stepped => String::new(),
+        StopReason::Breakpoint(address) => format!("breakpoint at {}", address),
+        StopReason::Cursor(address) => format!("reached {}", address),
+        StopReason::Watchpoint { register, old, new } => {
+            format!("watchpoint: {} changed from {} to {}", register, old, new)
+        }
+        StopReason::InfiniteLoop(address) => {
+            format!("infinite loop: about to run {} a second time", address)
+        }
+        StopReason::Terminated => "program terminated".into(),
+        StopReason::JumpedOutOfBounds(pc) => format!("jumped out of bounds to {}", pc),
+        StopReason::Overflow(address) => format!("overflow in the instruction at {}", address),
+        StopReason::StepLimitExceeded => "step limit exceeded".into(),
+        StopReason::StartOfHistory => "at start of history".into(),
+    }
+}
+
+fn print_registers(debugger: &Debugger<ExtendedInstruction>) {
+    let registers = debugger.registers();
+    print!("pc {}  acc {}", registers.pc, registers.acc);
+    for index in 0..GENERAL_REGISTERS {
+        print!(
+            "  {} {}",
+            Register::General(index),
+            registers.get(Register::General(index))
+        );
+    }
+    println!("  (step {})", debugger.history_len());
+}
+
+fn print_listing(debugger: &Debugger<ExtendedInstruction>, context: usize) {
+    let program = debugger.program();
+    let pc = debugger.registers().pc;
+    let width = program.len().to_string().len();
+    let end = program
+        .len()
+        .min(pc.saturating_add(context).saturating_add(1));
+    for address in pc.saturating_sub(context)..end {
+        let marker = if address == pc { "=>" } else { "  " };
+        let breakpoint = if debugger.breakpoints().any(|b| b == address) {
+            '*'
+        } else {
+            ' '
+        };
+        println!(
+            "{} {}{:>width$}  {}",
+            marker,
+            breakpoint,
+            address,
+            program[address],
+            width = width
+        );
+    }
+    if pc >= program.len() {
+        println!("=>  {:>width$}  <end>", pc, width = width);
+    }
+}
+
+/// Runs a single command and returns whether the debugger should quit.
+fn execute(debugger: &mut Debugger<ExtendedInstruction>, line: &str) -> Result<bool, String> {
+    let mut words = line.split_whitespace();
+    let command = match words.next() {
+        Some(command) => command,
+        None => return Ok(false),
+    };
+    let argument = words.next();
+    let reason = match command {
+        "s" | "step" => {
+            let mut reason = StopReason::Stepped;
+            for _ in 0..parse_count(argument)? {
+                reason = debugger.step();
+                if reason != StopReason::Stepped {
+                    break;
+                }
+            }
+            Some(reason)
+        }
+        "rs" | "reverse-step" => {
+            let mut reason = StopReason::Stepped;
+            for _ in 0..parse_count(argument)? {
+                reason = debugger.reverse_step();
+                if reason != StopReason::Stepped {
+                    break;
+                }
+            }
+            Some(reason)
+        }
+        "c" | "continue" => Some(debugger.resume()),
+        "u" | "until" => Some(debugger.run_to(parse_address(argument)?)),
+        "b" | "break" => {
+            match argument {
+                Some(_) => {
+                    debugger.set_breakpoint(parse_address(argument)?);
+                }
+                None => {
+                    let breakpoints = debugger.breakpoints().collect::<Vec<_>>();
+                    println!("breakpoints: {:?}", breakpoints);
+                }
+            }
+            None
+        }
+        "d" | "delete" => {
+            let address = parse_address(argument)?;
+            if !debugger.clear_breakpoint(address) {
+                return Err(format!("there is no breakpoint at {}", address));
+            }
+            None
+        }
+        "w" | "watch" => {
+            debugger.watch(parse_register(argument)?);
+            None
+        }
+        "unwatch" => {
+            let register = parse_register(argument)?;
+            if !debugger.unwatch(register) {
+                return Err(format!("{} is not watched", register));
+            }
+            None
+        }
+        "r" | "registers" => {
+            print_registers(debugger);
+            None
+        }
+        "l" | "list" => {
+            let context = argument.map_or(Ok(DEFAULT_LIST_CONTEXT), |value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("not a valid count {:?}", value))
+            })?;
+            print_listing(debugger, context);
+            None
+        }
+        "o" | "output" => {
+            println!("{:?}", debugger.output());
+            None
+        }
+        "reset" => {
+            debugger.restart();
+            Some(StopReason::Stepped)
+        }
+        "h" | "help" => {
+            println!("{}", HELP);
+            None
+        }
+        "q" | "quit" => return Ok(true),
+        _ => return Err(format!("unknown command {:?}, type help for help", command)),
+    };
+    if let Some(reason) = reason {
+        let description = describe(reason);
+        if !description.is_empty() {
+            println!("{}", description);
+        }
+        print_listing(debugger, 0);
+    }
+    Ok(false)
+}
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return;
+    }
+    let options = parse_args(args).unwrap_or_else(|err| {
+        eprintln!("error: {}\n{}", err, USAGE);
+        process::exit(2)
+    });
+    let source = fs::read_to_string(&options.path).unwrap_or_else(|err| {
+        eprintln!("error: cannot read program: {}", err);
+        process::exit(1)
+    });
+    let program = ext::assemble(&source).unwrap_or_else(|err| {
+        eprintln!("error: invalid program: {}", err);
+        process::exit(1)
+    });
+    let mut debugger = Debugger::new(program);
+    debugger.set_limits(Limits::with_max_steps(options.max_steps));
+    println!(
+        "loaded {} instructions from {}, type help for help",
+        debugger.program().len(),
+        options.path
+    );
+    print_listing(&debugger, 0);
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("(day08) ");
+        io::stdout().flush().unwrap_or_default();
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(err)) => {
+                eprintln!("error: cannot read command: {}", err);
+                process::exit(1)
+            }
+            None => break,
+        };
+        match execute(&mut debugger, &line) {
+            Ok(true) => break,
+            Ok(false) => {}
+            Err(err) => println!("error: {}", err),
+        }
+    }
+}
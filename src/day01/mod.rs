@@ -38,23 +38,20 @@
 //!
 //! [Advent of Code 2020 - Day 1](https://adventofcode.com/2020/day/1)
 
-use std::str::FromStr;
+use crate::parse::{parse_number, ParseError};
 
-#[aoc_generator(day1)]
-pub fn parse(input: &str) -> Vec<i32> {
+pub fn try_parse(input: &str) -> Result<Vec<i32>, ParseError> {
     input
         .split_whitespace()
-        .map(|entry| {
-            i32::from_str(entry.trim()).unwrap_or_else(|err| {
-                panic!(
-                    "input str is not a valid i32: {:?}, reason: {:?}",
-                    entry, err
-                )
-            })
-        })
+        .map(|entry| parse_number(input, entry))
         .collect()
 }
 
+#[aoc_generator(day1)]
+pub fn parse(input: &str) -> Vec<i32> {
+    try_parse(input).unwrap_or_else(|err| panic!("{}", err))
+}
+
 pub fn find_two_incorrect_expenses(expense_report: &[i32]) -> (i32, i32) {
     expense_report
         .iter()
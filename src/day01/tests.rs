@@ -1,7 +1,20 @@
 use super::*;
+use crate::answers::recorded_answer;
 
 const INPUT: &str = include_str!("../../input/2020/day1.txt");
 
+mod try_parse {
+    use super::*;
+
+    #[test]
+    fn invalid_entry() {
+        let error = try_parse("1721\n979\n36x6\n").unwrap_err();
+
+        assert_eq!((error.line(), error.column()), (3, 1));
+        assert_eq!(error.snippet(), "36x6");
+    }
+}
+
 mod product_of_two_incorrect_expenses {
     use super::*;
 
@@ -37,7 +50,7 @@ mod part1 {
 
         let product = product_of_two_incorrect_expenses(&report);
 
-        assert_eq!(product, 788739);
+        assert_eq!(product.to_string(), recorded_answer(1, 1));
     }
 }
 
@@ -50,6 +63,6 @@ mod part2 {
 
         let product = product_of_three_incorrect_expenses(&report);
 
-        assert_eq!(product, 178724430);
+        assert_eq!(product.to_string(), recorded_answer(1, 2));
     }
 }
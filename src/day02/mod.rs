@@ -62,10 +62,8 @@
 //!
 //! [Advent of Code 2020 - Day 2](https://adventofcode.com/2020/day/2)
 
+use crate::parse::{end_of, parse_number, ParseError, ParseErrorKind};
 use std::cmp::Ordering;
-use std::iter::FromIterator;
-use std::mem;
-use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Policy {
@@ -74,71 +72,66 @@ pub struct Policy {
     character: char,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Parts {
-    Min,
-    Max,
-    Character,
-    Password,
+pub fn try_parse(input: &str) -> Result<Vec<(Policy, String)>, ParseError> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let colon = line.find(':').ok_or_else(|| {
+                ParseError::at(
+                    input,
+                    end_of(line),
+                    ParseErrorKind::Missing("':' after policy"),
+                )
+            })?;
+            let policy_str = &line[..colon];
+            let mut policy_parts = policy_str.split_whitespace();
+            let range = policy_parts.next().ok_or_else(|| {
+                ParseError::at(
+                    input,
+                    policy_str,
+                    ParseErrorKind::Missing("range of policy"),
+                )
+            })?;
+            let dash = range.find('-').ok_or_else(|| {
+                ParseError::at(input, range, ParseErrorKind::Missing("'-' in range"))
+            })?;
+            let min = parse_number(input, &range[..dash])?;
+            let max = parse_number(input, &range[dash + 1..])?;
+            let letter = policy_parts.next().ok_or_else(|| {
+                ParseError::at(
+                    input,
+                    end_of(policy_str),
+                    ParseErrorKind::Missing("character of policy"),
+                )
+            })?;
+            let mut letter_chars = letter.chars();
+            let character = match (letter_chars.next(), letter_chars.next()) {
+                (Some(character), None) => character,
+                _ => {
+                    return Err(ParseError::at(
+                        input,
+                        letter,
+                        ParseErrorKind::Invalid("character of policy"),
+                    ))
+                }
+            };
+            let password = line[colon + 1..].trim().to_string();
+            Ok((
+                Policy {
+                    min,
+                    max,
+                    character,
+                },
+                password,
+            ))
+        })
+        .collect()
 }
 
 #[aoc_generator(day2)]
 pub fn parse(input: &str) -> Vec<(Policy, String)> {
-    let mut policies = Vec::new();
-    let mut passwords = Vec::new();
-
-    let mut min = String::new();
-    let mut max = String::new();
-    let mut password = String::new();
-
-    let mut part = Parts::Min;
-    let mut characters = input.chars().chain("\n".chars());
-    while let Some(c) = characters.next() {
-        match c {
-            '-' => part = Parts::Max,
-            ':' => part = Parts::Password,
-            '\n' => {
-                passwords.push(mem::replace(&mut password, String::new()));
-                part = Parts::Min;
-            }
-            _ => match part {
-                Parts::Min => {
-                    if c.is_ascii_digit() {
-                        min.push(c)
-                    }
-                }
-                Parts::Max => {
-                    if c.is_ascii_digit() {
-                        max.push(c)
-                    } else if c.is_whitespace() {
-                        part = Parts::Character;
-                    }
-                }
-                Parts::Character => {
-                    if !c.is_whitespace() {
-                        policies.push(Policy {
-                            min: usize::from_str(&min).unwrap_or_else(|err| {
-                                panic!("not a valid min value {:?}, reason: {:?}", min, err)
-                            }),
-                            max: usize::from_str(&max).unwrap_or_else(|err| {
-                                panic!("no a valid max value {:?}, reason: {:?}", max, err)
-                            }),
-                            character: c,
-                        });
-                        min.clear();
-                        max.clear();
-                        part = Parts::Password;
-                    }
-                }
-                Parts::Password => {
-                    if !c.is_whitespace() {
-                        password.push(c);
-                    }
-                }
-            },
-        }
-    }
-    Vec::from_iter(policies.into_iter().zip(passwords.into_iter()))
+    try_parse(input).unwrap_or_else(|err| panic!("{}", err))
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
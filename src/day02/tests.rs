@@ -1,4 +1,5 @@
 use super::*;
+use crate::answers::recorded_answer;
 
 const INPUT: &str = include_str!("../../input/2020/day2.txt");
 
@@ -50,6 +51,17 @@ mod parse_input {
 
         assert_eq!(entries.len(), 1000);
     }
+
+    #[test]
+    fn try_parse_policy_without_character() {
+        let error = try_parse("1-3 a: abcde\n2-9 : ccccccccc").unwrap_err();
+
+        assert_eq!((error.line(), error.column()), (2, 5));
+        assert_eq!(
+            error.kind(),
+            &ParseErrorKind::Missing("character of policy")
+        );
+    }
 }
 
 mod part1 {
@@ -66,7 +78,7 @@ mod part1 {
     fn answer() {
         let answer = count_valid_passwords(&parse(INPUT));
 
-        assert_eq!(answer, 467);
+        assert_eq!(answer.to_string(), recorded_answer(2, 1));
     }
 }
 
@@ -84,6 +96,6 @@ mod part2 {
     fn answer() {
         let answer = count_valid_passwords_part2(&parse(INPUT));
 
-        assert_eq!(answer, 441);
+        assert_eq!(answer.to_string(), recorded_answer(2, 2));
     }
 }
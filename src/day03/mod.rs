@@ -98,7 +98,13 @@
 //!
 //! [Advent of Code 2020 - Day 3](https://adventofcode.com/2020/day/3)
 
-use std::mem;
+use crate::grid::Grid;
+use crate::parse::{ParseError, ParseErrorKind};
+use std::convert::TryFrom;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+pub use crate::grid::Position;
 
 pub const O: Tile = Tile::Open;
 pub const X: Tile = Tile::Tree;
@@ -109,25 +115,41 @@ pub enum Tile {
     Tree,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Position {
-    pub row: usize,
-    pub col: usize,
+impl TryFrom<char> for Tile {
+    type Error = ParseErrorKind;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Tile::Open),
+            '#' => Ok(Tile::Tree),
+            _ => Err(ParseErrorKind::UnexpectedCharacter(value)),
+        }
+    }
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tile::Open => f.write_str("."),
+            Tile::Tree => f.write_str("#"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Map {
-    tiles: Vec<Vec<Tile>>,
-    width: usize,
+    tiles: Grid<Tile>,
 }
 
 impl Map {
     pub fn height(&self) -> usize {
-        self.tiles.len()
+        self.tiles.height()
     }
 
+    /// Returns the tile at the given position, where the pattern of the map
+    /// repeats to the right.
     pub fn tile(&self, position: Position) -> Tile {
-        self.tiles[position.row][position.col % self.width]
+        *self.tiles.get_wrapping(position)
     }
 
     pub fn walk(&self, slope: Slope, start: Position) -> Walker<'_> {
@@ -146,35 +168,13 @@ pub struct Slope {
     pub down: usize,
 }
 
+pub fn try_parse(input: &str) -> Result<Map, ParseError> {
+    Grid::from_str(input).map(|tiles| Map { tiles })
+}
+
 #[aoc_generator(day3)]
 pub fn parse(input: &str) -> Map {
-    let mut map_tiles = Vec::with_capacity(8);
-    let mut row_tiles = Vec::with_capacity(8);
-    for c in input.chars().chain("\n".chars()) {
-        match c {
-            '.' => {
-                row_tiles.push(Tile::Open);
-            }
-            '#' => {
-                row_tiles.push(Tile::Tree);
-            }
-            '\n' => {
-                if !row_tiles.is_empty() {
-                    map_tiles.push(mem::replace(&mut row_tiles, Vec::with_capacity(8)));
-                }
-            }
-            _ => {
-                if !c.is_whitespace() {
-                    panic!("unrecognized character {:?} in input", c);
-                }
-            }
-        }
-    }
-    let width = map_tiles.get(0).map(|row| row.len()).unwrap_or(0);
-    Map {
-        tiles: map_tiles,
-        width,
-    }
+    try_parse(input).unwrap_or_else(|err| panic!("{}", err))
 }
 
 #[derive(Debug)]
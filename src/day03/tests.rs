@@ -1,4 +1,5 @@
 use super::*;
+use crate::answers::recorded_answer;
 
 const INPUT: &str = include_str!("../../input/2020/day3.txt");
 
@@ -21,8 +22,8 @@ fn parse_example_input() {
     let map = parse(EXAMPLE);
 
     assert_eq!(
-        &map.tiles,
-        &vec![
+        map.tiles,
+        Grid::from_rows(vec![
             vec![O, O, X, X, O, O, O, O, O, O, O],
             vec![X, O, O, O, X, O, O, O, X, O, O],
             vec![O, X, O, O, O, O, X, O, O, X, O],
@@ -34,7 +35,7 @@ fn parse_example_input() {
             vec![X, O, X, X, O, O, O, X, O, O, O],
             vec![X, O, O, O, X, X, O, O, O, O, X],
             vec![O, X, O, O, X, O, O, O, X, O, X],
-        ]
+        ])
     );
 }
 
@@ -42,8 +43,16 @@ fn parse_example_input() {
 fn parse_puzzle_input() {
     let map = parse(INPUT);
 
-    assert_eq!(map.tiles.len(), 323);
-    assert_eq!(map.tiles[0].len(), 31);
+    assert_eq!(map.tiles.height(), 323);
+    assert_eq!(map.tiles.width(), 31);
+}
+
+#[test]
+fn try_parse_map_with_unrecognized_tile() {
+    let error = try_parse("..##.\n#..o.\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (2, 4));
+    assert_eq!(error.kind(), &ParseErrorKind::UnexpectedCharacter('o'));
 }
 
 #[test]
@@ -61,7 +70,7 @@ fn count_trees_on_slope_r3d1_on_input_map() {
 
     let num_trees = count_trees_on_slope_r3d1(&map);
 
-    assert_eq!(num_trees, 209);
+    assert_eq!(num_trees.to_string(), recorded_answer(3, 1));
 }
 
 #[test]
@@ -79,5 +88,5 @@ fn product_of_trees_on_multiple_slopes_on_input_map() {
 
     let num_trees = product_of_trees_on_multiple_slopes(&map);
 
-    assert_eq!(num_trees, 1574890240);
+    assert_eq!(num_trees.to_string(), recorded_answer(3, 2));
 }
@@ -154,6 +154,7 @@
 //!
 //! [Advent of Code 2020 - Day 4](https://adventofcode.com/2020/day/4)
 
+use crate::parse::{ParseError, ParseErrorKind};
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::convert::TryFrom;
@@ -262,57 +263,32 @@ pub struct Passport {
     cid: String,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Token {
-    Key,
-    Value,
-}
-
-#[aoc_generator(day4)]
-pub fn parse(input: &str) -> Vec<HashMap<String, String>> {
+pub fn try_parse(input: &str) -> Result<Vec<HashMap<String, String>>, ParseError> {
     let mut records = Vec::new();
     let mut entry = HashMap::new();
-    let mut key = String::new();
-    let mut value = String::new();
-    let mut token = Token::Key;
-    let mut new_lines = 0;
-
-    for c in input.chars().chain("\n\n".chars()) {
-        match c {
-            ':' => token = Token::Value,
-            '\n' => {
-                if token == Token::Value {
-                    entry.insert(
-                        mem::replace(&mut key, String::new()),
-                        mem::replace(&mut value, String::new()),
-                    );
-                    token = Token::Key;
-                }
-                new_lines += 1;
-                if new_lines >= 2 {
-                    new_lines = 0;
-                    records.push(mem::replace(&mut entry, HashMap::new()));
-                }
-            }
-            _ if c.is_whitespace() => {
-                if token == Token::Value {
-                    entry.insert(
-                        mem::replace(&mut key, String::new()),
-                        mem::replace(&mut value, String::new()),
-                    );
-                }
-                token = Token::Key;
-            }
-            _ => {
-                match token {
-                    Token::Key => key.push(c),
-                    Token::Value => value.push(c),
-                }
-                new_lines = 0;
+    for line in input.lines() {
+        if line.trim().is_empty() {
+            if !entry.is_empty() {
+                records.push(mem::replace(&mut entry, HashMap::new()));
             }
+            continue;
         }
+        for field in line.split_whitespace() {
+            let colon = field.find(':').ok_or_else(|| {
+                ParseError::at(input, field, ParseErrorKind::Missing("':' in field"))
+            })?;
+            entry.insert(field[..colon].to_string(), field[colon + 1..].to_string());
+        }
+    }
+    if !entry.is_empty() {
+        records.push(entry);
     }
-    records
+    Ok(records)
+}
+
+#[aoc_generator(day4)]
+pub fn parse(input: &str) -> Vec<HashMap<String, String>> {
+    try_parse(input).unwrap_or_else(|err| panic!("{}", err))
 }
 
 fn validate_required_fields(passport: &HashMap<String, String>) -> bool {
@@ -1,4 +1,5 @@
 use super::*;
+use crate::answers::recorded_answer;
 
 const INPUT: &str = include_str!("../../input/2020/day4.txt");
 
@@ -63,6 +64,14 @@ fn parse_example_input() {
     assert_eq!(record.get("cid"), None);
 }
 
+#[test]
+fn try_parse_field_without_value() {
+    let error = try_parse("ecl:gry pid:860033327\nbyr:1937 hcl\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (2, 10));
+    assert_eq!(error.snippet(), "hcl");
+}
+
 #[test]
 fn count_valid_passports_in_example_input() {
     let count = count_valid_passports(&parse(EXAMPLE));
@@ -74,12 +83,12 @@ fn count_valid_passports_in_example_input() {
 fn count_valid_passports_in_puzzle_input() {
     let count = count_valid_passports(&parse(INPUT));
 
-    assert_eq!(count, 210);
+    assert_eq!(count.to_string(), recorded_answer(4, 1));
 }
 
 #[test]
 fn count_valid_passports2_in_puzzle_input() {
     let count = count_valid_passports2(&parse(INPUT));
 
-    assert_eq!(count, 131);
+    assert_eq!(count.to_string(), recorded_answer(4, 2));
 }
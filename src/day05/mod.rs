@@ -75,6 +75,7 @@
 //! [Advent of Code 2020 - Day 5](https://adventofcode.com/2020/day/5)
 //! [zones or groups](https://www.youtube.com/watch?v=oAHbLRjF0vo)
 
+use crate::parse::{ParseError, ParseErrorKind};
 use std::convert::TryFrom;
 
 const NUM_ROWS_ON_PLANE: usize = 128;
@@ -242,15 +243,44 @@ fn seat_on_boarding_pass(boarding_pass: &str) -> Result<Seat, PartitionError> {
     seat_position(boarding_pass).map(Seat::from)
 }
 
-#[aoc_generator(day5)]
-pub fn parse_boarding_passes(input: &str) -> Vec<String> {
+pub fn try_parse_boarding_passes(input: &str) -> Result<Vec<String>, ParseError> {
     input
-        .split('\n')
-        .filter(|bp| bp.len() == 10)
-        .map(String::from)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            for (index, c) in line.char_indices() {
+                let recognized = if index < 7 {
+                    RowPart::try_from(c).is_ok()
+                } else {
+                    ColumnPart::try_from(c).is_ok()
+                };
+                if !recognized {
+                    return Err(ParseError::at(
+                        input,
+                        &line[index..index + c.len_utf8()],
+                        ParseErrorKind::UnexpectedCharacter(c),
+                    ));
+                }
+            }
+            if line.len() == 10 {
+                Ok(line.to_string())
+            } else {
+                Err(ParseError::at(
+                    input,
+                    line,
+                    ParseErrorKind::Invalid("boarding pass"),
+                ))
+            }
+        })
         .collect()
 }
 
+#[aoc_generator(day5)]
+pub fn parse_boarding_passes(input: &str) -> Vec<String> {
+    try_parse_boarding_passes(input).unwrap_or_else(|err| panic!("{}", err))
+}
+
 #[aoc(day5, part1)]
 pub fn highest_seat_id_on_a_boarding_pass(boarding_passes: &[String]) -> usize {
     boarding_passes
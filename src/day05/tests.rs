@@ -1,4 +1,5 @@
 use super::*;
+use crate::answers::recorded_answer;
 
 const INPUT: &str = include_str!("../../input/2020/day5.txt");
 
@@ -9,6 +10,14 @@ fn parse_input() {
     assert_eq!(boarding_passes.len(), 826);
 }
 
+#[test]
+fn try_parse_boarding_pass_with_column_in_row_code() {
+    let error = try_parse_boarding_passes("BFFFBBFRRR\nFFFBRBBRRR\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (2, 5));
+    assert_eq!(error.kind(), &ParseErrorKind::UnexpectedCharacter('R'));
+}
+
 #[test]
 fn seat_id_of_example1() {
     let seat = seat_on_boarding_pass("FBFBBFFRLR").unwrap();
@@ -41,12 +50,12 @@ fn seat_id_of_example4() {
 fn highest_seat_id_of_puzzle_input() {
     let seat_id = highest_seat_id_on_a_boarding_pass(&parse_boarding_passes(INPUT));
 
-    assert_eq!(seat_id, 874);
+    assert_eq!(seat_id.to_string(), recorded_answer(5, 1));
 }
 
 #[test]
 fn find_free_seat_in_puzzle_input() {
     let free_seat = find_free_seat(&parse_boarding_passes(INPUT));
 
-    assert_eq!(free_seat, 594);
+    assert_eq!(free_seat.to_string(), recorded_answer(5, 2));
 }
@@ -110,7 +110,9 @@
 //!
 //! [Advent of Code 2020 - Day 6](https://adventofcode.com/2020/day/6)
 
+use crate::parse::{ParseError, ParseErrorKind};
 use std::collections::{HashMap, HashSet};
+use std::iter;
 use std::iter::FromIterator;
 use std::mem;
 
@@ -122,27 +124,38 @@ pub struct Group {
     compliance_list: Vec<Vec<Compliance>>,
 }
 
-#[aoc_generator(day6)]
-pub fn parse_compliance_list(input: &str) -> Vec<Group> {
+pub fn try_parse_compliance_list(input: &str) -> Result<Vec<Group>, ParseError> {
     let mut groups = Vec::new();
     let mut compliance_list = Vec::new();
-    let mut answers = Vec::new();
-    for c in input.chars().chain("\n\n".chars()) {
-        match c {
-            '\n' => {
-                if answers.is_empty() && !compliance_list.is_empty() {
-                    groups.push(Group {
-                        compliance_list: mem::replace(&mut compliance_list, Vec::new()),
-                    });
-                } else {
-                    compliance_list.push(mem::replace(&mut answers, Vec::new()));
+    for line in input.lines().chain(iter::once("")) {
+        let mut answers = Vec::new();
+        for (index, c) in line.char_indices() {
+            match c {
+                'a'..='z' => answers.push(Compliance(c)),
+                _ if c.is_whitespace() => {}
+                _ => {
+                    return Err(ParseError::at(
+                        input,
+                        &line[index..index + c.len_utf8()],
+                        ParseErrorKind::UnexpectedCharacter(c),
+                    ))
                 }
             }
-            _ if c.is_whitespace() => {}
-            _ => answers.push(Compliance(c)),
+        }
+        if !answers.is_empty() {
+            compliance_list.push(answers);
+        } else if !compliance_list.is_empty() {
+            groups.push(Group {
+                compliance_list: mem::replace(&mut compliance_list, Vec::new()),
+            });
         }
     }
-    groups
+    Ok(groups)
+}
+
+#[aoc_generator(day6)]
+pub fn parse_compliance_list(input: &str) -> Vec<Group> {
+    try_parse_compliance_list(input).unwrap_or_else(|err| panic!("{}", err))
 }
 
 fn distinct_answers(compliance_list: &[Vec<Compliance>]) -> HashSet<Compliance> {
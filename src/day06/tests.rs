@@ -1,4 +1,5 @@
 use super::*;
+use crate::answers::recorded_answer;
 
 const INPUT: &str = include_str!("../../input/2020/day6.txt");
 
@@ -58,6 +59,14 @@ fn parse_compliance_list_of_example() {
     );
 }
 
+#[test]
+fn try_parse_compliance_list_with_invalid_answer() {
+    let error = try_parse_compliance_list("abc\n\na\nB\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (4, 1));
+    assert_eq!(error.kind(), &ParseErrorKind::UnexpectedCharacter('B'));
+}
+
 #[test]
 fn sum_of_anyone_is_compliant_in_example() {
     let sum = sum_of_anyone_is_compliant(&parse_compliance_list(EXAMPLE));
@@ -69,7 +78,7 @@ fn sum_of_anyone_is_compliant_in_example() {
 fn sum_of_anyone_is_compliant_in_puzzle_input() {
     let sum = sum_of_anyone_is_compliant(&parse_compliance_list(INPUT));
 
-    assert_eq!(sum, 7283);
+    assert_eq!(sum.to_string(), recorded_answer(6, 1));
 }
 
 #[test]
@@ -83,5 +92,5 @@ fn sum_of_everyone_is_compliant_in_example() {
 fn sum_of_everyone_is_compliant_in_puzzle_input() {
     let sum = sum_of_everyone_is_compliant(&parse_compliance_list(INPUT));
 
-    assert_eq!(sum, 3520);
+    assert_eq!(sum.to_string(), recorded_answer(6, 2));
 }
@@ -85,9 +85,9 @@
 //!
 //! [Advent of Code 2020 - Day 7](https://adventofcode.com/2020/day/7)
 
+use crate::parse::{end_of, parse_number, ParseError, ParseErrorKind};
 use std::collections::HashMap;
 use std::iter::FromIterator;
-use std::str::FromStr;
 use std::{fmt, mem};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -135,55 +135,45 @@ pub struct Content<'a> {
     quantity: usize,
 }
 
-#[aoc_generator(day7)]
-pub fn parse_bag_rules(input: &str) -> Vec<Bag<'_>> {
+fn parse_color_code<'a>(input: &str, s: &'a str) -> Result<ColorCode<'a>, ParseError> {
+    ColorCode::from_str(s)
+        .map_err(|_| ParseError::at(input, s, ParseErrorKind::Invalid("color code")))
+}
+
+pub fn try_parse_bag_rules(input: &str) -> Result<Vec<Bag<'_>>, ParseError> {
     let mut bag_rules = Vec::with_capacity(8);
     let mut content_list = Vec::new();
-    for (line_index, line) in input.split(".\n").enumerate() {
-        if line.is_empty() {
+    for line in input.split(".\n") {
+        if line.trim().is_empty() {
             continue;
         }
         let mut parts = line.split("bags contain");
-        let color_code = parts.next().expect("rule is empty").trim();
-        let rule_color = ColorCode::from_str(color_code.trim())
-            .unwrap_or_else(|err| panic!("line {}: invalid color code: {:?}", line_index + 1, err));
-        let contents = parts
-            .next()
-            .unwrap_or_else(|| panic!("line {}: no content in rule: {:?}", line_index + 1, line));
+        let rule_color = parse_color_code(input, parts.next().unwrap_or(line))?;
+        let contents = parts.next().ok_or_else(|| {
+            ParseError::at(
+                input,
+                end_of(line),
+                ParseErrorKind::Missing("\"bags contain\""),
+            )
+        })?;
         if !contents.contains("no other bags") {
             for content in contents.split(',') {
                 let number_index_start =
-                    content
-                        .find(|c: char| c.is_ascii_digit())
-                        .unwrap_or_else(|| {
-                            panic!(
-                                "line {}: no quantity in rule: {:?}",
-                                line_index + 1,
-                                content
-                            )
-                        });
+                    content.find(|c: char| c.is_ascii_digit()).ok_or_else(|| {
+                        ParseError::at(input, content, ParseErrorKind::Missing("quantity"))
+                    })?;
                 let number_index_end = number_index_start
                     + content[number_index_start..]
                         .find(char::is_whitespace)
-                        .unwrap();
-                let quantity = usize::from_str(&content[number_index_start..number_index_end])
-                    .unwrap_or_else(|err| {
-                        panic!(
-                            "line {}: not a number: {:?}; reason: {:?}",
-                            line_index + 1,
-                            &content[number_index_start..number_index_end],
-                            err
-                        )
-                    });
-                let color_code = ColorCode::from_str(&content[number_index_end + 1..])
-                    .unwrap_or_else(|err| {
-                        panic!(
-                            "line {}: not a color code: {:?}; reason: {:?}",
-                            line_index + 1,
-                            &content[number_index_end + 1..],
-                            err
-                        )
-                    });
+                        .ok_or_else(|| {
+                            ParseError::at(
+                                input,
+                                end_of(content),
+                                ParseErrorKind::Missing("color code"),
+                            )
+                        })?;
+                let quantity = parse_number(input, &content[number_index_start..number_index_end])?;
+                let color_code = parse_color_code(input, &content[number_index_end + 1..])?;
                 content_list.push(Content {
                     color_code,
                     quantity,
@@ -195,7 +185,11 @@ pub fn parse_bag_rules(input: &str) -> Vec<Bag<'_>> {
             contents: mem::replace(&mut content_list, Vec::new()),
         })
     }
-    bag_rules
+    Ok(bag_rules)
+}
+
+pub fn parse_bag_rules(input: &str) -> Vec<Bag<'_>> {
+    try_parse_bag_rules(input).unwrap_or_else(|err| panic!("{}", err))
 }
 
 impl<'a> Bag<'a> {
@@ -238,7 +232,6 @@ const SHINY_GOLD: ColorCode<'_> = ColorCode {
     color: "gold",
 };
 
-//#[aoc(day7, part1)]
 pub fn number_of_bag_colors_containing_shiny_gold_bags(bag_rules: &[Bag<'_>]) -> usize {
     let color_map: HashMap<ColorCode<'_>, &Vec<Content<'_>>> = HashMap::from_iter(
         bag_rules
@@ -271,7 +264,6 @@ fn number_of_bags_inside(
     }
 }
 
-//#[aoc(day7, part2)]
 pub fn number_of_bags_inside_shiny_gold_bag(bag_rules: &[Bag<'_>]) -> usize {
     let color_map: HashMap<ColorCode<'_>, &Vec<Content<'_>>> = HashMap::from_iter(
         bag_rules
@@ -281,5 +273,19 @@ pub fn number_of_bags_inside_shiny_gold_bag(bag_rules: &[Bag<'_>]) -> usize {
     number_of_bags_inside(1, SHINY_GOLD, &color_map) - 1
 }
 
+// The runner can not hold generated input that borrows from the puzzle input,
+// like the parsed bag rules do. So the solvers registered for the runner take
+// the puzzle input and parse the bag rules themselves.
+
+#[aoc(day7, part1)]
+pub fn part1_number_of_bag_colors_containing_shiny_gold_bags(input: &str) -> usize {
+    number_of_bag_colors_containing_shiny_gold_bags(&parse_bag_rules(input))
+}
+
+#[aoc(day7, part2)]
+pub fn part2_number_of_bags_inside_shiny_gold_bag(input: &str) -> usize {
+    number_of_bags_inside_shiny_gold_bag(&parse_bag_rules(input))
+}
+
 #[cfg(test)]
 mod tests;
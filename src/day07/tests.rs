@@ -1,4 +1,5 @@
 use super::*;
+use crate::answers::recorded_answer;
 
 const INPUT: &str = include_str!("../../input/2020/day7.txt");
 
@@ -201,6 +202,17 @@ fn parse_bag_rules_from_puzzle_input() {
     assert_eq!(bag_rules.len(), 594);
 }
 
+#[test]
+fn try_parse_bag_rules_with_missing_quantity() {
+    let error = try_parse_bag_rules(
+        "bright white bags contain 1 shiny gold bag.\nmuted yellow bags contain shiny gold bags.\n",
+    )
+    .unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (2, 26));
+    assert_eq!(error.kind(), &ParseErrorKind::Missing("quantity"));
+}
+
 #[test]
 fn number_of_bag_colors_containing_shiny_gold_bags_example1() {
     let num_bag_colors =
@@ -213,7 +225,7 @@ fn number_of_bag_colors_containing_shiny_gold_bags_example1() {
 fn number_of_bag_colors_containing_shiny_gold_bags_puzzle_input() {
     let num_bag_colors = number_of_bag_colors_containing_shiny_gold_bags(&parse_bag_rules(INPUT));
 
-    assert_eq!(num_bag_colors, 261);
+    assert_eq!(num_bag_colors.to_string(), recorded_answer(7, 1));
 }
 
 #[test]
@@ -234,5 +246,19 @@ fn number_of_bags_inside_shiny_gold_bag_example2() {
 fn number_of_bags_inside_shiny_gold_bag_puzzle_input() {
     let num_bags = number_of_bags_inside_shiny_gold_bag(&parse_bag_rules(INPUT));
 
-    assert_eq!(num_bags, 3765);
+    assert_eq!(num_bags.to_string(), recorded_answer(7, 2));
+}
+
+#[test]
+fn part1_number_of_bag_colors_containing_shiny_gold_bags_puzzle_input() {
+    let num_bag_colors = part1_number_of_bag_colors_containing_shiny_gold_bags(INPUT);
+
+    assert_eq!(num_bag_colors.to_string(), recorded_answer(7, 1));
+}
+
+#[test]
+fn part2_number_of_bags_inside_shiny_gold_bag_puzzle_input() {
+    let num_bags = part2_number_of_bags_inside_shiny_gold_bag(INPUT);
+
+    assert_eq!(num_bags.to_string(), recorded_answer(7, 2));
 }
@@ -0,0 +1,158 @@
+//! Assembler for boot code with comments and labels.
+//!
+//! Besides the plain instructions of the puzzle input the assembler accepts
+//! blank lines, comments from `;` to the end of the line and labels. A label
+//! is defined by its name followed by `:` at the start of a line, optionally
+//! followed by an instruction, and stands for the address of the next
+//! instruction. An argument naming a label is resolved to the offset from the
+//! instruction to that address, so `jmp loop_start` jumps to the label.
+//!
+//! ```text
+//! ; count down from 3
+//!             acc +3
+//! loop_start: acc -1
+//!             jmp loop_start
+//! ```
+
+use super::{Address, Data, Instruction, OperationCode, Program};
+use crate::parse::{end_of, parse_number, ParseError, ParseErrorKind};
+use hashbrown::HashMap;
+use std::str::FromStr;
+
+/// Starts a comment that extends to the end of the line.
+pub const COMMENT: char = ';';
+
+/// Returns whether `name` is a valid label name: a letter or underscore
+/// followed by letters, digits and underscores.
+pub fn is_label(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// An instruction that the assembler can assemble.
+pub trait Assemble: Sized {
+    /// Assembles the instruction from `code`, the part of a line after any
+    /// labels and before any comment.
+    fn assemble(code: &str, resolver: &Resolver<'_>) -> Result<Self, ParseError>;
+}
+
+/// Resolves the arguments of the instruction at an address.
+#[derive(Debug)]
+pub struct Resolver<'a> {
+    source: &'a str,
+    labels: &'a HashMap<&'a str, Address>,
+    address: Address,
+}
+
+impl Resolver<'_> {
+    /// Returns the whole source code, which errors have to point into.
+    pub fn source(&self) -> &str {
+        self.source
+    }
+
+    /// Parses the token as a number or, if it names a label, resolves it to
+    /// the offset from the instruction to the label.
+    pub fn argument(&self, token: &str) -> Result<Data, ParseError> {
+        if is_label(token) {
+            let target = self.labels.get(token).ok_or_else(|| {
+                ParseError::at(
+                    self.source,
+                    token,
+                    ParseErrorKind::Missing("definition of label"),
+                )
+            })?;
+            Ok(*target as Data - self.address as Data)
+        } else {
+            parse_number(self.source, token)
+        }
+    }
+
+    /// Fails on the first of the remaining tokens of an instruction, if any.
+    pub fn expect_end<'t>(
+        &self,
+        mut tokens: impl Iterator<Item = &'t str>,
+    ) -> Result<(), ParseError> {
+        match tokens.next() {
+            Some(extra) => Err(ParseError::at(
+                self.source,
+                extra,
+                ParseErrorKind::UnexpectedCharacter(extra.chars().next().unwrap_or_default()),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Assemble for Instruction {
+    fn assemble(code: &str, resolver: &Resolver<'_>) -> Result<Self, ParseError> {
+        let source = resolver.source();
+        let mut parts = code.split_whitespace();
+        let opcode = parts.next().ok_or_else(|| {
+            ParseError::at(source, code, ParseErrorKind::Missing("operation code"))
+        })?;
+        let operation = OperationCode::from_str(opcode).map_err(|_| {
+            ParseError::at(source, opcode, ParseErrorKind::Invalid("operation code"))
+        })?;
+        let arg = parts.next().ok_or_else(|| {
+            ParseError::at(source, end_of(code), ParseErrorKind::Missing("argument"))
+        })?;
+        resolver.expect_end(parts)?;
+        Ok(Instruction {
+            operation,
+            argument: resolver.argument(arg)?,
+        })
+    }
+}
+
+/// Assembles the source into a program of boot code, resolving labels to
+/// relative offsets.
+pub fn assemble(source: &str) -> Result<Program, ParseError> {
+    assemble_as(source)
+}
+
+/// Assembles the source into a program of any instruction set.
+pub fn assemble_as<I: Assemble>(source: &str) -> Result<Program<I>, ParseError> {
+    let mut labels = HashMap::new();
+    let mut statements = Vec::with_capacity(source.len() / 8);
+    for line in source.lines() {
+        let mut code = line
+            .find(COMMENT)
+            .map_or(line, |comment| &line[..comment])
+            .trim();
+        while let Some(colon) = code.find(':') {
+            let name = code[..colon].trim_end();
+            if !is_label(name) {
+                return Err(ParseError::at(
+                    source,
+                    name,
+                    ParseErrorKind::Invalid("label"),
+                ));
+            }
+            if labels.insert(name, statements.len()).is_some() {
+                return Err(ParseError::at(
+                    source,
+                    name,
+                    ParseErrorKind::Invalid("label, it is already defined"),
+                ));
+            }
+            code = code[colon + 1..].trim_start();
+        }
+        if !code.is_empty() {
+            statements.push(code);
+        }
+    }
+
+    let mut instructions = Vec::with_capacity(statements.len());
+    for (address, code) in statements.into_iter().enumerate() {
+        let resolver = Resolver {
+            source,
+            labels: &labels,
+            address,
+        };
+        instructions.push(I::assemble(code, &resolver)?);
+    }
+    Ok(Program::new(instructions))
+}
@@ -0,0 +1,164 @@
+//! Control flow graph of a program for repairing its endless loop in linear
+//! time.
+//!
+//! Every instruction has a single successor: the next instruction for `acc`
+//! and `nop` and the jump target for `jmp`. The program terminates if the
+//! successors starting at address 0 lead to the address immediately after
+//! the last instruction. Walking the predecessors backwards from there yields
+//! all addresses that terminate. The repair is the single `jmp` or `nop` on
+//! the original execution path whose flipped successor is one of them.
+
+use super::{Address, Data, Instruction, OperationCode, Program};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ControlFlowGraph {
+    /// The successor of each address, `None` for jumps out of bounds. The
+    /// address `len` stands for termination and has no successor.
+    successors: Vec<Option<Address>>,
+    /// The predecessors of address `a` are
+    /// `predecessors[starts[a]..starts[a + 1]]`.
+    starts: Vec<usize>,
+    predecessors: Vec<Address>,
+}
+
+/// Returns the address `offset` away from `address` or `None` if it is
+/// outside of the program and not the address immediately after it, which
+/// includes offsets that overflow.
+fn offset_address(address: Address, offset: Data, len: usize) -> Option<Address> {
+    let target = (address as Data).checked_add(offset)?;
+    if target < 0 || target > len as Data {
+        None
+    } else {
+        Some(target as Address)
+    }
+}
+
+fn successor(address: Address, instruction: Instruction, len: usize) -> Option<Address> {
+    match instruction.operation {
+        OperationCode::Acc | OperationCode::Nop => Some(address + 1),
+        OperationCode::Jmp => offset_address(address, instruction.argument, len),
+    }
+}
+
+impl ControlFlowGraph {
+    pub fn new(program: &Program) -> Self {
+        let len = program.len();
+        let successors = program
+            .instructions()
+            .enumerate()
+            .map(|(address, instruction)| successor(address, *instruction, len))
+            .collect::<Vec<_>>();
+
+        let mut starts = vec![0; len + 3];
+        for target in successors.iter().flatten() {
+            starts[target + 2] += 1;
+        }
+        for address in 2..starts.len() {
+            starts[address] += starts[address - 1];
+        }
+        let mut predecessors = vec![0; starts[len + 2]];
+        for (address, target) in successors.iter().enumerate() {
+            if let Some(target) = target {
+                predecessors[starts[target + 1]] = address;
+                starts[target + 1] += 1;
+            }
+        }
+
+        Self {
+            successors,
+            starts,
+            predecessors,
+        }
+    }
+
+    /// Returns the number of instructions, which is also the address that
+    /// stands for termination.
+    pub fn len(&self) -> usize {
+        self.successors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.successors.is_empty()
+    }
+
+    /// Returns the address run after the given one or `None` if it jumps out
+    /// of bounds.
+    pub fn successor(&self, address: Address) -> Option<Address> {
+        self.successors.get(address).copied().flatten()
+    }
+
+    /// Returns the addresses run immediately before the given one.
+    pub fn predecessors(&self, address: Address) -> &[Address] {
+        &self.predecessors[self.starts[address]..self.starts[address + 1]]
+    }
+
+    /// Returns for every address up to and including `len` whether running
+    /// the program from there terminates.
+    pub fn terminating(&self) -> Vec<bool> {
+        let mut terminating = vec![false; self.len() + 1];
+        terminating[self.len()] = true;
+        let mut pending = vec![self.len()];
+        while let Some(address) = pending.pop() {
+            for predecessor in self.predecessors(address) {
+                if !terminating[*predecessor] {
+                    terminating[*predecessor] = true;
+                    pending.push(*predecessor);
+                }
+            }
+        }
+        terminating
+    }
+
+    /// Returns the addresses run from address 0 until an address is about to
+    /// be run a second time, the program terminates or jumps out of bounds.
+    pub fn execution_path(&self) -> Vec<Address> {
+        let mut visited = vec![false; self.len()];
+        let mut path = Vec::new();
+        let mut address = Some(0);
+        while let Some(current) = address.filter(|current| *current < self.len()) {
+            if visited[current] {
+                break;
+            }
+            visited[current] = true;
+            path.push(current);
+            address = self.successor(current);
+        }
+        path
+    }
+}
+
+/// An instruction to change to make the program terminate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repair {
+    pub address: Address,
+    pub original: OperationCode,
+    pub patched: OperationCode,
+}
+
+/// Finds the first `jmp` or `nop` on the execution path of the program that
+/// makes the program terminate when flipped.
+///
+/// Returns `None` if there is no such instruction. This runs in time linear
+/// in the length of the program.
+pub fn find_repair(program: &Program) -> Option<Repair> {
+    let cfg = ControlFlowGraph::new(program);
+    let terminating = cfg.terminating();
+    cfg.execution_path().into_iter().find_map(|address| {
+        let instruction = program[address];
+        let (patched, target) = match instruction.operation {
+            OperationCode::Acc => return None,
+            OperationCode::Jmp => (OperationCode::Nop, Some(address + 1)),
+            OperationCode::Nop => (
+                OperationCode::Jmp,
+                offset_address(address, instruction.argument, cfg.len()),
+            ),
+        };
+        target
+            .filter(|target| terminating[*target])
+            .map(|_| Repair {
+                address,
+                original: instruction.operation,
+                patched,
+            })
+    })
+}
@@ -0,0 +1,224 @@
+//! Step debugger for programs of the virtual machine.
+//!
+//! The [`Debugger`] runs a program one instruction at a time and stops at
+//! breakpoints on addresses, at watchpoints on registers, at a cursor, when
+//! the program is about to loop forever or when it ends. It records the
+//! registers before every instruction, so steps can be undone again.
+
+use super::{
+    Address, Continuation, Data, Instruction, InstructionSet, Limits, Program, Register, Registers,
+    VirtualMachine,
+};
+use hashbrown::HashSet;
+use std::collections::BTreeSet;
+
+/// Why the debugger stopped running the program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// A single instruction was run or undone.
+    Stepped,
+    /// The program is about to run the instruction at a breakpoint.
+    Breakpoint(Address),
+    /// The program is about to run the instruction at the cursor.
+    Cursor(Address),
+    /// The last instruction changed a watched register.
+    Watchpoint {
+        register: Register,
+        old: Data,
+        new: Data,
+    },
+    /// The program is about to run the instruction at the address a second
+    /// time, with the same registers for programs with conditional jumps.
+    InfiniteLoop(Address),
+    /// The program attempted to run the instruction immediately after the
+    /// last instruction.
+    Terminated,
+    /// A jump went to an address outside of the program.
+    JumpedOutOfBounds(Data),
+    /// The instruction at the address overflowed and was not run.
+    Overflow(Address),
+    /// The maximum number of steps were run without any other reason to stop.
+    StepLimitExceeded,
+    /// There is no instruction left to undo.
+    StartOfHistory,
+}
+
+/// The state to restore when undoing a step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Snapshot {
+    registers: Registers,
+    output_len: usize,
+}
+
+#[derive(Debug)]
+pub struct Debugger<I = Instruction> {
+    vm: VirtualMachine<I>,
+    conditional: bool,
+    registers: Registers,
+    output: Vec<Data>,
+    history: Vec<Snapshot>,
+    breakpoints: BTreeSet<Address>,
+    watchpoints: Vec<Register>,
+    limits: Limits,
+}
+
+impl<I: InstructionSet> Debugger<I> {
+    pub fn new(program: Program<I>) -> Self {
+        let conditional = program.instructions().any(|op| op.is_conditional());
+        Self {
+            vm: VirtualMachine::new(program),
+            conditional,
+            registers: Registers::default(),
+            output: Vec::new(),
+            history: Vec::new(),
+            breakpoints: BTreeSet::new(),
+            watchpoints: Vec::new(),
+            limits: Limits::default(),
+        }
+    }
+
+    pub fn program(&self) -> &Program<I> {
+        self.vm.program()
+    }
+
+    pub fn registers(&self) -> &Registers {
+        &self.registers
+    }
+
+    pub fn output(&self) -> &[Data] {
+        &self.output
+    }
+
+    /// Returns the number of instructions run that can be undone.
+    pub fn history_len(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Sets the limits for every [`resume`](Debugger::resume) and
+    /// [`run_to`](Debugger::run_to).
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+    }
+
+    /// Returns whether there was no breakpoint at the address before.
+    pub fn set_breakpoint(&mut self, address: Address) -> bool {
+        self.breakpoints.insert(address)
+    }
+
+    /// Returns whether there was a breakpoint at the address.
+    pub fn clear_breakpoint(&mut self, address: Address) -> bool {
+        self.breakpoints.remove(&address)
+    }
+
+    pub fn breakpoints(&self) -> impl Iterator<Item = Address> + '_ {
+        self.breakpoints.iter().copied()
+    }
+
+    /// Returns whether the register was not watched before.
+    pub fn watch(&mut self, register: Register) -> bool {
+        if self.watchpoints.contains(&register) {
+            false
+        } else {
+            self.watchpoints.push(register);
+            true
+        }
+    }
+
+    /// Returns whether the register was watched.
+    pub fn unwatch(&mut self, register: Register) -> bool {
+        let len = self.watchpoints.len();
+        self.watchpoints.retain(|watched| *watched != register);
+        self.watchpoints.len() != len
+    }
+
+    pub fn watchpoints(&self) -> &[Register] {
+        &self.watchpoints
+    }
+
+    /// Starts the program over, keeping breakpoints and watchpoints.
+    pub fn restart(&mut self) {
+        self.registers = Registers::default();
+        self.output.clear();
+        self.history.clear();
+    }
+
+    /// Runs the instruction at `pc`.
+    pub fn step(&mut self) -> StopReason {
+        let before = Snapshot {
+            registers: self.registers,
+            output_len: self.output.len(),
+        };
+        match self.vm.step(&mut self.registers, &mut self.output) {
+            Continuation::Continue => {}
+            Continuation::Halt => return StopReason::Terminated,
+            Continuation::OutOfBounds(pc) => return StopReason::JumpedOutOfBounds(pc),
+            Continuation::Overflow => return StopReason::Overflow(self.registers.pc),
+        }
+        self.history.push(before);
+        self.watchpoints
+            .iter()
+            .map(|register| {
+                (
+                    *register,
+                    before.registers.get(*register),
+                    self.registers.get(*register),
+                )
+            })
+            .find(|(_, old, new)| old != new)
+            .map_or(StopReason::Stepped, |(register, old, new)| {
+                StopReason::Watchpoint { register, old, new }
+            })
+    }
+
+    /// Undoes the last instruction run.
+    pub fn reverse_step(&mut self) -> StopReason {
+        match self.history.pop() {
+            Some(snapshot) => {
+                self.registers = snapshot.registers;
+                self.output.truncate(snapshot.output_len);
+                StopReason::Stepped
+            }
+            None => StopReason::StartOfHistory,
+        }
+    }
+
+    /// Continues running the program until it hits a breakpoint or a
+    /// watchpoint, is about to loop forever or ends.
+    pub fn resume(&mut self) -> StopReason {
+        self.run_until(|_| false)
+    }
+
+    /// Continues running the program like [`resume`](Debugger::resume), but
+    /// also stops before running the instruction at `cursor`.
+    pub fn run_to(&mut self, cursor: Address) -> StopReason {
+        self.run_until(|pc| pc == cursor)
+    }
+
+    fn run_until(&mut self, at_cursor: impl Fn(Address) -> bool) -> StopReason {
+        let mut visited = HashSet::new();
+        let mut states = HashSet::new();
+        let max_steps = self.limits.max_steps(self.conditional);
+        for _ in 0..max_steps {
+            let repeated = if self.conditional {
+                !states.insert(self.registers)
+            } else {
+                !visited.insert(self.registers.pc)
+            };
+            if repeated {
+                return StopReason::InfiniteLoop(self.registers.pc);
+            }
+            match self.step() {
+                StopReason::Stepped => {}
+                reason => return reason,
+            }
+            let pc = self.registers.pc;
+            if at_cursor(pc) {
+                return StopReason::Cursor(pc);
+            }
+            if self.breakpoints.contains(&pc) {
+                return StopReason::Breakpoint(pc);
+            }
+        }
+        StopReason::StepLimitExceeded
+    }
+}
@@ -0,0 +1,208 @@
+//! Extended instruction set for teaching with the virtual machine.
+//!
+//! Besides the boot code's `acc`, `jmp` and `nop`, which behave exactly the
+//! same, it offers:
+//!
+//! * sub and mul subtract the argument from or multiply the register by it.
+//! * jz and jnz jump like jmp, but only if the register is zero or not zero.
+//!   Otherwise the instruction immediately below is executed next.
+//! * out appends the value of the register to the output. It has no argument.
+//!
+//! Arithmetic does not wrap around. An instruction whose result or jump
+//! target does not fit into a register stops the program with
+//! [`Continuation::Overflow`].
+//!
+//! All of them except jmp and nop take an optional register before the
+//! argument, `acc` or one of the general purpose registers `a` to `d`. It
+//! defaults to the accumulator, so `acc b +2` adds 2 to register `b` while
+//! `acc +2` adds 2 to the accumulator as in boot code.
+//!
+//! ```text
+//!         acc a +3
+//! loop:   out a       ; writes 3, 2 and 1
+//!         sub a +1
+//!         jnz a loop
+//! ```
+
+use super::asm::{self, Assemble, Resolver};
+use super::{
+    Continuation, Data, Instruction, InstructionSet, OperationCode, OperationCodeParseError,
+    Program, Register, Registers,
+};
+use crate::parse::{end_of, ParseError, ParseErrorKind};
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtendedInstruction {
+    pub operation: ExtendedOperation,
+    /// The register operated on, which `jmp` and `nop` ignore.
+    pub register: Register,
+    /// The argument, which `out` ignores.
+    pub argument: Data,
+}
+
+impl From<Instruction> for ExtendedInstruction {
+    fn from(instruction: Instruction) -> Self {
+        Self {
+            operation: match instruction.operation {
+                OperationCode::Acc => ExtendedOperation::Acc,
+                OperationCode::Jmp => ExtendedOperation::Jmp,
+                OperationCode::Nop => ExtendedOperation::Nop,
+            },
+            register: Register::Acc,
+            argument: instruction.argument,
+        }
+    }
+}
+
+/// Formats the instruction in canonical form, leaving out the register if
+/// it is the accumulator, like `acc +1`, `jnz b -3` or `out`.
+impl Display for ExtendedInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.operation, self.register) {
+            (ExtendedOperation::Out, Register::Acc) => write!(f, "{}", self.operation),
+            (ExtendedOperation::Out, register) => write!(f, "{} {}", self.operation, register),
+            (ExtendedOperation::Jmp, _) | (ExtendedOperation::Nop, _) | (_, Register::Acc) => {
+                write!(f, "{} {:+}", self.operation, self.argument)
+            }
+            (_, register) => write!(f, "{} {} {:+}", self.operation, register, self.argument),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtendedOperation {
+    Acc,
+    Sub,
+    Mul,
+    Jmp,
+    Jz,
+    Jnz,
+    Nop,
+    Out,
+}
+
+impl ExtendedOperation {
+    /// Returns the number of operands the operation takes at most, including
+    /// the optional register.
+    fn max_operands(self) -> usize {
+        match self {
+            ExtendedOperation::Jmp | ExtendedOperation::Nop | ExtendedOperation::Out => 1,
+            _ => 2,
+        }
+    }
+}
+
+impl Display for ExtendedOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ExtendedOperation::Acc => "acc",
+            ExtendedOperation::Sub => "sub",
+            ExtendedOperation::Mul => "mul",
+            ExtendedOperation::Jmp => "jmp",
+            ExtendedOperation::Jz => "jz",
+            ExtendedOperation::Jnz => "jnz",
+            ExtendedOperation::Nop => "nop",
+            ExtendedOperation::Out => "out",
+        })
+    }
+}
+
+impl FromStr for ExtendedOperation {
+    type Err = OperationCodeParseError;
+
+    fn from_str(opcode: &str) -> Result<Self, Self::Err> {
+        match opcode {
+            "acc" => Ok(ExtendedOperation::Acc),
+            "sub" => Ok(ExtendedOperation::Sub),
+            "mul" => Ok(ExtendedOperation::Mul),
+            "jmp" => Ok(ExtendedOperation::Jmp),
+            "jz" => Ok(ExtendedOperation::Jz),
+            "jnz" => Ok(ExtendedOperation::Jnz),
+            "nop" => Ok(ExtendedOperation::Nop),
+            "out" => Ok(ExtendedOperation::Out),
+            _ => Err(OperationCodeParseError::InvalidOperationCode(opcode.into())),
+        }
+    }
+}
+
+impl InstructionSet for ExtendedInstruction {
+    fn execute(self, reg: &mut Registers, output: &mut Vec<Data>, len: usize) -> Continuation {
+        let value = reg.get(self.register);
+        match self.operation {
+            ExtendedOperation::Acc => {
+                return reg.store(self.register, value.checked_add(self.argument))
+            }
+            ExtendedOperation::Sub => {
+                return reg.store(self.register, value.checked_sub(self.argument))
+            }
+            ExtendedOperation::Mul => {
+                return reg.store(self.register, value.checked_mul(self.argument))
+            }
+            ExtendedOperation::Jmp => return reg.jump(self.argument, len),
+            ExtendedOperation::Jz if value == 0 => return reg.jump(self.argument, len),
+            ExtendedOperation::Jnz if value != 0 => return reg.jump(self.argument, len),
+            ExtendedOperation::Jz | ExtendedOperation::Jnz | ExtendedOperation::Nop => {}
+            ExtendedOperation::Out => output.push(value),
+        }
+        reg.pc += 1;
+        Continuation::Continue
+    }
+
+    fn is_conditional(self) -> bool {
+        matches!(
+            self.operation,
+            ExtendedOperation::Jz | ExtendedOperation::Jnz
+        )
+    }
+}
+
+fn parse_register(source: &str, token: &str) -> Result<Register, ParseError> {
+    Register::from_str(token)
+        .map_err(|_| ParseError::at(source, token, ParseErrorKind::Invalid("register")))
+}
+
+impl Assemble for ExtendedInstruction {
+    fn assemble(code: &str, resolver: &Resolver<'_>) -> Result<Self, ParseError> {
+        let source = resolver.source();
+        let mut parts = code.split_whitespace();
+        let opcode = parts.next().ok_or_else(|| {
+            ParseError::at(source, code, ParseErrorKind::Missing("operation code"))
+        })?;
+        let operation = ExtendedOperation::from_str(opcode).map_err(|_| {
+            ParseError::at(source, opcode, ParseErrorKind::Invalid("operation code"))
+        })?;
+        let operands = parts
+            .by_ref()
+            .take(operation.max_operands())
+            .collect::<Vec<_>>();
+        resolver.expect_end(parts)?;
+        let (register, argument) = match (operation, operands.as_slice()) {
+            (ExtendedOperation::Out, []) => (Register::Acc, 0),
+            (ExtendedOperation::Out, [register]) => (parse_register(source, register)?, 0),
+            (_, []) => {
+                return Err(ParseError::at(
+                    source,
+                    end_of(code),
+                    ParseErrorKind::Missing("argument"),
+                ))
+            }
+            (_, [argument]) => (Register::Acc, resolver.argument(argument)?),
+            (_, [register, argument, ..]) => (
+                parse_register(source, register)?,
+                resolver.argument(argument)?,
+            ),
+        };
+        Ok(Self {
+            operation,
+            register,
+            argument,
+        })
+    }
+}
+
+/// Assembles the source into a program of the extended instruction set.
+pub fn assemble(source: &str) -> Result<Program<ExtendedInstruction>, ParseError> {
+    asm::assemble_as(source)
+}
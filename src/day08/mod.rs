@@ -129,7 +129,14 @@
 //!
 //! [Advent of Code 2020 - Day 8](https://adventofcode.com/2020/day/8)
 
-use std::collections::HashSet;
+pub mod asm;
+pub mod cfg;
+pub mod debugger;
+pub mod ext;
+
+use crate::parse::ParseError;
+use hashbrown::HashMap;
+use std::fmt::{self, Display};
 use std::iter::FromIterator;
 use std::ops::Index;
 use std::slice::Iter;
@@ -139,10 +146,10 @@ pub type Address = usize;
 pub type Data = i64;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Program(Vec<Instruction>);
+pub struct Program<I = Instruction>(Vec<I>);
 
-impl Program {
-    pub fn new(instructions: impl IntoIterator<Item = Instruction>) -> Self {
+impl<I> Program<I> {
+    pub fn new(instructions: impl IntoIterator<Item = I>) -> Self {
         Program(Vec::from_iter(instructions.into_iter()))
     }
 
@@ -150,13 +157,32 @@ impl Program {
         self.0.len()
     }
 
-    pub fn instructions(&self) -> Iter<'_, Instruction> {
+    pub fn instructions(&self) -> Iter<'_, I> {
         self.0.iter()
     }
 }
 
-impl Index<usize> for Program {
-    type Output = Instruction;
+/// Disassembles the program into one instruction per line in canonical form,
+/// each followed by a comment with its address.
+impl<I: Display> Display for Program<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.len().saturating_sub(1).to_string().len();
+        for (address, instruction) in self.instructions().enumerate() {
+            writeln!(
+                f,
+                "{:<8}{} {:>width$}",
+                instruction.to_string(),
+                asm::COMMENT,
+                address,
+                width = width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<I> Index<usize> for Program<I> {
+    type Output = I;
 
     fn index(&self, index: usize) -> &Self::Output {
         &self.0[index]
@@ -169,6 +195,13 @@ pub struct Instruction {
     pub argument: Data,
 }
 
+/// Formats the instruction in canonical form, like `acc +1`.
+impl Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:+}", self.operation, self.argument)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OperationCode {
     Acc,
@@ -176,6 +209,16 @@ pub enum OperationCode {
     Nop,
 }
 
+impl Display for OperationCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            OperationCode::Acc => "acc",
+            OperationCode::Jmp => "jmp",
+            OperationCode::Nop => "nop",
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum OperationCodeParseError {
     InvalidOperationCode(String),
@@ -194,15 +237,123 @@ impl FromStr for OperationCode {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The number of general purpose registers besides the accumulator.
+pub const GENERAL_REGISTERS: usize = 4;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Registers {
     pub pc: Address,
     pub acc: Data,
+    /// The registers `a` to `d`, which only extended instruction sets use.
+    pub general: [Data; GENERAL_REGISTERS],
 }
 
-impl Default for Registers {
-    fn default() -> Self {
-        Self { pc: 0, acc: 0 }
+impl Registers {
+    pub fn get(&self, register: Register) -> Data {
+        match register {
+            Register::Acc => self.acc,
+            Register::General(index) => self.general[index],
+        }
+    }
+
+    pub fn get_mut(&mut self, register: Register) -> &mut Data {
+        match register {
+            Register::Acc => &mut self.acc,
+            Register::General(index) => &mut self.general[index],
+        }
+    }
+
+    /// Stores the result of an arithmetic instruction in the register and
+    /// moves on to the next instruction, or overflows without a result.
+    pub fn store(&mut self, register: Register, result: Option<Data>) -> Continuation {
+        match result {
+            Some(value) => {
+                *self.get_mut(register) = value;
+                self.pc += 1;
+                Continuation::Continue
+            }
+            None => Continuation::Overflow,
+        }
+    }
+
+    /// Jumps `offset` instructions away from the current one in a program
+    /// with `len` instructions.
+    pub fn jump(&mut self, offset: Data, len: usize) -> Continuation {
+        let target = match (self.pc as Data).checked_add(offset) {
+            Some(target) => target,
+            None => return Continuation::Overflow,
+        };
+        if target < 0 || target > len as Data {
+            return Continuation::OutOfBounds(target);
+        }
+        self.pc = target as Address;
+        Continuation::Continue
+    }
+}
+
+/// A register an instruction operates on, written `acc` or `a` to `d`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Register {
+    Acc,
+    /// One of the general purpose registers, `0` for `a`.
+    General(usize),
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Register::Acc => f.write_str("acc"),
+            Register::General(index) => write!(f, "{}", (b'a' + *index as u8) as char),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegisterParseError {
+    InvalidRegister(String),
+}
+
+impl FromStr for Register {
+    type Err = RegisterParseError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.as_bytes() {
+            b"acc" => Ok(Register::Acc),
+            [letter @ b'a'..=b'z'] if ((letter - b'a') as usize) < GENERAL_REGISTERS => {
+                Ok(Register::General((letter - b'a') as usize))
+            }
+            _ => Err(RegisterParseError::InvalidRegister(name.into())),
+        }
+    }
+}
+
+/// The instructions of programs the [`VirtualMachine`] runs.
+///
+/// The boot code of the puzzle consists of [`Instruction`]s. The
+/// [`ext::ExtendedInstruction`]s add more registers, arithmetic, conditional
+/// jumps and output on top of them.
+pub trait InstructionSet: Copy {
+    /// Runs the instruction at `reg.pc` of a program with `len` instructions,
+    /// appending any output to `output`.
+    fn execute(self, reg: &mut Registers, output: &mut Vec<Data>, len: usize) -> Continuation;
+
+    /// Returns whether the address run after this instruction depends on the
+    /// values of the registers.
+    fn is_conditional(self) -> bool {
+        false
+    }
+}
+
+impl InstructionSet for Instruction {
+    fn execute(self, reg: &mut Registers, _output: &mut Vec<Data>, len: usize) -> Continuation {
+        match self.operation {
+            OperationCode::Acc => {
+                return reg.store(Register::Acc, reg.acc.checked_add(self.argument))
+            }
+            OperationCode::Jmp => return reg.jump(self.argument, len),
+            OperationCode::Nop => reg.pc += 1,
+        }
+        Continuation::Continue
     }
 }
 
@@ -210,129 +361,222 @@ impl Default for Registers {
 pub enum Continuation {
     Continue,
     Halt,
+    /// A jump to an address outside of the program, which may be negative.
+    OutOfBounds(Data),
+    /// The result of an arithmetic instruction or the target of a jump does
+    /// not fit into [`Data`]. The registers are left unchanged.
+    Overflow,
+}
+
+/// The number of instructions a program with conditional jumps executes at
+/// most unless its [`Limits`] say otherwise.
+pub const DEFAULT_MAX_STEPS: usize = 1_000_000;
+
+/// Limits for running a program.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// The maximum number of instructions to execute or `None` for the
+    /// default limit.
+    ///
+    /// By default a program without conditional jumps runs without a limit,
+    /// as every such program either terminates, jumps out of bounds or runs
+    /// an instruction a second time within `len + 1` steps. A program with
+    /// conditional jumps may run for as many steps as its registers have
+    /// states, remembering each of them to detect a loop, so it is limited to
+    /// [`DEFAULT_MAX_STEPS`].
+    pub max_steps: Option<usize>,
+}
+
+impl Limits {
+    pub fn with_max_steps(max_steps: usize) -> Self {
+        Self {
+            max_steps: Some(max_steps),
+        }
+    }
+
+    /// Returns the maximum number of instructions to execute for a program
+    /// with or without conditional jumps.
+    pub fn max_steps(&self, conditional: bool) -> usize {
+        match self.max_steps {
+            Some(max_steps) => max_steps,
+            None if conditional => DEFAULT_MAX_STEPS,
+            None => usize::MAX,
+        }
+    }
+}
+
+/// How a run of a program ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExecutionOutcome {
+    /// The program attempted to run the instruction immediately after the
+    /// last instruction.
+    Terminated { acc: Data },
+    /// The program was about to run an instruction a second time with the
+    /// same registers. Programs without conditional jumps loop as soon as
+    /// they are about to run an instruction a second time at all.
+    InfiniteLoop {
+        /// The accumulator before running any instruction a second time.
+        acc: Data,
+        /// The address of the first instruction run a second time.
+        loop_start: Address,
+        /// The addresses of the instructions in the loop in the order they
+        /// were run, starting with `loop_start`.
+        trace: Vec<Address>,
+    },
+    /// A jump went to an address other than the ones of the program and the
+    /// one immediately after it.
+    JumpedOutOfBounds { pc: Data },
+    /// The instruction at `pc` overflowed, see [`Continuation::Overflow`].
+    Overflow { pc: Address },
+    /// The maximum number of steps were run without any other outcome.
+    StepLimitExceeded,
+}
+
+/// An instruction run by the virtual machine together with the registers
+/// before running it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceEntry<I = Instruction> {
+    pub registers: Registers,
+    pub instruction: I,
 }
 
 #[derive(Debug)]
-pub struct VirtualMachine {
-    program: Program,
+pub struct VirtualMachine<I = Instruction> {
+    program: Program<I>,
 }
 
 impl VirtualMachine {
-    pub fn new(program: Program) -> Self {
+    pub fn patch(&mut self, address: Address, opcode: OperationCode) {
+        self.program.0[address].operation = opcode;
+    }
+}
+
+impl<I: InstructionSet> VirtualMachine<I> {
+    pub fn new(program: Program<I>) -> Self {
         Self { program }
     }
 
-    pub fn patch(&mut self, address: Address, opcode: OperationCode) {
-        self.program.0[address].operation = opcode;
+    pub fn program(&self) -> &Program<I> {
+        &self.program
     }
 
-    pub fn step(&self, reg: &mut Registers) -> Continuation {
+    pub fn step(&self, reg: &mut Registers, output: &mut Vec<Data>) -> Continuation {
         if reg.pc >= self.program.len() {
             return Continuation::Halt;
         }
-        let instruction = self.program[reg.pc];
-        match instruction.operation {
-            OperationCode::Acc => {
-                reg.acc += instruction.argument;
-                reg.pc += 1;
-            }
-            OperationCode::Jmp => {
-                if instruction.argument < 0 {
-                    reg.pc -= instruction.argument.abs() as usize
+        self.program[reg.pc].execute(reg, output, self.program.len())
+    }
+
+    /// Runs the program from the first instruction until it terminates,
+    /// loops or exceeds the limits.
+    pub fn run(&self, limits: Limits) -> ExecutionOutcome {
+        self.execute(limits, &mut Vec::new(), |_| {})
+    }
+
+    /// Runs the program like [`run`](VirtualMachine::run) and also returns
+    /// the output it wrote.
+    pub fn run_with_output(&self, limits: Limits) -> (ExecutionOutcome, Vec<Data>) {
+        let mut output = Vec::new();
+        let outcome = self.execute(limits, &mut output, |_| {});
+        (outcome, output)
+    }
+
+    /// Runs the program like [`run`](VirtualMachine::run) and also returns
+    /// every instruction run.
+    pub fn run_with_trace(&self, limits: Limits) -> (ExecutionOutcome, Vec<TraceEntry<I>>) {
+        let mut trace = Vec::new();
+        let outcome = self.execute(limits, &mut Vec::new(), |entry| trace.push(entry));
+        (outcome, trace)
+    }
+
+    fn execute(
+        &self,
+        limits: Limits,
+        output: &mut Vec<Data>,
+        mut on_step: impl FnMut(TraceEntry<I>),
+    ) -> ExecutionOutcome {
+        let conditional = self.program.instructions().any(|op| op.is_conditional());
+        let mut registers = Registers::default();
+        let mut first_run = vec![None; self.program.len()];
+        let mut first_state = HashMap::new();
+        let mut path = Vec::new();
+        let max_steps = limits.max_steps(conditional);
+        loop {
+            if registers.pc < self.program.len() {
+                let seen = if conditional {
+                    first_state.get(&registers).copied()
+                } else {
+                    first_run[registers.pc]
+                };
+                if let Some(step) = seen {
+                    return ExecutionOutcome::InfiniteLoop {
+                        acc: registers.acc,
+                        loop_start: registers.pc,
+                        trace: path.split_off(step),
+                    };
+                }
+                if path.len() >= max_steps {
+                    return ExecutionOutcome::StepLimitExceeded;
+                }
+                if conditional {
+                    first_state.insert(registers, path.len());
                 } else {
-                    reg.pc += instruction.argument as usize
+                    first_run[registers.pc] = Some(path.len());
                 }
+                path.push(registers.pc);
+                on_step(TraceEntry {
+                    registers,
+                    instruction: self.program[registers.pc],
+                });
+            }
+            match self.step(&mut registers, output) {
+                Continuation::Continue => {}
+                Continuation::Halt => return ExecutionOutcome::Terminated { acc: registers.acc },
+                Continuation::OutOfBounds(pc) => return ExecutionOutcome::JumpedOutOfBounds { pc },
+                Continuation::Overflow => return ExecutionOutcome::Overflow { pc: registers.pc },
             }
-            OperationCode::Nop => reg.pc += 1,
         }
-        Continuation::Continue
     }
 }
 
+/// Parses the boot code with the [`asm`] assembler, which also accepts
+/// comments and labels.
+pub fn try_parse_program(input: &str) -> Result<Program, ParseError> {
+    asm::assemble(input)
+}
+
 #[aoc_generator(day8)]
 pub fn parse_program(input: &str) -> Program {
-    let mut instructions = Vec::with_capacity(input.len() / 8);
-    for (lno, line) in input.lines().enumerate() {
-        if line.is_empty() {
-            continue;
-        }
-        let mut parts = line.split(char::is_whitespace);
-        let opcode = parts
-            .next()
-            .unwrap_or_else(|| panic!("line {}: no opcode in {:?}", lno, line));
-        let operation = OperationCode::from_str(opcode).unwrap_or_else(|err| {
-            panic!(
-                "line {}: unrecognized opcode {:?}; reason: {:?}",
-                lno, opcode, err
-            )
-        });
-        let arg = parts
-            .next()
-            .unwrap_or_else(|| panic!("line {}: no argument in {:?}", lno, line));
-        let argument = Data::from_str(arg).unwrap_or_else(|err| {
-            panic!(
-                "line {}: argument is not an integer {:?}; reason: {:?}",
-                lno, arg, err
-            )
-        });
-        instructions.push(Instruction {
-            operation,
-            argument,
-        })
-    }
-
-    Program::new(instructions)
+    try_parse_program(input).unwrap_or_else(|err| panic!("{}", err))
 }
 
 #[aoc(day8, part1)]
 pub fn accumulator_value_before_second_run(program: &Program) -> Data {
     let vm = VirtualMachine::new(program.clone());
-    let mut registers = Registers::default();
 
-    let mut visited = HashSet::new();
-    loop {
-        if !visited.insert(registers.pc) {
-            break;
-        }
-        match vm.step(&mut registers) {
-            Continuation::Continue => {}
-            Continuation::Halt => break,
-        }
+    match vm.run(Limits::default()) {
+        ExecutionOutcome::InfiniteLoop { acc, .. } | ExecutionOutcome::Terminated { acc } => acc,
+        outcome => panic!("program does not loop: {:?}", outcome),
     }
-    registers.acc
 }
 
-#[aoc(day8, part2)]
-pub fn accumulator_value_after_fixing_the_endless_loop(program: &Program) -> Data {
+/// Repairs the program with the control flow graph and returns the patched
+/// address and the accumulator after the repaired program terminates.
+pub fn fix_endless_loop(program: &Program) -> Option<(Address, Data)> {
+    let repair = cfg::find_repair(program)?;
     let mut vm = VirtualMachine::new(program.clone());
-    let mut patchable_instructions = program
-        .instructions()
-        .enumerate()
-        .filter_map(|(pc, inst)| match inst.operation {
-            OperationCode::Jmp => Some((pc, OperationCode::Nop)),
-            _ => None,
-        })
-        .collect::<Vec<_>>();
-    while let Some(patched_instruction) = patchable_instructions.pop() {
-        vm.patch(patched_instruction.0, patched_instruction.1);
-        let mut registers = Registers::default();
-        let mut visited = HashSet::new();
-        let continuation = loop {
-            if !visited.insert(registers.pc) {
-                break Continuation::Continue;
-            }
-            match vm.step(&mut registers) {
-                Continuation::Continue => {}
-                Continuation::Halt => break Continuation::Halt,
-            }
-        };
-        match continuation {
-            Continuation::Continue => {}
-            Continuation::Halt => return registers.acc,
-        }
-        vm.patch(patched_instruction.0, OperationCode::Jmp)
+    vm.patch(repair.address, repair.patched);
+    match vm.run(Limits::default()) {
+        ExecutionOutcome::Terminated { acc } => Some((repair.address, acc)),
+        _ => None,
     }
-    panic!("that is not an answer!");
+}
+
+#[aoc(day8, part2)]
+pub fn accumulator_value_after_fixing_the_endless_loop(program: &Program) -> Data {
+    fix_endless_loop(program)
+        .map(|(_, acc)| acc)
+        .unwrap_or_else(|| panic!("that is not an answer!"))
 }
 
 #[cfg(test)]
@@ -1,4 +1,8 @@
 use super::*;
+use crate::answers::recorded_answer;
+use crate::parse::ParseErrorKind;
+use proptest::collection::vec;
+use proptest::prelude::*;
 
 const INPUT: &str = include_str!("../../input/2020/day8.txt");
 
@@ -61,6 +65,131 @@ fn parse_program_example() {
     )
 }
 
+#[test]
+fn try_parse_program_with_unknown_opcode() {
+    let error = try_parse_program("nop +0\nacc +1\nhlt -3\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (3, 1));
+    assert_eq!(error.kind(), &ParseErrorKind::Invalid("operation code"));
+}
+
+#[test]
+fn try_parse_program_with_missing_argument() {
+    let error = try_parse_program("nop +0\njmp\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (2, 4));
+    assert_eq!(error.kind(), &ParseErrorKind::Missing("argument"));
+}
+
+#[test]
+fn assemble_program_with_comments_and_labels() {
+    let source = "\
+; the example program with labels
+        nop +0
+        acc +1
+        jmp skip    ; to the second acc +1
+back:   acc +3
+        jmp -3
+
+        acc -99
+skip:   acc +1
+        jmp back
+end:
+";
+
+    let program = asm::assemble(source).unwrap();
+
+    assert_eq!(
+        program,
+        Program::new(parse_program(EXAMPLE).instructions().copied().take(8))
+    );
+}
+
+#[test]
+fn assemble_label_after_last_instruction() {
+    let program = asm::assemble("jmp end\nacc +1\nend:\n").unwrap();
+
+    assert_eq!(program[0].argument, 2);
+}
+
+#[test]
+fn assemble_program_with_undefined_label() {
+    let error = asm::assemble("nop +0\njmp nowhere\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (2, 5));
+    assert_eq!(
+        error.kind(),
+        &ParseErrorKind::Missing("definition of label")
+    );
+}
+
+#[test]
+fn assemble_program_with_label_defined_twice() {
+    let error = asm::assemble("top: nop +0\ntop: jmp top\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (2, 1));
+    assert_eq!(
+        error.kind(),
+        &ParseErrorKind::Invalid("label, it is already defined")
+    );
+}
+
+#[test]
+fn assemble_instruction_with_trailing_tokens() {
+    let error = asm::assemble("acc +1 +2\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (1, 8));
+    assert_eq!(error.kind(), &ParseErrorKind::UnexpectedCharacter('+'));
+}
+
+#[test]
+fn disassemble_example_program() {
+    let program = parse_program(EXAMPLE);
+
+    assert_eq!(
+        program.to_string(),
+        "\
+nop +0  ; 0
+acc +1  ; 1
+jmp +4  ; 2
+acc +3  ; 3
+jmp -3  ; 4
+acc -99 ; 5
+acc +1  ; 6
+jmp -4  ; 7
+acc +6  ; 8
+"
+    );
+}
+
+fn any_instruction() -> impl Strategy<Value = Instruction> {
+    (
+        prop_oneof![
+            Just(OperationCode::Acc),
+            Just(OperationCode::Jmp),
+            Just(OperationCode::Nop),
+        ],
+        any::<Data>(),
+    )
+        .prop_map(|(operation, argument)| Instruction {
+            operation,
+            argument,
+        })
+}
+
+proptest! {
+    #[test]
+    fn assembling_the_disassembly_of_any_program_gives_the_original_program(
+        instructions in vec(any_instruction(), 0..200)
+    ) {
+        let program = Program::new(instructions);
+
+        let assembled = asm::assemble(&program.to_string());
+
+        prop_assert_eq!(assembled, Ok(program));
+    }
+}
+
 #[test]
 fn accumulator_value_before_second_run_of_example_program() {
     let program = parse_program(EXAMPLE);
@@ -76,7 +205,7 @@ fn accumulator_value_before_second_run_of_program_in_puzzle_input() {
 
     let acc = accumulator_value_before_second_run(&program);
 
-    assert_eq!(acc, 1723);
+    assert_eq!(acc.to_string(), recorded_answer(8, 1));
 }
 
 #[test]
@@ -85,5 +214,432 @@ fn accumulator_value_after_fixing_the_endless_loop_in_puzzle_input() {
 
     let acc = accumulator_value_after_fixing_the_endless_loop(&program);
 
-    assert_eq!(acc, 846);
+    assert_eq!(acc.to_string(), recorded_answer(8, 2));
+}
+
+#[test]
+fn run_example_program_into_infinite_loop() {
+    let vm = VirtualMachine::new(parse_program(EXAMPLE));
+
+    let outcome = vm.run(Limits::default());
+
+    assert_eq!(
+        outcome,
+        ExecutionOutcome::InfiniteLoop {
+            acc: 5,
+            loop_start: 1,
+            trace: vec![1, 2, 6, 7, 3, 4],
+        }
+    );
+}
+
+#[test]
+fn run_patched_example_program_to_termination() {
+    let mut vm = VirtualMachine::new(parse_program(EXAMPLE));
+    vm.patch(7, OperationCode::Nop);
+
+    let outcome = vm.run(Limits::default());
+
+    assert_eq!(outcome, ExecutionOutcome::Terminated { acc: 8 });
+}
+
+#[test]
+fn run_program_jumping_out_of_bounds() {
+    let vm = VirtualMachine::new(parse_program("nop +0\njmp -2\n"));
+
+    let outcome = vm.run(Limits::default());
+
+    assert_eq!(outcome, ExecutionOutcome::JumpedOutOfBounds { pc: -1 });
+}
+
+#[test]
+fn run_program_overflowing_the_accumulator() {
+    let vm = VirtualMachine::new(parse_program("acc +9223372036854775807\nacc +1\n"));
+
+    let outcome = vm.run(Limits::default());
+
+    assert_eq!(outcome, ExecutionOutcome::Overflow { pc: 1 });
+}
+
+#[test]
+fn run_program_jumping_beyond_the_largest_address() {
+    let vm = VirtualMachine::new(parse_program("nop +0\njmp +9223372036854775807\n"));
+
+    let outcome = vm.run(Limits::default());
+
+    assert_eq!(outcome, ExecutionOutcome::Overflow { pc: 1 });
+}
+
+#[test]
+fn run_example_program_exceeding_step_limit() {
+    let vm = VirtualMachine::new(parse_program(EXAMPLE));
+
+    let outcome = vm.run(Limits::with_max_steps(3));
+
+    assert_eq!(outcome, ExecutionOutcome::StepLimitExceeded);
+}
+
+#[test]
+fn run_example_program_with_trace() {
+    let vm = VirtualMachine::new(parse_program(EXAMPLE));
+
+    let (outcome, trace) = vm.run_with_trace(Limits::default());
+
+    assert!(matches!(outcome, ExecutionOutcome::InfiniteLoop { .. }));
+    assert_eq!(
+        trace
+            .iter()
+            .map(|entry| (entry.registers.pc, entry.registers.acc))
+            .collect::<Vec<_>>(),
+        vec![(0, 0), (1, 0), (2, 1), (6, 1), (7, 2), (3, 2), (4, 5)]
+    );
+    assert_eq!(
+        trace[3].instruction,
+        Instruction {
+            operation: OperationCode::Acc,
+            argument: 1
+        }
+    );
+}
+
+#[test]
+fn control_flow_graph_of_example_program() {
+    let cfg = cfg::ControlFlowGraph::new(&parse_program(EXAMPLE));
+
+    assert_eq!(cfg.successor(2), Some(6));
+    assert_eq!(cfg.predecessors(1), &[0, 4]);
+    assert_eq!(cfg.execution_path(), vec![0, 1, 2, 6, 7, 3, 4]);
+    assert_eq!(
+        cfg.terminating(),
+        vec![false, false, false, false, false, false, false, false, true, true]
+    );
+}
+
+#[test]
+fn find_repair_of_example_program() {
+    let repair = cfg::find_repair(&parse_program(EXAMPLE));
+
+    assert_eq!(
+        repair,
+        Some(cfg::Repair {
+            address: 7,
+            original: OperationCode::Jmp,
+            patched: OperationCode::Nop,
+        })
+    );
+}
+
+#[test]
+fn find_repair_of_program_with_jump_beyond_the_largest_address() {
+    let program = parse_program("nop +0\njmp +9223372036854775807\n");
+
+    let repair = cfg::find_repair(&program);
+
+    assert_eq!(
+        repair,
+        Some(cfg::Repair {
+            address: 1,
+            original: OperationCode::Jmp,
+            patched: OperationCode::Nop
+        })
+    );
+}
+
+#[test]
+fn fix_endless_loop_in_example_program() {
+    assert_eq!(fix_endless_loop(&parse_program(EXAMPLE)), Some((7, 8)));
+}
+
+#[test]
+fn fix_endless_loop_in_program_with_300000_instructions() {
+    let len = 300_000;
+    let program = Program::new((0..len).map(|address| match address % 3 {
+        _ if address == len - 1 => Instruction {
+            operation: OperationCode::Jmp,
+            argument: -(len as Data - 1),
+        },
+        0 => Instruction {
+            operation: OperationCode::Nop,
+            argument: 0,
+        },
+        1 => Instruction {
+            operation: OperationCode::Jmp,
+            argument: 1,
+        },
+        _ => Instruction {
+            operation: OperationCode::Acc,
+            argument: 1,
+        },
+    }));
+
+    let fixed = fix_endless_loop(&program);
+
+    assert_eq!(fixed, Some((len - 1, (len as Data - 1) / 3)));
+}
+
+const COUNTDOWN: &str = "\
+        acc a +3
+loop:   out a
+        sub a +1
+        jnz a loop
+";
+
+#[test]
+fn run_extended_program_with_conditional_jump() {
+    let vm = VirtualMachine::new(ext::assemble(COUNTDOWN).unwrap());
+
+    let outcome = vm.run_with_output(Limits::default());
+
+    assert_eq!(
+        outcome,
+        (ExecutionOutcome::Terminated { acc: 0 }, vec![3, 2, 1])
+    );
+}
+
+#[test]
+fn run_extended_program_with_arithmetic_on_registers() {
+    let program = ext::assemble("acc +6\nmul +7\nsub b +2\nout\nout b\n").unwrap();
+    let vm = VirtualMachine::new(program);
+
+    let outcome = vm.run_with_output(Limits::default());
+
+    assert_eq!(
+        outcome,
+        (ExecutionOutcome::Terminated { acc: 42 }, vec![42, -2])
+    );
+}
+
+#[test]
+fn run_extended_program_overflowing_in_a_mul_loop() {
+    let vm = VirtualMachine::new(ext::assemble("acc +1\nloop: mul +2\njnz loop\n").unwrap());
+
+    let outcome = vm.run(Limits::default());
+
+    assert_eq!(outcome, ExecutionOutcome::Overflow { pc: 1 });
+}
+
+#[test]
+fn run_extended_program_that_loops_with_the_same_registers() {
+    let vm = VirtualMachine::new(ext::assemble("acc b +1\njz +0\n").unwrap());
+
+    let outcome = vm.run(Limits::default());
+
+    assert_eq!(
+        outcome,
+        ExecutionOutcome::InfiniteLoop {
+            acc: 0,
+            loop_start: 1,
+            trace: vec![1],
+        }
+    );
+}
+
+#[test]
+fn run_extended_counter_stops_at_default_step_limit() {
+    let vm = VirtualMachine::new(ext::assemble("acc a +1\njnz a -1\n").unwrap());
+
+    let outcome = vm.run(Limits::default());
+
+    assert_eq!(outcome, ExecutionOutcome::StepLimitExceeded);
+}
+
+#[test]
+fn default_step_limit_applies_only_to_programs_with_conditional_jumps() {
+    assert_eq!(Limits::default().max_steps(false), usize::MAX);
+    assert_eq!(Limits::default().max_steps(true), DEFAULT_MAX_STEPS);
+    assert_eq!(Limits::with_max_steps(10).max_steps(true), 10);
+}
+
+#[test]
+fn run_example_program_as_extended_program() {
+    let program = parse_program(EXAMPLE);
+    let extended = Program::new(
+        program
+            .instructions()
+            .map(|instruction| ext::ExtendedInstruction::from(*instruction)),
+    );
+
+    let (outcome, trace) = VirtualMachine::new(program).run_with_trace(Limits::default());
+    let (extended_outcome, extended_trace) =
+        VirtualMachine::new(extended).run_with_trace(Limits::default());
+
+    assert_eq!(extended_outcome, outcome);
+    assert_eq!(
+        extended_trace
+            .iter()
+            .map(|entry| entry.registers)
+            .collect::<Vec<_>>(),
+        trace
+            .iter()
+            .map(|entry| entry.registers)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn assemble_extended_program_with_invalid_register() {
+    let error = ext::assemble("acc +1\nsub x +1\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (2, 5));
+    assert_eq!(error.kind(), &ParseErrorKind::Invalid("register"));
+}
+
+fn any_extended_instruction() -> impl Strategy<Value = ext::ExtendedInstruction> {
+    use ext::ExtendedOperation::*;
+
+    (
+        prop_oneof![
+            Just(Acc),
+            Just(Sub),
+            Just(Mul),
+            Just(Jmp),
+            Just(Jz),
+            Just(Jnz),
+            Just(Nop),
+            Just(Out),
+        ],
+        prop_oneof![
+            Just(Register::Acc),
+            (0..GENERAL_REGISTERS).prop_map(Register::General),
+        ],
+        any::<Data>(),
+    )
+        .prop_map(|(operation, register, argument)| match operation {
+            Jmp | Nop => ext::ExtendedInstruction {
+                operation,
+                register: Register::Acc,
+                argument,
+            },
+            Out => ext::ExtendedInstruction {
+                operation,
+                register,
+                argument: 0,
+            },
+            _ => ext::ExtendedInstruction {
+                operation,
+                register,
+                argument,
+            },
+        })
+}
+
+proptest! {
+    #[test]
+    fn assembling_the_disassembly_of_any_extended_program_gives_the_original_program(
+        instructions in vec(any_extended_instruction(), 0..200)
+    ) {
+        let program = Program::new(instructions);
+
+        let assembled = ext::assemble(&program.to_string());
+
+        prop_assert_eq!(assembled, Ok(program));
+    }
+}
+
+#[test]
+fn debugger_stops_at_breakpoint() {
+    let mut debugger = debugger::Debugger::new(parse_program(EXAMPLE));
+    debugger.set_breakpoint(3);
+
+    let reason = debugger.resume();
+
+    assert_eq!(reason, debugger::StopReason::Breakpoint(3));
+    assert_eq!(debugger.registers().acc, 2);
+}
+
+#[test]
+fn debugger_stops_when_watched_accumulator_changes() {
+    let mut debugger = debugger::Debugger::new(parse_program(EXAMPLE));
+    debugger.watch(Register::Acc);
+
+    let first = debugger.resume();
+    let second = debugger.resume();
+
+    assert_eq!(
+        first,
+        debugger::StopReason::Watchpoint {
+            register: Register::Acc,
+            old: 0,
+            new: 1
+        }
+    );
+    assert_eq!(
+        second,
+        debugger::StopReason::Watchpoint {
+            register: Register::Acc,
+            old: 1,
+            new: 2
+        }
+    );
+    assert_eq!(debugger.registers().pc, 7);
+}
+
+#[test]
+fn debugger_stops_before_running_an_instruction_a_second_time() {
+    let mut debugger = debugger::Debugger::new(parse_program(EXAMPLE));
+
+    let reason = debugger.resume();
+
+    assert_eq!(reason, debugger::StopReason::InfiniteLoop(1));
+    assert_eq!(debugger.registers().acc, 5);
+}
+
+#[test]
+fn debugger_runs_to_cursor() {
+    let mut debugger = debugger::Debugger::new(parse_program(EXAMPLE));
+
+    let reason = debugger.run_to(4);
+
+    assert_eq!(reason, debugger::StopReason::Cursor(4));
+    assert_eq!(debugger.history_len(), 6);
+}
+
+#[test]
+fn debugger_reverse_steps_through_history() {
+    let mut debugger = debugger::Debugger::new(ext::assemble(COUNTDOWN).unwrap());
+    assert_eq!(debugger.resume(), debugger::StopReason::Terminated);
+    assert_eq!(debugger.output(), &[3, 2, 1]);
+
+    for _ in 0..3 {
+        assert_eq!(debugger.reverse_step(), debugger::StopReason::Stepped);
+    }
+
+    assert_eq!(debugger.registers().pc, 1);
+    assert_eq!(debugger.registers().get(Register::General(0)), 1);
+    assert_eq!(debugger.output(), &[3, 2]);
+}
+
+#[test]
+fn debugger_cannot_reverse_step_before_the_start() {
+    let mut debugger = debugger::Debugger::new(parse_program(EXAMPLE));
+    debugger.step();
+
+    assert_eq!(debugger.reverse_step(), debugger::StopReason::Stepped);
+    assert_eq!(
+        debugger.reverse_step(),
+        debugger::StopReason::StartOfHistory
+    );
+    assert_eq!(debugger.registers(), &Registers::default());
+}
+
+#[test]
+fn debugger_stops_at_an_overflowing_instruction() {
+    let mut debugger =
+        debugger::Debugger::new(ext::assemble("acc a -2\nsub a +9223372036854775807\n").unwrap());
+
+    let reason = debugger.resume();
+
+    assert_eq!(reason, debugger::StopReason::Overflow(1));
+    assert_eq!(debugger.registers().get(Register::General(0)), -2);
+    assert_eq!(debugger.history_len(), 1);
+}
+
+#[test]
+fn debugger_stops_extended_counter_at_default_step_limit() {
+    let mut debugger = debugger::Debugger::new(ext::assemble("acc a +1\njnz a -1\n").unwrap());
+
+    let reason = debugger.resume();
+
+    assert_eq!(reason, debugger::StopReason::StepLimitExceeded);
+    assert_eq!(debugger.history_len(), DEFAULT_MAX_STEPS);
 }
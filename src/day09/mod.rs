@@ -115,20 +115,21 @@
 //!
 //! [Advent of Code 2020 - Day 9](https://adventofcode.com/2020/day/9)
 
-use std::str::FromStr;
+use crate::parse::{parse_number, ParseError};
 
-#[aoc_generator(day9)]
-pub fn parse_xmas_code(input: &str) -> XmasCode {
+pub fn try_parse_xmas_code(input: &str) -> Result<XmasCode, ParseError> {
     let numbers = input
         .lines()
-        .enumerate()
-        .map(|line| {
-            i64::from_str(line.1).unwrap_or_else(|err| {
-                panic!("line {}: {:?} no a valid number: {:?}", line.0, line.1, err)
-            })
-        })
-        .collect();
-    XmasCode { numbers }
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| parse_number(input, line))
+        .collect::<Result<_, _>>()?;
+    Ok(XmasCode { numbers })
+}
+
+#[aoc_generator(day9)]
+pub fn parse_xmas_code(input: &str) -> XmasCode {
+    try_parse_xmas_code(input).unwrap_or_else(|err| panic!("{}", err))
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -1,4 +1,5 @@
 use super::*;
+use crate::answers::recorded_answer;
 
 const INPUT: &str = include_str!("../../input/2020/day9.txt");
 
@@ -45,6 +46,14 @@ fn parse_puzzle_input_xmas_code() {
     assert_eq!(xmas_code.numbers.len(), 1000);
 }
 
+#[test]
+fn try_parse_xmas_code_with_invalid_number() {
+    let error = try_parse_xmas_code("35\n20\n15\n2S\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (4, 1));
+    assert_eq!(error.snippet(), "2S");
+}
+
 #[test]
 fn find_first_invalid_number_in_example() {
     let xmas_code = parse_xmas_code(EXAMPLE);
@@ -60,7 +69,7 @@ fn find_first_invalid_number_in_puzzle_input() {
 
     let first_invalid = first_invalid_number(&xmas_code);
 
-    assert_eq!(first_invalid, 542529149);
+    assert_eq!(first_invalid.to_string(), recorded_answer(9, 1));
 }
 
 #[test]
@@ -78,5 +87,5 @@ fn encryption_weakness_in_puzzle_input() {
 
     let weakness = encryption_weakness(&xmas_code);
 
-    assert_eq!(weakness, 75678618);
+    assert_eq!(weakness.to_string(), recorded_answer(9, 2));
 }
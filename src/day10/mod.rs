@@ -192,8 +192,8 @@
 //!
 //! [Advent of Code 2020 - Day 10](https://adventofcode.com/2020/day/10)
 
+use crate::parse::{parse_number, ParseError};
 use std::collections::HashSet;
-use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Adapter {
@@ -212,23 +212,20 @@ impl Adapter {
 
 const SEAT_CHARGING_OUTLET: Adapter = Adapter { rating: 0 };
 
-#[aoc_generator(day10)]
-pub fn parse_adapter_joltages(input: &str) -> Vec<Adapter> {
+pub fn try_parse_adapter_joltages(input: &str) -> Result<Vec<Adapter>, ParseError> {
     input
         .lines()
-        .enumerate()
-        .map(|line| {
-            let rating = u32::from_str(line.1).unwrap_or_else(|err| {
-                panic!(
-                    "line {}: {:?} is not a valid number: {}",
-                    line.0, line.1, err
-                )
-            });
-            Adapter { rating }
-        })
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| parse_number(input, line).map(|rating| Adapter { rating }))
         .collect()
 }
 
+#[aoc_generator(day10)]
+pub fn parse_adapter_joltages(input: &str) -> Vec<Adapter> {
+    try_parse_adapter_joltages(input).unwrap_or_else(|err| panic!("{}", err))
+}
+
 fn build_chain_of_adapters(all_adapters: &[Adapter]) -> Vec<Adapter> {
     let mut all_adapters = all_adapters.to_vec();
     all_adapters.insert(0, SEAT_CHARGING_OUTLET);
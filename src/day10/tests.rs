@@ -1,4 +1,5 @@
 use super::*;
+use crate::answers::recorded_answer;
 use proptest::prelude::*;
 
 use std::collections::HashSet;
@@ -129,6 +130,14 @@ fn parse_adapter_joltages_in_puzzle_input() {
     );
 }
 
+#[test]
+fn try_parse_adapter_joltages_with_negative_rating() {
+    let error = try_parse_adapter_joltages("16\n10\n-15\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (3, 1));
+    assert_eq!(error.snippet(), "-15");
+}
+
 proptest! {
     #[test]
     fn adapter_cannot_connect_to_source_of_same_rating(
@@ -216,7 +225,7 @@ fn joltage_differences_in_adapter_chain_in_puzzle_input() {
 
     let result = joltage_differences_in_adapter_chain(&all_adapters);
 
-    assert_eq!(result, 1885);
+    assert_eq!(result.to_string(), recorded_answer(10, 1));
 }
 //
 // #[test]
@@ -316,5 +325,5 @@ fn number_of_possible_adapter_arrangements_in_puzzle_input() {
 
     let num_arrangements = number_of_possible_adapter_arrangements(&all_adapters);
 
-    assert_eq!(num_arrangements, 2024782584832);
+    assert_eq!(num_arrangements.to_string(), recorded_answer(10, 2));
 }
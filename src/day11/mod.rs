@@ -266,23 +266,17 @@
 //!
 //! [Advent of Code 2020 - Day 11](https://adventofcode.com/2020/day/11)
 
-use std::mem;
+use crate::automaton::{Automaton, BitCells, Graph, LifeLike};
+use crate::grid::{Direction, Grid};
+use crate::parse::{ParseError, ParseErrorKind};
+use std::convert::TryFrom;
+use std::fmt::{self, Display};
+use std::str::FromStr;
 
 pub const F: Tile = Tile::Floor;
 pub const L: Tile = Tile::EmptySeat;
 pub const O: Tile = Tile::OccupiedSeat;
 
-const NEIGHBORS: [Neighbor; 8] = [
-    Neighbor::North,
-    Neighbor::NorthEast,
-    Neighbor::East,
-    Neighbor::SouthEast,
-    Neighbor::South,
-    Neighbor::SouthWest,
-    Neighbor::West,
-    Neighbor::NorthWest,
-];
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Tile {
     Floor,
@@ -290,296 +284,138 @@ pub enum Tile {
     OccupiedSeat,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Neighbor {
-    North,
-    NorthEast,
-    East,
-    SouthEast,
-    South,
-    SouthWest,
-    West,
-    NorthWest,
-}
+impl TryFrom<char> for Tile {
+    type Error = ParseErrorKind;
 
-#[aoc_generator(day11)]
-pub fn parse_initial_seat_layout(input: &str) -> Vec<Vec<Tile>> {
-    let mut rows = Vec::new();
-    let mut current_row = Vec::new();
-    for c in input.chars() {
-        match c {
-            '.' => current_row.push(Tile::Floor),
-            'L' => current_row.push(Tile::EmptySeat),
-            '#' => current_row.push(Tile::OccupiedSeat),
-            '\n' => {
-                if !current_row.is_empty() {
-                    rows.push(mem::replace(&mut current_row, Vec::new()));
-                }
-            }
-            _ => panic!("unrecognized tile {:?}", c),
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Tile::Floor),
+            'L' => Ok(Tile::EmptySeat),
+            '#' => Ok(Tile::OccupiedSeat),
+            _ => Err(ParseErrorKind::UnexpectedCharacter(value)),
         }
     }
-    rows
 }
 
-fn count_tiles(state: Tile, layout: &[Vec<Tile>]) -> usize {
-    let mut count = 0;
-    for row in layout {
-        for tile in row {
-            if *tile == state {
-                count += 1
-            }
+impl Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tile::Floor => f.write_str("."),
+            Tile::EmptySeat => f.write_str("L"),
+            Tile::OccupiedSeat => f.write_str("#"),
         }
     }
-    count
 }
 
-fn next_generation_part1(prev_layout: &[Vec<Tile>], next_layout: &mut [Vec<Tile>]) {
-    for row in 0..prev_layout.len() {
-        for col in 0..prev_layout[row].len() {
-            let prev_state = prev_layout[row][col];
-            let new_state = match prev_state {
-                Tile::Floor => prev_state,
-                Tile::EmptySeat => {
-                    if 0 == count_neighbors_part1(Tile::OccupiedSeat, row, col, prev_layout) {
-                        Tile::OccupiedSeat
-                    } else {
-                        prev_state
-                    }
-                }
-                Tile::OccupiedSeat => {
-                    if 4 <= count_neighbors_part1(Tile::OccupiedSeat, row, col, prev_layout) {
-                        Tile::EmptySeat
-                    } else {
-                        prev_state
-                    }
-                }
-            };
-            next_layout[row][col] = new_state;
-        }
-    }
+pub type SeatLayout = Grid<Tile>;
+
+pub fn try_parse_initial_seat_layout(input: &str) -> Result<SeatLayout, ParseError> {
+    SeatLayout::from_str(input)
 }
 
-fn count_neighbors_part1(state: Tile, row: usize, col: usize, layout: &[Vec<Tile>]) -> usize {
-    let mut count = 0;
-    let num_rows = layout.len();
-    let num_cols = layout[row].len();
-    for neighbor in &NEIGHBORS {
-        match neighbor {
-            Neighbor::North => {
-                if row > 0 && layout[row - 1][col] == state {
-                    count += 1;
-                }
-            }
-            Neighbor::NorthEast => {
-                if row > 0 && col < num_cols - 1 && layout[row - 1][col + 1] == state {
-                    count += 1;
-                }
-            }
-            Neighbor::East => {
-                if col < num_cols - 1 && layout[row][col + 1] == state {
-                    count += 1;
-                }
-            }
-            Neighbor::SouthEast => {
-                if row < num_rows - 1 && col < num_cols - 1 && layout[row + 1][col + 1] == state {
-                    count += 1;
-                }
-            }
-            Neighbor::South => {
-                if row < num_rows - 1 && layout[row + 1][col] == state {
-                    count += 1;
-                }
-            }
-            Neighbor::SouthWest => {
-                if row < num_rows - 1 && col > 0 && layout[row + 1][col - 1] == state {
-                    count += 1;
-                }
-            }
-            Neighbor::West => {
-                if col > 0 && layout[row][col - 1] == state {
-                    count += 1;
-                }
-            }
-            Neighbor::NorthWest => {
-                if row > 0 && col > 0 && layout[row - 1][col - 1] == state {
-                    count += 1;
-                }
-            }
-        }
-    }
-    count
+#[aoc_generator(day11)]
+pub fn parse_initial_seat_layout(input: &str) -> SeatLayout {
+    try_parse_initial_seat_layout(input).unwrap_or_else(|err| panic!("{}", err))
 }
 
-fn next_generation_part2(prev_layout: &[Vec<Tile>], next_layout: &mut [Vec<Tile>]) {
-    for row in 0..prev_layout.len() {
-        for col in 0..prev_layout[row].len() {
-            let prev_state = prev_layout[row][col];
-            let new_state = match prev_state {
-                Tile::Floor => prev_state,
-                Tile::EmptySeat => {
-                    if 0 == count_neighbors_part2(Tile::OccupiedSeat, row, col, prev_layout) {
-                        Tile::OccupiedSeat
-                    } else {
-                        prev_state
-                    }
-                }
-                Tile::OccupiedSeat => {
-                    if 5 <= count_neighbors_part2(Tile::OccupiedSeat, row, col, prev_layout) {
-                        Tile::EmptySeat
-                    } else {
-                        prev_state
-                    }
-                }
-            };
-            next_layout[row][col] = new_state;
-        }
-    }
+/// An empty seat becomes occupied if no adjacent seat is occupied and an
+/// occupied seat becomes empty if four or more adjacent seats are occupied.
+const SEATING_RULE_PART1: LifeLike = LifeLike::new(&[0], &[0, 1, 2, 3]);
+
+/// An empty seat becomes occupied if no visible seat is occupied and an
+/// occupied seat becomes empty if five or more visible seats are occupied.
+const SEATING_RULE_PART2: LifeLike = LifeLike::new(&[0], &[0, 1, 2, 3, 4]);
+
+/// Returns the number of each seat, counting the seats row by row, or `None`
+/// for the floor.
+fn seat_numbers(layout: &SeatLayout) -> Grid<Option<usize>> {
+    let mut seats = 0..;
+    layout.map(|tile| match tile {
+        Tile::Floor => None,
+        _ => seats.next(),
+    })
 }
 
-fn count_neighbors_part2(state: Tile, row: usize, col: usize, layout: &[Vec<Tile>]) -> usize {
-    let mut count = 0;
-    let num_rows = layout.len();
-    let num_cols = layout[row].len();
-    for neighbor in &NEIGHBORS {
-        match neighbor {
-            Neighbor::North => {
-                for ri in (0..row).rev() {
-                    match layout[ri][col] {
-                        s if s == state => {
-                            count += 1;
-                            break;
-                        }
-                        Tile::Floor => {}
-                        _ => break,
-                    }
-                }
-            }
-            Neighbor::NorthEast => {
-                if col < num_cols - 1 {
-                    for (ri, ci) in (0..row).rev().zip(col + 1..num_cols) {
-                        match layout[ri][ci] {
-                            s if s == state => {
-                                count += 1;
-                                break;
-                            }
-                            Tile::Floor => {}
-                            _ => break,
-                        }
-                    }
-                }
-            }
-            Neighbor::East => {
-                if col < num_cols - 1 {
-                    for ci in col + 1..num_cols {
-                        match layout[row][ci] {
-                            s if s == state => {
-                                count += 1;
-                                break;
-                            }
-                            Tile::Floor => {}
-                            _ => break,
-                        }
-                    }
-                }
-            }
-            Neighbor::SouthEast => {
-                if row < num_rows - 1 && col < num_cols - 1 {
-                    for (ri, ci) in (row + 1..num_rows).zip(col + 1..num_cols) {
-                        match layout[ri][ci] {
-                            s if s == state => {
-                                count += 1;
-                                break;
-                            }
-                            Tile::Floor => {}
-                            _ => break,
-                        }
-                    }
-                }
-            }
-            Neighbor::South => {
-                if row < num_rows - 1 {
-                    for ri in row + 1..num_rows {
-                        match layout[ri][col] {
-                            s if s == state => {
-                                count += 1;
-                                break;
-                            }
-                            Tile::Floor => {}
-                            _ => break,
-                        }
-                    }
-                }
-            }
-            Neighbor::SouthWest => {
-                if row < num_rows - 1 {
-                    for (ri, ci) in (row + 1..num_rows).zip((0..col).rev()) {
-                        match layout[ri][ci] {
-                            s if s == state => {
-                                count += 1;
-                                break;
-                            }
-                            Tile::Floor => {}
-                            _ => break,
-                        }
-                    }
-                }
-            }
-            Neighbor::West => {
-                for ci in (0..col).rev() {
-                    match layout[row][ci] {
-                        s if s == state => {
-                            count += 1;
-                            break;
-                        }
-                        Tile::Floor => {}
-                        _ => break,
-                    }
-                }
-            }
-            Neighbor::NorthWest => {
-                for (ri, ci) in (0..row).rev().zip((0..col).rev()) {
-                    match layout[ri][ci] {
-                        s if s == state => {
-                            count += 1;
-                            break;
-                        }
-                        Tile::Floor => {}
-                        _ => break,
-                    }
-                }
-            }
-        }
-    }
-    count
+/// Returns the seats with the adjacent seats as neighbors.
+pub fn adjacent_seats(layout: &SeatLayout) -> Graph {
+    let numbers = &seat_numbers(layout);
+    Graph::new(
+        numbers
+            .positions()
+            .filter(|position| numbers[*position].is_some())
+            .map(|position| {
+                numbers
+                    .neighbors(position)
+                    .filter_map(|(_, number)| *number)
+            }),
+    )
+}
+
+/// Returns the seats with the first seat seen in each of the eight
+/// directions as neighbors.
+pub fn visible_seats(layout: &SeatLayout) -> Graph {
+    let numbers = &seat_numbers(layout);
+    Graph::new(
+        numbers
+            .positions()
+            .filter(|position| numbers[*position].is_some())
+            .map(|position| {
+                Direction::ALL.iter().filter_map(move |direction| {
+                    numbers
+                        .ray(position, *direction)
+                        .find_map(|(_, number)| *number)
+                })
+            }),
+    )
+}
+
+/// Returns the states of the seats in the order of their numbers.
+fn occupied_seats(layout: &SeatLayout) -> BitCells {
+    BitCells::new(
+        layout
+            .iter()
+            .filter(|tile| **tile != Tile::Floor)
+            .map(|tile| *tile == Tile::OccupiedSeat),
+    )
+}
+
+/// Returns the layout with the seats occupied as given.
+pub fn seat_layout(layout: &SeatLayout, occupied: &BitCells) -> SeatLayout {
+    seat_numbers(layout).map(|number| match number {
+        None => Tile::Floor,
+        Some(seat) if occupied.get(*seat) => Tile::OccupiedSeat,
+        Some(_) => Tile::EmptySeat,
+    })
+}
+
+fn seating_part1(initial_layout: &SeatLayout) -> Automaton<Graph, LifeLike> {
+    Automaton::new(
+        adjacent_seats(initial_layout),
+        SEATING_RULE_PART1,
+        occupied_seats(initial_layout),
+    )
+}
+
+fn seating_part2(initial_layout: &SeatLayout) -> Automaton<Graph, LifeLike> {
+    Automaton::new(
+        visible_seats(initial_layout),
+        SEATING_RULE_PART2,
+        occupied_seats(initial_layout),
+    )
 }
 
 #[aoc(day11, part1)]
-pub fn number_of_occupied_seats_part1(initial_layout: &[Vec<Tile>]) -> usize {
-    let mut prev_layout = initial_layout.to_vec();
-    let mut next_layout = initial_layout.to_vec();
-    loop {
-        next_generation_part1(&prev_layout, &mut next_layout);
-        if next_layout == prev_layout {
-            break;
-        }
-        mem::swap(&mut prev_layout, &mut next_layout);
-    }
-    count_tiles(Tile::OccupiedSeat, &next_layout)
+pub fn number_of_occupied_seats_part1(initial_layout: &SeatLayout) -> usize {
+    let mut seating = seating_part1(initial_layout);
+    seating.run_until_stable();
+    seating.population()
 }
 
 #[aoc(day11, part2)]
-pub fn number_of_occupied_seats_part2(initial_layout: &[Vec<Tile>]) -> usize {
-    let mut prev_layout = initial_layout.to_vec();
-    let mut next_layout = initial_layout.to_vec();
-    loop {
-        next_generation_part2(&prev_layout, &mut next_layout);
-        if next_layout == prev_layout {
-            break;
-        }
-        mem::swap(&mut prev_layout, &mut next_layout);
-    }
-    count_tiles(Tile::OccupiedSeat, &next_layout)
+pub fn number_of_occupied_seats_part2(initial_layout: &SeatLayout) -> usize {
+    let mut seating = seating_part2(initial_layout);
+    seating.run_until_stable();
+    seating.population()
 }
 
 #[cfg(test)]
@@ -1,4 +1,6 @@
 use super::*;
+use crate::answers::recorded_answer;
+use crate::grid::Position;
 
 const INPUT: &str = include_str!("../../input/2020/day11.txt");
 
@@ -21,7 +23,7 @@ fn parse_initial_seat_layout_in_example1() {
 
     assert_eq!(
         seat_layout,
-        vec![
+        Grid::from_rows(vec![
             [L, F, L, L, F, L, L, F, L, L],
             [L, L, L, L, L, L, L, F, L, L],
             [L, F, L, F, L, F, F, L, F, F],
@@ -32,7 +34,7 @@ fn parse_initial_seat_layout_in_example1() {
             [L, L, L, L, L, L, L, L, L, L],
             [L, F, L, L, L, L, L, L, F, L],
             [L, F, L, L, L, L, L, F, L, L],
-        ]
+        ])
     );
 }
 
@@ -40,19 +42,52 @@ fn parse_initial_seat_layout_in_example1() {
 fn parse_initial_seat_layout_in_puzzle_input() {
     let seat_layout = parse_initial_seat_layout(INPUT);
 
-    assert_eq!(seat_layout.len(), 90);
+    assert_eq!(seat_layout.height(), 90);
+}
+
+#[test]
+fn try_parse_initial_seat_layout_with_unrecognized_tile() {
+    let error = try_parse_initial_seat_layout("L.LL\nLLxL\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (2, 3));
+    assert_eq!(error.kind(), &ParseErrorKind::UnexpectedCharacter('x'));
+}
+
+#[test]
+fn visible_seats_of_empty_seat_seeing_eight_occupied_seats() {
+    let layout = parse_initial_seat_layout(
+        "\
+.......#.
+...#.....
+.#.......
+.........
+..#L....#
+....#....
+.........
+#........
+...#.....
+",
+    );
+
+    let seats = visible_seats(&layout);
+
+    let empty_seat = 4;
+    assert_eq!(layout[Position::new(4, 3)], L);
+    assert_eq!(seats.neighbors(empty_seat).len(), 8);
+    assert_eq!(adjacent_seats(&layout).neighbors(empty_seat), &[6, 3]);
 }
 
 #[test]
 fn one_generation_after_initial_layout_in_example1() {
     let generation0 = parse_initial_seat_layout(EXAMPLE1);
 
-    let mut generation1 = generation0.clone();
-    next_generation_part1(&generation0, &mut generation1);
+    let mut seating = seating_part1(&generation0);
+    seating.step();
+    let generation1 = seat_layout(&generation0, seating.cells());
 
     assert_eq!(
         generation1,
-        vec![
+        Grid::from_rows(vec![
             [O, F, O, O, F, O, O, F, O, O],
             [O, O, O, O, O, O, O, F, O, O],
             [O, F, O, F, O, F, F, O, F, F],
@@ -63,7 +98,7 @@ fn one_generation_after_initial_layout_in_example1() {
             [O, O, O, O, O, O, O, O, O, O],
             [O, F, O, O, O, O, O, O, F, O],
             [O, F, O, O, O, O, O, F, O, O],
-        ]
+        ])
     )
 }
 
@@ -82,7 +117,7 @@ fn number_of_occupied_seats_for_part1_in_puzzle_input() {
 
     let num_occupied = number_of_occupied_seats_part1(&seat_layout);
 
-    assert_eq!(num_occupied, 2324);
+    assert_eq!(num_occupied.to_string(), recorded_answer(11, 1));
 }
 
 #[test]
@@ -100,5 +135,5 @@ fn number_of_occupied_seats_for_part2_in_puzzle_input() {
 
     let num_occupied = number_of_occupied_seats_part2(&seat_layout);
 
-    assert_eq!(num_occupied, 2068);
+    assert_eq!(num_occupied.to_string(), recorded_answer(11, 2));
 }
@@ -103,8 +103,8 @@
 //!
 //! [Advent of Code 2020 - Day 12](https://adventofcode.com/2020/day/12)
 
+use crate::parse::{parse_number, ParseError, ParseErrorKind};
 use std::ops::{Add, AddAssign};
-use std::str::FromStr;
 
 pub type Mile = i32;
 
@@ -348,64 +348,46 @@ pub trait AutoPilot {
     }
 }
 
-#[aoc_generator(day12)]
-pub fn parse_navigation_instructions(input: &str) -> Vec<Navigate> {
+pub fn try_parse_navigation_instructions(input: &str) -> Result<Vec<Navigate>, ParseError> {
     let mut instructions = Vec::new();
 
-    for (lno, line) in input.lines().enumerate() {
-        if line.is_empty() {
-            continue;
-        }
-
-        let amount = i32::from_str(&line[1..]).unwrap_or_else(|err| {
-            panic!(
-                "line {}: not a number {:?}; reason: {}",
-                lno + 1,
-                &line[1..],
-                err
-            )
-        });
+    for line in input.lines().map(str::trim) {
+        let code = match line.chars().next() {
+            Some(code) => code,
+            None => continue,
+        };
+        let (code_str, amount_str) = line.split_at(code.len_utf8());
+        let amount = parse_number(input, amount_str)?;
+        let angle = || {
+            parse_angle(amount)
+                .ok_or_else(|| ParseError::at(input, amount_str, ParseErrorKind::Invalid("angle")))
+        };
 
-        let instruction = match line.chars().nth(0).unwrap() {
+        let instruction = match code {
             'N' => Navigate::North(amount),
             'S' => Navigate::South(amount),
             'E' => Navigate::East(amount),
             'W' => Navigate::West(amount),
-            'L' => {
-                if let Some(angle) = parse_angle(amount) {
-                    Navigate::RotateLeft(angle)
-                } else {
-                    panic!(
-                        "line {}: invalid amount for turn left: {:?}",
-                        lno + 1,
-                        amount
-                    );
-                }
-            }
-            'R' => {
-                if let Some(angle) = parse_angle(amount) {
-                    Navigate::RotateRight(angle)
-                } else {
-                    panic!(
-                        "line {}: invalid amount for turn left: {:?}",
-                        lno + 1,
-                        amount
-                    );
-                }
-            }
+            'L' => Navigate::RotateLeft(angle()?),
+            'R' => Navigate::RotateRight(angle()?),
             'F' => Navigate::Forward(amount),
             _ => {
-                panic!(
-                    "line {}: unrecognized instruction code in '{:?}'",
-                    lno + 1,
-                    line
-                );
+                return Err(ParseError::at(
+                    input,
+                    code_str,
+                    ParseErrorKind::UnexpectedCharacter(code),
+                ))
             }
         };
 
         instructions.push(instruction);
     }
-    instructions
+    Ok(instructions)
+}
+
+#[aoc_generator(day12)]
+pub fn parse_navigation_instructions(input: &str) -> Vec<Navigate> {
+    try_parse_navigation_instructions(input).unwrap_or_else(|err| panic!("{}", err))
 }
 
 fn parse_angle(amount: i32) -> Option<Angle> {
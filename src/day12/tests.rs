@@ -1,4 +1,5 @@
 use super::*;
+use crate::answers::recorded_answer;
 
 const INPUT: &str = include_str!("../../input/2020/day12.txt");
 
@@ -33,6 +34,14 @@ fn parse_navigation_instructions_in_puzzle_input() {
     assert_eq!(instructions.len(), 759);
 }
 
+#[test]
+fn try_parse_navigation_instructions_with_invalid_angle() {
+    let error = try_parse_navigation_instructions("F10\nN3\nR45\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (3, 2));
+    assert_eq!(error.kind(), &ParseErrorKind::Invalid("angle"));
+}
+
 #[test]
 fn ship_starts_at_position_0_0_facing_east_by_default() {
     let ship = Ship::default();
@@ -82,7 +91,7 @@ fn part1_distance_to_final_position_in_puzzle_input() {
 
     let distance = part1_distance_to_final_position(&instructions);
 
-    assert_eq!(distance, 923);
+    assert_eq!(distance.to_string(), recorded_answer(12, 1));
 }
 
 #[test]
@@ -123,5 +132,5 @@ fn part2_distance_to_final_position_in_puzzle_input() {
 
     let distance = part2_distance_to_final_position(&instructions);
 
-    assert_eq!(distance, 24769);
+    assert_eq!(distance.to_string(), recorded_answer(12, 2));
 }
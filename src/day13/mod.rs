@@ -158,7 +158,7 @@
 //!
 //! [Advent of Code 2020 - Day 13](https://adventofcode.com/2020/day/13)
 
-use std::str::FromStr;
+use crate::parse::{end_of, parse_number, ParseError, ParseErrorKind};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BusId {
@@ -172,32 +172,43 @@ pub struct BusServiceNotes {
     pub bus_lines: Vec<BusId>,
 }
 
-#[aoc_generator(day13)]
-pub fn parse_bus_service_notes(input: &str) -> BusServiceNotes {
-    let mut lines = input.lines();
-    let time_code = lines
-        .next()
-        .unwrap_or_else(|| panic!("no line with arrival time in input"));
-    let arrival_time =
-        u32::from_str(time_code).unwrap_or_else(|err| panic!("no a valid time code {:?}", err));
-    let bus_lines = lines
-        .next()
-        .unwrap_or_else(|| panic!("no line with bus ids in input"))
+pub fn try_parse_bus_service_notes(input: &str) -> Result<BusServiceNotes, ParseError> {
+    let mut lines = input.lines().map(str::trim);
+    let time_code = lines.next().ok_or_else(|| {
+        ParseError::at(
+            input,
+            input,
+            ParseErrorKind::Missing("line with arrival time"),
+        )
+    })?;
+    let arrival_time = parse_number(input, time_code)?;
+    let bus_ids = lines.next().ok_or_else(|| {
+        ParseError::at(
+            input,
+            end_of(input),
+            ParseErrorKind::Missing("line with bus ids"),
+        )
+    })?;
+    let bus_lines = bus_ids
         .split(',')
         .map(|s| {
             if s == "x" {
                 Ok(BusId::OutOfService)
             } else {
-                u32::from_str(s).map(|id| BusId::Scheduled(id))
+                parse_number(input, s).map(BusId::Scheduled)
             }
         })
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap_or_else(|err| panic!("unrecognized characters for bus ids: {}", err));
+        .collect::<Result<Vec<_>, _>>()?;
 
-    BusServiceNotes {
+    Ok(BusServiceNotes {
         arrival_time,
         bus_lines,
-    }
+    })
+}
+
+#[aoc_generator(day13)]
+pub fn parse_bus_service_notes(input: &str) -> BusServiceNotes {
+    try_parse_bus_service_notes(input).unwrap_or_else(|err| panic!("{}", err))
 }
 
 fn earliest_bus_to_catch(bus_services: &BusServiceNotes) -> Option<(BusId, u32)> {
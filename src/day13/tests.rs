@@ -1,4 +1,6 @@
 use super::*;
+use crate::answers::recorded_answer;
+use crate::registry::Answer;
 
 const INPUT: &str = include_str!("../../input/2020/day13.txt");
 
@@ -54,6 +56,21 @@ fn parse_bus_service_notes_in_example() {
     );
 }
 
+#[test]
+fn try_parse_bus_service_notes_with_invalid_bus_id() {
+    let error = try_parse_bus_service_notes("939\n7,13,x,x,S9,x,31,19\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (2, 10));
+    assert_eq!(error.snippet(), "S9");
+}
+
+#[test]
+fn try_parse_bus_service_notes_without_bus_ids() {
+    let error = try_parse_bus_service_notes("939\n").unwrap_err();
+
+    assert_eq!(error.kind(), &ParseErrorKind::Missing("line with bus ids"));
+}
+
 #[test]
 fn earliest_bus_to_catch_in_example1() {
     let notes = parse_bus_service_notes(EXAMPLE1);
@@ -78,7 +95,7 @@ fn earliest_bus_result_in_puzzle_input() {
 
     let result = earliest_bus_result(&notes);
 
-    assert_eq!(result, Some(2045));
+    assert_eq!(result.to_answer(), recorded_answer(13, 1));
 }
 
 #[test]
@@ -142,5 +159,5 @@ fn earliest_time_buses_depart_in_sequence_in_puzzle_input() {
 
     let earliest_time = earliest_time_buses_depart_in_sequence(&notes);
 
-    assert_eq!(earliest_time, 402251700208309);
+    assert_eq!(earliest_time.to_string(), recorded_answer(13, 2));
 }
@@ -158,6 +158,7 @@
 //!
 //! [Advent of Code 2020 - Day 14](https://adventofcode.com/2020/day/14)
 
+use crate::parse::{end_of, parse_number, ParseError, ParseErrorKind};
 use std::collections::HashMap;
 use std::str::FromStr;
 
@@ -292,65 +293,49 @@ where
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Token {
-    Address,
-    Value,
-    Mask,
-}
-
-#[aoc_generator(day14)]
-pub fn parse_init_program(input: &str) -> Program {
+pub fn try_parse_init_program(input: &str) -> Result<Program, ParseError> {
     let mut instructions = Vec::new();
-    let mut opcode = String::new();
-    let mut mask = String::new();
-    let mut address = String::new();
-    let mut value = String::new();
-    let mut token = Token::Value;
 
-    for c in input.chars() {
-        match c {
-            'a'..='z' => opcode.push(c),
-            '[' => token = Token::Address,
-            ']' => {}
-            '=' => match token {
-                Token::Address => token = Token::Value,
-                _ => token = Token::Mask,
-            },
-            'X' | '0' if token == Token::Mask => {
-                mask.push(c);
-            }
-            '1' if token == Token::Mask => {
-                mask.push(c);
-            }
-            '0'..='9' => match token {
-                Token::Address => address.push(c),
-                Token::Value => value.push(c),
-                _ => panic!("unexpected digit at this position"),
-            },
-            '\n' => {
-                match &opcode[..] {
-                    "mask" => {
-                        instructions.push(Instruction::Mask(mask.clone()));
-                        mask.clear();
-                    }
-                    "mem" => {
-                        let addr = u64::from_str(&address).unwrap();
-                        let val = u64::from_str(&value).unwrap();
-                        instructions.push(Instruction::Mem(addr, val));
-                        address.clear();
-                        value.clear();
-                    }
-                    _ => panic!("unrecognized opcode {:?}", opcode),
-                }
-                opcode.clear();
+    for line in input.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let assign = line
+            .find('=')
+            .ok_or_else(|| ParseError::at(input, end_of(line), ParseErrorKind::Missing("'='")))?;
+        let target = line[..assign].trim();
+        let value = line[assign + 1..].trim();
+        if target == "mask" {
+            if let Some((index, c)) = value
+                .char_indices()
+                .find(|(_, c)| !matches!(c, 'X' | '0' | '1'))
+            {
+                return Err(ParseError::at(
+                    input,
+                    &value[index..index + c.len_utf8()],
+                    ParseErrorKind::UnexpectedCharacter(c),
+                ));
             }
-            _ if c.is_whitespace() => {}
-            _ => panic!("unrecognized character {:?}", c),
+            instructions.push(Instruction::Mask(value.to_string()));
+        } else if target.starts_with("mem[") && target.ends_with(']') {
+            let address = parse_number(input, &target[4..target.len() - 1])?;
+            let value = parse_number(input, value)?;
+            instructions.push(Instruction::Mem(address, value));
+        } else {
+            return Err(ParseError::at(
+                input,
+                target,
+                ParseErrorKind::Invalid("operation"),
+            ));
         }
     }
 
-    Program { instructions }
+    Ok(Program { instructions })
+}
+
+#[aoc_generator(day14)]
+pub fn parse_init_program(input: &str) -> Program {
+    try_parse_init_program(input).unwrap_or_else(|err| panic!("{}", err))
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
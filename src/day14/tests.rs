@@ -1,4 +1,5 @@
 use super::*;
+use crate::answers::recorded_answer;
 
 const INPUT: &str = include_str!("../../input/2020/day14.txt");
 
@@ -40,6 +41,25 @@ fn parse_init_program_in_puzzle_input() {
     assert_eq!(program.instructions.len(), 576);
 }
 
+#[test]
+fn try_parse_init_program_with_invalid_mask() {
+    let error = try_parse_init_program(
+        "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X\nmem[8] = 11\nmask = 1X2X\n",
+    )
+    .unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (3, 10));
+    assert_eq!(error.kind(), &ParseErrorKind::UnexpectedCharacter('2'));
+}
+
+#[test]
+fn try_parse_init_program_with_invalid_address() {
+    let error = try_parse_init_program("mem[0x8] = 11\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (1, 5));
+    assert_eq!(error.snippet(), "0x8");
+}
+
 #[test]
 fn sum_of_all_values_in_memory_v1_in_example1() {
     let init_program = parse_init_program(EXAMPLE1);
@@ -55,7 +75,7 @@ fn sum_of_all_values_in_memory_in_v1_puzzle_input() {
 
     let result = sum_of_all_values_in_memory_v1(&init_program);
 
-    assert_eq!(result, 7997531787333);
+    assert_eq!(result.to_string(), recorded_answer(14, 1));
 }
 
 #[test]
@@ -105,5 +125,5 @@ fn sum_of_all_values_in_memory_in_v2_puzzle_input() {
 
     let result = sum_of_all_values_in_memory_v2(&init_program);
 
-    assert_eq!(result, 3564822193820);
+    assert_eq!(result.to_string(), recorded_answer(14, 2));
 }
@@ -76,9 +76,9 @@
 //!
 //! [Advent of Code 2020 - Day 15](https://adventofcode.com/2020/day/15)
 
+use crate::parse::{parse_number, ParseError};
 use fxhash::FxBuildHasher;
 use hashbrown::HashMap;
-use std::str::FromStr;
 
 pub type Number = u32;
 
@@ -151,16 +151,18 @@ impl Iterator for Memory {
     }
 }
 
-#[aoc_generator(day15)]
-pub fn parse_starting_numbers(input: &str) -> Vec<u32> {
+pub fn try_parse_starting_numbers(input: &str) -> Result<Vec<u32>, ParseError> {
     input
         .split(',')
-        .map(|s| {
-            u32::from_str(s.trim()).unwrap_or_else(|err| panic!("not a number {:?}; {}", s, err))
-        })
+        .map(|s| parse_number(input, s.trim()))
         .collect()
 }
 
+#[aoc_generator(day15)]
+pub fn parse_starting_numbers(input: &str) -> Vec<u32> {
+    try_parse_starting_numbers(input).unwrap_or_else(|err| panic!("{}", err))
+}
+
 #[aoc(day15, part1)]
 pub fn determine_the_2020th_number_spoken(numbers: &[u32]) -> u32 {
     let mut memory = Memory::new();
@@ -1,4 +1,5 @@
 use super::*;
+use crate::answers::recorded_answer;
 
 const INPUT: &str = include_str!("../../input/2020/day15.txt");
 
@@ -18,6 +19,14 @@ fn parse_starting_numbers_in_example1() {
     assert_eq!(numbers, vec![0, 3, 6]);
 }
 
+#[test]
+fn try_parse_starting_numbers_with_invalid_number() {
+    let error = try_parse_starting_numbers("0,3,six").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (1, 5));
+    assert_eq!(error.snippet(), "six");
+}
+
 #[test]
 fn determine_the_2020th_number_spoken_in_example1() {
     let numbers = parse_starting_numbers(EXAMPLE1);
@@ -87,7 +96,7 @@ fn determine_the_2020th_number_spoken_in_puzzle_input() {
 
     let num2020 = determine_the_2020th_number_spoken(&numbers);
 
-    assert_eq!(num2020, 211);
+    assert_eq!(num2020.to_string(), recorded_answer(15, 1));
 }
 
 #[test]
@@ -159,5 +168,5 @@ fn determine_the_30millionsth_number_spoken_in_puzzle_input() {
 
     let num30mill = determine_the_30millionsth_number_spoken(&numbers);
 
-    assert_eq!(num30mill, 2159626);
+    assert_eq!(num30mill.to_string(), recorded_answer(15, 2));
 }
@@ -112,12 +112,10 @@
 //!
 //! [Advent of Code 2020 - Day 16](https://adventofcode.com/2020/day/16)
 
-use hashbrown::HashMap;
-use std::collections::hash_map::RandomState;
-use std::collections::HashSet;
-use std::iter::FromIterator;
+use crate::assignment::assign_by_elimination;
+use crate::parse::{end_of, parse_number, ParseError, ParseErrorKind};
+use hashbrown::{HashMap, HashSet};
 use std::ops::RangeInclusive;
-use std::str::FromStr;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Field {
@@ -143,68 +141,82 @@ pub struct TicketNotes {
     pub nearby_tickets: Vec<Ticket>,
 }
 
-#[aoc_generator(day16)]
-pub fn parse_ticket_notes(input: &str) -> TicketNotes {
+fn parse_range(input: &str, s: &str) -> Result<RangeInclusive<u32>, ParseError> {
+    let dash = s
+        .find('-')
+        .ok_or_else(|| ParseError::at(input, s, ParseErrorKind::Missing("'-' in range")))?;
+    let min = parse_number(input, s[..dash].trim())?;
+    let max = parse_number(input, s[dash + 1..].trim())?;
+    Ok(min..=max)
+}
+
+/// Parses a ticket, which must have one value per field.
+fn parse_ticket(input: &str, line: &str, num_fields: usize) -> Result<Ticket, ParseError> {
+    let values = line
+        .split(',')
+        .map(|s| parse_number(input, s.trim()))
+        .collect::<Result<Vec<_>, _>>()?;
+    if values.len() != num_fields {
+        return Err(ParseError::at(
+            input,
+            line,
+            ParseErrorKind::Invalid("ticket, it must have one value per field"),
+        ));
+    }
+    Ok(Ticket { values })
+}
+
+pub fn try_parse_ticket_notes(input: &str) -> Result<TicketNotes, ParseError> {
     let mut fields = Vec::new();
-    let mut my_ticket = None;
-    let mut nearby_tickets = Vec::new();
 
     let mut lines = input.trim_end().lines();
     while let Some(line) = lines.next() {
         if line.trim().is_empty() {
             break;
         }
-        let mut line_split = line.split(':');
-        let label = line_split.next().unwrap();
-        let mut range_split = line_split.next().unwrap().split(" or ");
-        let mut range1 = range_split.next().unwrap().split('-');
-        let mut range2 = range_split.next().unwrap().split('-');
-        let range1_min = u32::from_str(range1.next().unwrap().trim()).unwrap();
-        let range1_max = u32::from_str(range1.next().unwrap().trim()).unwrap();
-        let range2_min = u32::from_str(range2.next().unwrap().trim()).unwrap();
-        let range2_max = u32::from_str(range2.next().unwrap().trim()).unwrap();
+        let colon = line.find(':').ok_or_else(|| {
+            ParseError::at(
+                input,
+                end_of(line),
+                ParseErrorKind::Missing("':' after label"),
+            )
+        })?;
+        let ranges_str = &line[colon + 1..];
+        let or = ranges_str.find(" or ").ok_or_else(|| {
+            ParseError::at(input, ranges_str, ParseErrorKind::Missing("second range"))
+        })?;
         let field = Field {
-            label: label.to_string(),
-            ranges: [range1_min..=range1_max, range2_min..=range2_max],
+            label: line[..colon].to_string(),
+            ranges: [
+                parse_range(input, &ranges_str[..or])?,
+                parse_range(input, &ranges_str[or + 4..])?,
+            ],
         };
         fields.push(field);
     }
 
-    while let Some(line) = lines.next() {
-        if line.starts_with("your ticket:") {
-            break;
-        }
-    }
-    if let Some(line) = lines.next() {
-        let values = line
-            .split(',')
-            .map(|s| {
-                u32::from_str(s).unwrap_or_else(|err| panic!("not a valid number {:?}; {}", s, err))
-            })
-            .collect();
-        my_ticket = Some(Ticket { values })
-    }
+    let my_ticket = lines
+        .find(|line| line.starts_with("your ticket:"))
+        .and_then(|_| lines.next())
+        .ok_or_else(|| ParseError::at(input, end_of(input), ParseErrorKind::Missing("my ticket")))
+        .and_then(|line| parse_ticket(input, line, fields.len()))?;
 
-    while let Some(line) = lines.next() {
-        if line.starts_with("nearby tickets:") {
-            break;
-        }
-    }
-    while let Some(line) = lines.next() {
-        let values = line
-            .split(',')
-            .map(|s| {
-                u32::from_str(s).unwrap_or_else(|err| panic!("not a valid number {:?}; {}", s, err))
-            })
-            .collect();
-        nearby_tickets.push(Ticket { values })
-    }
+    let nearby_tickets = lines
+        .skip_while(|line| !line.starts_with("nearby tickets:"))
+        .skip(1)
+        .map(|line| parse_ticket(input, line, fields.len()))
+        .collect::<Result<_, _>>()?;
 
-    TicketNotes {
+    Ok(TicketNotes {
         fields,
-        my_ticket: my_ticket.unwrap(),
+        my_ticket,
         nearby_tickets,
-    }
+    })
+}
+
+#[aoc_generator(day16)]
+pub fn parse_ticket_notes(input: &str) -> TicketNotes {
+    try_parse_ticket_notes(input).unwrap_or_else(|err| panic!("{}", err))
 }
 
 fn find_invalid_values_in_ticket(ticket: &Ticket, fields: &[Field]) -> Vec<u32> {
@@ -246,43 +258,23 @@ fn determine_field_positions(ticket_notes: &TicketNotes) -> HashMap<String, usiz
         })
         .collect::<Vec<_>>();
 
-    let num_fields = ticket_notes.fields.len();
-
-    let mut invalid_positions: HashMap<usize, HashSet<String>> = HashMap::with_capacity(num_fields);
-    for ticket in &valid_nearby_tickets {
-        for (position, value) in ticket.values.iter().enumerate() {
-            let position_entry = invalid_positions
-                .entry(position)
-                .or_insert_with(HashSet::new);
-            for field in &ticket_notes.fields {
-                if !field.is_valid(*value) {
-                    position_entry.insert(field.label.clone());
-                }
-            }
-        }
-    }
-
-    debug_assert_eq!(invalid_positions.len(), num_fields);
-
-    let mut field_positions = HashMap::with_capacity(num_fields);
-    let mut remaining_fields = HashSet::<_, RandomState>::from_iter(
-        ticket_notes.fields.iter().map(|fld| fld.label.clone()),
-    );
-    while !remaining_fields.is_empty() {
-        for (position, invalid_fields) in &invalid_positions {
-            let mut valid_fields = remaining_fields
-                .difference(invalid_fields)
-                .cloned()
-                .collect::<Vec<_>>();
-            if valid_fields.len() == 1 {
-                remaining_fields.remove(&valid_fields[0]);
-                field_positions.insert(valid_fields.remove(0), *position);
-                break;
-            }
-        }
-    }
+    let num_positions = ticket_notes.my_ticket.values.len();
+    let candidates = ticket_notes
+        .fields
+        .iter()
+        .map(|field| {
+            let positions = (0..num_positions)
+                .filter(|position| {
+                    valid_nearby_tickets
+                        .iter()
+                        .all(|ticket| field.is_valid(ticket.values[*position]))
+                })
+                .collect::<HashSet<_>>();
+            (field.label.clone(), positions)
+        })
+        .collect::<HashMap<_, _>>();
 
-    field_positions
+    assign_by_elimination(candidates).expect("no unique field positions found!")
 }
 
 #[aoc(day16, part2)]
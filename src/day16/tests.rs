@@ -1,4 +1,5 @@
 use super::*;
+use crate::answers::recorded_answer;
 
 const INPUT: &str = include_str!("../../input/2020/day16.txt");
 
@@ -81,6 +82,45 @@ fn parse_ticket_notes_in_puzzle_input() {
     assert_eq!(notes.nearby_tickets.len(), 261 - 25);
 }
 
+#[test]
+fn try_parse_ticket_notes_with_invalid_range() {
+    let error = try_parse_ticket_notes("class: 1-3 or 5-7\nrow: 6-11 or 33+44\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (2, 14));
+    assert_eq!(error.kind(), &ParseErrorKind::Missing("'-' in range"));
+}
+
+#[test]
+fn try_parse_ticket_notes_without_my_ticket() {
+    let error =
+        try_parse_ticket_notes("class: 1-3 or 5-7\n\nnearby tickets:\n7,3,47\n").unwrap_err();
+
+    assert_eq!(error.kind(), &ParseErrorKind::Missing("my ticket"));
+}
+
+#[test]
+fn try_parse_ticket_notes_with_nearby_ticket_missing_a_value() {
+    let error = try_parse_ticket_notes(
+        "class: 1-3 or 5-7\nrow: 6-11 or 33-44\n\nyour ticket:\n7,1\n\nnearby tickets:\n7,3\n40\n",
+    )
+    .unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (9, 1));
+    assert_eq!(
+        error.kind(),
+        &ParseErrorKind::Invalid("ticket, it must have one value per field")
+    );
+}
+
+#[test]
+fn try_parse_ticket_notes_with_my_ticket_having_too_many_values() {
+    let error =
+        try_parse_ticket_notes("class: 1-3 or 5-7\n\nyour ticket:\n7,1\n\nnearby tickets:\n7\n")
+            .unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (4, 1));
+}
+
 #[test]
 fn all_tickets_have_same_count_of_fields_in_puzzle_input() {
     let notes = parse_ticket_notes(INPUT);
@@ -107,7 +147,7 @@ fn ticket_scanning_error_rate_in_puzzle_input() {
 
     let error_rate = ticket_scanning_error_rate(&notes);
 
-    assert_eq!(error_rate, 19087);
+    assert_eq!(error_rate.to_string(), recorded_answer(16, 1));
 }
 
 #[test]
@@ -155,5 +195,5 @@ fn product_of_departure_values_in_my_ticket_in_puzzle_input() {
 
     let result = product_of_departure_values_in_my_ticket(&notes);
 
-    assert_eq!(result, 1382443095281);
+    assert_eq!(result.to_string(), recorded_answer(16, 2));
 }
@@ -445,41 +445,38 @@
 //!
 //! [Advent of Code 2020 - Day 17](https://adventofcode.com/2020/day/17)
 
-use fxhash::FxBuildHasher;
-use hashbrown::HashSet;
-use std::hash::Hash;
-use std::iter::FromIterator;
+use crate::automaton::{Lattice, LifeLike, LiveCells, NeighborCounts, SymmetricLattice, Topology};
+use crate::grid::Grid;
+use crate::parse::{ParseError, ParseErrorKind};
+use std::convert::TryFrom;
+use std::fmt::{self, Display};
+use std::io::{self, Write};
 use std::mem;
 use std::ops::Deref;
+use std::str::FromStr;
 
 pub type Coord = i64;
 
-pub type D3 = [Coord; 3];
-pub type D4 = [Coord; 4];
-
-pub type Position3D = Position<D3>;
-pub type Position4D = Position<D4>;
+pub type Position3D = Position<3>;
+pub type Position4D = Position<4>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Position<D>(D);
+pub struct Position<const N: usize>([Coord; N]);
 
-impl<D> Default for Position<D>
-where
-    D: Default,
-{
+impl<const N: usize> Default for Position<N> {
     fn default() -> Self {
-        Position(D::default())
+        Position([0; N])
     }
 }
 
-impl<D> From<D> for Position<D> {
-    fn from(coords: D) -> Self {
+impl<const N: usize> From<[Coord; N]> for Position<N> {
+    fn from(coords: [Coord; N]) -> Self {
         Position(coords)
     }
 }
 
-impl<D> Deref for Position<D> {
-    type Target = D;
+impl<const N: usize> Deref for Position<N> {
+    type Target = [Coord; N];
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -492,420 +489,438 @@ pub enum State {
     Inactive,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct PocketDimension<D>
-where
-    D: Eq + Hash,
-{
-    active_cubes: HashSet<Position<D>, FxBuildHasher>,
-    next_gen: HashSet<Position<D>, FxBuildHasher>,
-}
+impl TryFrom<char> for State {
+    type Error = ParseErrorKind;
 
-impl From<&PocketDimension<D3>> for PocketDimension<D4> {
-    fn from(value: &PocketDimension<D3>) -> Self {
-        let active_cubes = HashSet::from_iter(value.active_cubes.iter().map(|p3| {
-            (0..p3.0.len()).fold(Position::from([0; 4]), |mut p4, i| {
-                p4.0[i] = p3.0[i];
-                p4
-            })
-        }));
-        let next_gen = HashSet::from_iter(value.next_gen.iter().map(|p3| {
-            (0..p3.0.len()).fold(Position::from([0; 4]), |mut p4, i| {
-                p4.0[i] = p3.0[i];
-                p4
-            })
-        }));
-        Self {
-            active_cubes,
-            next_gen,
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '#' => Ok(State::Active),
+            '.' => Ok(State::Inactive),
+            _ => Err(ParseErrorKind::UnexpectedCharacter(value)),
         }
     }
 }
 
-impl<D> PocketDimension<D>
-where
-    D: Eq + Hash,
-{
-    pub fn get(&self, position: Position<D>) -> State {
-        if self.active_cubes.contains(&position) {
-            State::Active
-        } else {
-            State::Inactive
+impl Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            State::Active => f.write_str("#"),
+            State::Inactive => f.write_str("."),
         }
     }
+}
 
-    pub fn num_active(&self) -> usize {
-        self.active_cubes.len()
-    }
+/// The Conway cubes rule: an inactive cube becomes active if exactly 3 of its
+/// neighbors are active and an active cube stays active if 2 or 3 of its
+/// neighbors are active.
+const CONWAY_CUBES: LifeLike = LifeLike::new(&[3], &[2, 3]);
 
-    pub fn neighbors(&self, position: Position<D>) -> Neighbors<'_, D> {
-        Neighbors {
-            active_cubes: &self.active_cubes,
-            position,
-            current: 0,
+/// How the active cubes of a pocket dimension are stored and evolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Evolution {
+    /// Every active cube is stored and evolved.
+    Full,
+    /// Only the active cubes with non-negative coordinates sorted in
+    /// descending order in the dimensions beyond x and y are stored and
+    /// evolved, every other active cube being a mirror image of one of them.
+    ///
+    /// This requires the pocket dimension to be symmetric in those
+    /// dimensions, which is the case for every pocket dimension booted from a
+    /// slice at coordinate 0 in each of them.
+    Symmetric,
+}
+
+impl Evolution {
+    fn lattice<const N: usize>(self) -> SymmetricLattice<N> {
+        match self {
+            Evolution::Full => SymmetricLattice::new(N),
+            Evolution::Symmetric => SymmetricLattice::new(2.min(N)),
         }
     }
 }
 
-impl PocketDimension<D3> {
-    pub fn region(&self) -> Region<D3> {
-        let (mut min_pos, mut max_pos) = if self.active_cubes.is_empty() {
-            ([0; 3], [0; 3])
-        } else {
-            ([Coord::max_value(); 3], [Coord::min_value(); 3])
+/// How the numbers of active neighbors are determined in a cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Visits every active cube and every neighbor of an active cube and
+    /// looks up the states of its neighbors.
+    ProbeNeighbors,
+    /// Visits only the active cubes and accumulates the numbers of active
+    /// neighbors in a map.
+    CountNeighbors,
+}
+
+/// The active cubes of an `N`-dimensional pocket dimension.
+#[derive(Debug, Clone)]
+pub struct PocketDimension<const N: usize> {
+    evolution: Evolution,
+    lattice: SymmetricLattice<N>,
+    active_cubes: LiveCells<[Coord; N]>,
+    next_gen: LiveCells<[Coord; N]>,
+    neighbor_counts: NeighborCounts<[Coord; N]>,
+}
+
+impl<const N: usize> PartialEq for PocketDimension<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.evolution == other.evolution && self.active_cubes == other.active_cubes
+    }
+}
+
+impl<const N: usize> Eq for PocketDimension<N> {}
+
+/// Lifts a pocket dimension into a higher dimension, placing its active cubes
+/// at coordinate 0 in each additional dimension. The lifted pocket dimension
+/// uses [`Evolution::Full`].
+///
+/// Converting into a lower dimension fails to compile.
+impl<const M: usize, const N: usize> From<&PocketDimension<M>> for PocketDimension<N> {
+    fn from(value: &PocketDimension<M>) -> Self {
+        const {
+            assert!(
+                M <= N,
+                "cannot lift a pocket dimension into a lower dimension"
+            )
         };
-        for pos in self.active_cubes.iter() {
-            for d in 0..pos.len() {
-                if pos[d] < min_pos[d] {
-                    min_pos[d] = pos[d];
-                }
-                if pos[d] > max_pos[d] {
-                    max_pos[d] = pos[d];
-                }
-            }
+        let mut active_cubes = LiveCells::default();
+        value.for_each_active_cube(|cube| {
+            let mut lifted = [0; N];
+            lifted[..M].copy_from_slice(&cube);
+            active_cubes.insert(lifted);
+        });
+        Self::new(Evolution::Full, active_cubes)
+    }
+}
+
+impl<const N: usize> PocketDimension<N> {
+    fn new(evolution: Evolution, active_cubes: LiveCells<[Coord; N]>) -> Self {
+        let lattice = evolution.lattice();
+        let active_cubes = active_cubes
+            .into_iter()
+            .map(|cube| lattice.representative(cube))
+            .collect();
+        Self {
+            evolution,
+            lattice,
+            active_cubes,
+            next_gen: LiveCells::default(),
+            neighbor_counts: NeighborCounts::default(),
         }
-        for d in 0..3 {
-            min_pos[d] -= 1;
-            max_pos[d] += 1;
+    }
+
+    pub fn evolution(&self) -> Evolution {
+        self.evolution
+    }
+
+    /// Switches to the given evolution.
+    ///
+    /// Switching to [`Evolution::Symmetric`] is only correct if the active
+    /// cubes are symmetric in the dimensions beyond x and y.
+    pub fn with_evolution(self, evolution: Evolution) -> Self {
+        let mut active_cubes = LiveCells::default();
+        self.for_each_active_cube(|cube| {
+            active_cubes.insert(cube);
+        });
+        Self::new(evolution, active_cubes)
+    }
+
+    fn for_each_active_cube(&self, mut f: impl FnMut([Coord; N])) {
+        for cube in &self.active_cubes {
+            self.lattice.for_each_image(*cube, &mut f);
         }
-        Region {
-            max_pos,
-            min_pos,
-            current: min_pos,
+    }
+
+    pub fn get(&self, position: Position<N>) -> State {
+        if self
+            .active_cubes
+            .contains(&self.lattice.representative(position.0))
+        {
+            State::Active
+        } else {
+            State::Inactive
         }
     }
 
-    pub fn evolve(&mut self) {
-        self.next_gen.clear();
-        for curr_pos in self.region() {
-            match self
-                .neighbors(curr_pos)
-                .filter(|s| *s == State::Active)
-                .count()
-            {
-                3 => {
-                    self.next_gen.insert(curr_pos);
-                }
-                2 => {
-                    if self.active_cubes.contains(&curr_pos) {
-                        self.next_gen.insert(curr_pos);
-                    }
-                }
-                _ => {}
+    pub fn num_active(&self) -> usize {
+        self.lattice.population(&self.active_cubes)
+    }
+
+    pub fn evolve(&mut self, strategy: Strategy) {
+        match strategy {
+            Strategy::ProbeNeighbors => {
+                self.lattice
+                    .next_generation(&CONWAY_CUBES, &self.active_cubes, &mut self.next_gen)
             }
+            Strategy::CountNeighbors => self.lattice.next_generation_by_counting(
+                &CONWAY_CUBES,
+                &self.active_cubes,
+                &mut self.next_gen,
+                &mut self.neighbor_counts,
+            ),
         }
         mem::swap(&mut self.active_cubes, &mut self.next_gen);
     }
 }
 
-impl PocketDimension<D4> {
-    pub fn region(&self) -> Region<D4> {
-        let (mut min_pos, mut max_pos) = if self.active_cubes.is_empty() {
-            ([0; 4], [0; 4])
-        } else {
-            ([Coord::max_value(); 4], [Coord::min_value(); 4])
-        };
-        for pos in self.active_cubes.iter() {
-            for d in 0..pos.len() {
-                if pos[d] < min_pos[d] {
-                    min_pos[d] = pos[d];
-                }
-                if pos[d] > max_pos[d] {
-                    max_pos[d] = pos[d];
-                }
+impl<const N: usize> PocketDimension<N> {
+    /// Returns the smallest and the largest coordinates of the active cubes
+    /// in each dimension or `None` if no cube is active.
+    pub fn bounds(&self) -> Option<([Coord; N], [Coord; N])> {
+        let mut bounds: Option<([Coord; N], [Coord; N])> = None;
+        self.for_each_active_cube(|cube| {
+            let (min, max) = bounds.get_or_insert((cube, cube));
+            for dim in 0..N {
+                min[dim] = min[dim].min(cube[dim]);
+                max[dim] = max[dim].max(cube[dim]);
             }
-        }
-        for d in 0..4 {
-            min_pos[d] -= 1;
-            max_pos[d] += 1;
-        }
-        Region {
-            max_pos,
-            min_pos,
-            current: min_pos,
-        }
+        });
+        bounds
     }
 
-    pub fn evolve(&mut self) {
-        self.next_gen.clear();
-        for curr_pos in self.region() {
-            match self
-                .neighbors(curr_pos)
-                .filter(|s| *s == State::Active)
-                .count()
-            {
-                3 => {
-                    self.next_gen.insert(curr_pos);
-                }
-                2 => {
-                    if self.active_cubes.contains(&curr_pos) {
-                        self.next_gen.insert(curr_pos);
-                    }
+    /// Returns the x-y slices through the bounds of the active cubes, in the
+    /// order of the puzzle description with z varying fastest.
+    pub fn slices(&self) -> Vec<Slice> {
+        const { assert!(N >= 2, "a pocket dimension must have an x and a y axis") };
+        let (min, max) = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return Vec::new(),
+        };
+
+        let mut coords = vec![Vec::new()];
+        for dim in 2..N {
+            coords = (min[dim]..=max[dim])
+                .flat_map(|coord| {
+                    coords.iter().map(move |prefix| {
+                        let mut coords = prefix.clone();
+                        coords.push(coord);
+                        coords
+                    })
+                })
+                .collect();
+        }
+        coords
+            .into_iter()
+            .map(|coords| {
+                let cubes = Grid::from_rows((min[1]..=max[1]).map(|y| {
+                    let coords = &coords;
+                    (min[0]..=max[0]).map(move |x| {
+                        let mut position = [0; N];
+                        position[0] = x;
+                        position[1] = y;
+                        position[2..].copy_from_slice(coords);
+                        self.get(Position::from(position))
+                    })
+                }));
+                Slice {
+                    coords,
+                    origin: [min[0], min[1]],
+                    cubes,
                 }
-                _ => {}
+            })
+            .collect()
+    }
+}
+
+/// Renders the slices through the active cubes like the puzzle description,
+/// separated by blank lines.
+impl<const N: usize> Display for PocketDimension<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, slice) in self.slices().iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
             }
+            write!(f, "{}", slice)?;
         }
-        mem::swap(&mut self.active_cubes, &mut self.next_gen);
+        Ok(())
     }
 }
 
-#[derive(Debug)]
-pub struct Region<D> {
-    max_pos: D,
-    min_pos: D,
-    current: D,
+/// Names of the dimensions beyond x and y.
+const DIMENSION_NAMES: [&str; 4] = ["z", "w", "v", "u"];
+
+/// A two-dimensional slice of a pocket dimension at fixed coordinates in the
+/// dimensions beyond x and y.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Slice {
+    coords: Vec<Coord>,
+    origin: [Coord; 2],
+    cubes: Grid<State>,
 }
 
-impl Iterator for Region<D3> {
-    type Item = Position<D3>;
+impl Slice {
+    /// Returns the coordinates of the slice in the dimensions beyond x and y.
+    pub fn coords(&self) -> &[Coord] {
+        &self.coords
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let last_i = self.current.len() - 1;
-        if self.current[last_i] > self.max_pos[last_i] {
-            return None;
-        }
-        let position = Position::from(self.current);
-        for i in 0..self.current.len() {
-            self.current[i] += 1;
-            if self.current[i] <= self.max_pos[i] || i == last_i {
-                break;
-            }
-            self.current[i] = self.min_pos[i];
-        }
-        Some(position)
+    /// Returns the x and y coordinates of the top left cube.
+    pub fn origin(&self) -> [Coord; 2] {
+        self.origin
     }
-}
 
-impl Iterator for Region<D4> {
-    type Item = Position<D4>;
+    /// Returns the states of the cubes with a row for each y and a column for
+    /// each x coordinate.
+    pub fn cubes(&self) -> &Grid<State> {
+        &self.cubes
+    }
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let last_i = self.current.len() - 1;
-        if self.current[last_i] > self.max_pos[last_i] {
-            return None;
-        }
-        let position = Position::from(self.current);
-        for i in 0..self.current.len() {
-            self.current[i] += 1;
-            if self.current[i] <= self.max_pos[i] || i == last_i {
-                break;
+/// Renders the slice as in the puzzle description, headed by its
+/// coordinates, e.g. `z=-1, w=0`.
+impl Display for Slice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, coord) in self.coords.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            match DIMENSION_NAMES.get(index) {
+                Some(name) => write!(f, "{}={}", name, coord)?,
+                None => write!(f, "d{}={}", index + 3, coord)?,
             }
-            self.current[i] = self.min_pos[i];
         }
-        Some(position)
+        if !self.coords.is_empty() {
+            writeln!(f)?;
+        }
+        write!(f, "{}", self.cubes)
     }
 }
 
-#[derive(Debug)]
-pub struct Neighbors<'a, D>
-where
-    D: Eq + Hash,
-{
-    active_cubes: &'a HashSet<Position<D>, FxBuildHasher>,
-    position: Position<D>,
-    current: usize,
+/// The slices of a pocket dimension after a number of cycles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    cycle: usize,
+    slices: Vec<Slice>,
 }
 
-const NEIGHBORS_3D: [D3; 26] = [
-    [-1, -1, -1],
-    [0, -1, -1],
-    [1, -1, -1],
-    [-1, 0, -1],
-    [0, 0, -1],
-    [1, 0, -1],
-    [-1, 1, -1],
-    [0, 1, -1],
-    [1, 1, -1],
-    [-1, -1, 0],
-    [0, -1, 0],
-    [1, -1, 0],
-    [-1, 0, 0],
-    [1, 0, 0],
-    [-1, 1, 0],
-    [0, 1, 0],
-    [1, 1, 0],
-    [-1, -1, 1],
-    [0, -1, 1],
-    [1, -1, 1],
-    [-1, 0, 1],
-    [0, 0, 1],
-    [1, 0, 1],
-    [-1, 1, 1],
-    [0, 1, 1],
-    [1, 1, 1],
-];
-
-impl<'a> Iterator for Neighbors<'a, D3> {
-    type Item = State;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current >= NEIGHBORS_3D.len() {
-            return None;
+impl Frame {
+    pub fn new<const N: usize>(cycle: usize, pocket_dimension: &PocketDimension<N>) -> Self {
+        Self {
+            cycle,
+            slices: pocket_dimension.slices(),
         }
-        let neighbor = Position3D::from([
-            self.position[0] + NEIGHBORS_3D[self.current][0],
-            self.position[1] + NEIGHBORS_3D[self.current][1],
-            self.position[2] + NEIGHBORS_3D[self.current][2],
-        ]);
-        self.current += 1;
-        let state = if self.active_cubes.contains(&neighbor) {
-            State::Active
-        } else {
-            State::Inactive
-        };
-        Some(state)
     }
-}
 
-const NEIGHBORS_4D: [D4; 80] = [
-    [-1, -1, -1, -1],
-    [0, -1, -1, -1],
-    [1, -1, -1, -1],
-    [-1, 0, -1, -1],
-    [0, 0, -1, -1],
-    [1, 0, -1, -1],
-    [-1, 1, -1, -1],
-    [0, 1, -1, -1],
-    [1, 1, -1, -1],
-    [-1, -1, 0, -1],
-    [0, -1, 0, -1],
-    [1, -1, 0, -1],
-    [-1, 0, 0, -1],
-    [0, 0, 0, -1],
-    [1, 0, 0, -1],
-    [-1, 1, 0, -1],
-    [0, 1, 0, -1],
-    [1, 1, 0, -1],
-    [-1, -1, 1, -1],
-    [0, -1, 1, -1],
-    [1, -1, 1, -1],
-    [-1, 0, 1, -1],
-    [0, 0, 1, -1],
-    [1, 0, 1, -1],
-    [-1, 1, 1, -1],
-    [0, 1, 1, -1],
-    [1, 1, 1, -1],
-    [-1, -1, -1, 0],
-    [0, -1, -1, 0],
-    [1, -1, -1, 0],
-    [-1, 0, -1, 0],
-    [0, 0, -1, 0],
-    [1, 0, -1, 0],
-    [-1, 1, -1, 0],
-    [0, 1, -1, 0],
-    [1, 1, -1, 0],
-    [-1, -1, 0, 0],
-    [0, -1, 0, 0],
-    [1, -1, 0, 0],
-    [-1, 0, 0, 0],
-    [1, 0, 0, 0],
-    [-1, 1, 0, 0],
-    [0, 1, 0, 0],
-    [1, 1, 0, 0],
-    [-1, -1, 1, 0],
-    [0, -1, 1, 0],
-    [1, -1, 1, 0],
-    [-1, 0, 1, 0],
-    [0, 0, 1, 0],
-    [1, 0, 1, 0],
-    [-1, 1, 1, 0],
-    [0, 1, 1, 0],
-    [1, 1, 1, 0],
-    [-1, -1, -1, 1],
-    [0, -1, -1, 1],
-    [1, -1, -1, 1],
-    [-1, 0, -1, 1],
-    [0, 0, -1, 1],
-    [1, 0, -1, 1],
-    [-1, 1, -1, 1],
-    [0, 1, -1, 1],
-    [1, 1, -1, 1],
-    [-1, -1, 0, 1],
-    [0, -1, 0, 1],
-    [1, -1, 0, 1],
-    [-1, 0, 0, 1],
-    [0, 0, 0, 1],
-    [1, 0, 0, 1],
-    [-1, 1, 0, 1],
-    [0, 1, 0, 1],
-    [1, 1, 0, 1],
-    [-1, -1, 1, 1],
-    [0, -1, 1, 1],
-    [1, -1, 1, 1],
-    [-1, 0, 1, 1],
-    [0, 0, 1, 1],
-    [1, 0, 1, 1],
-    [-1, 1, 1, 1],
-    [0, 1, 1, 1],
-    [1, 1, 1, 1],
-];
-
-impl<'a> Iterator for Neighbors<'a, D4> {
-    type Item = State;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current >= NEIGHBORS_4D.len() {
-            return None;
-        }
-        let neighbor = Position4D::from([
-            self.position[0] + NEIGHBORS_4D[self.current][0],
-            self.position[1] + NEIGHBORS_4D[self.current][1],
-            self.position[2] + NEIGHBORS_4D[self.current][2],
-            self.position[3] + NEIGHBORS_4D[self.current][3],
-        ]);
-        self.current += 1;
-        let state = if self.active_cubes.contains(&neighbor) {
-            State::Active
-        } else {
-            State::Inactive
-        };
-        Some(state)
+    pub fn cycle(&self) -> usize {
+        self.cycle
     }
-}
 
-#[aoc_generator(day17)]
-pub fn parse_conway_cubes(input: &str) -> PocketDimension<D3> {
-    let z = 0;
-    let mut active_cubes = HashSet::with_hasher(FxBuildHasher::default());
-    let mut y = 0;
-    let mut x = 0;
-    for c in input.chars() {
-        match c {
-            '.' => x += 1,
-            '#' => {
-                active_cubes.insert(Position3D::from([x, y, z]));
-                x += 1;
-            }
-            '\n' => {
-                x = 0;
-                y += 1;
+    pub fn slices(&self) -> &[Slice] {
+        &self.slices
+    }
+
+    /// Writes the frame as a plain PGM image with the slices side by side,
+    /// active cubes in white, inactive cubes in black and a gray column
+    /// between adjacent slices.
+    pub fn write_pgm(&self, out: &mut impl Write) -> io::Result<()> {
+        let (slice_width, height) = self
+            .slices
+            .first()
+            .map_or((0, 0), |slice| (slice.cubes.width(), slice.cubes.height()));
+        let width = (self.slices.len() * (slice_width + 1)).saturating_sub(1);
+        writeln!(out, "P2")?;
+        writeln!(out, "{} {}", width, height)?;
+        writeln!(out, "255")?;
+        for row in 0..height {
+            let mut pixels = Vec::with_capacity(width);
+            for (index, slice) in self.slices.iter().enumerate() {
+                if index > 0 {
+                    pixels.push("128");
+                }
+                pixels.extend(slice.cubes.row(row).iter().map(|state| match state {
+                    State::Active => "255",
+                    State::Inactive => "0",
+                }));
             }
-            _ => panic!("unexpected character {:?} at line {} column {} ", c, y, x),
+            writeln!(out, "{}", pixels.join(" "))?;
         }
+        Ok(())
     }
-    PocketDimension {
-        active_cubes,
-        next_gen: HashSet::default(),
+}
+
+/// Renders the frame like the puzzle description, headed by the number of
+/// cycles.
+impl Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.cycle {
+            0 => writeln!(f, "Before any cycles:")?,
+            1 => writeln!(f, "After 1 cycle:")?,
+            cycles => writeln!(f, "After {} cycles:", cycles)?,
+        }
+        for slice in &self.slices {
+            writeln!(f)?;
+            write!(f, "{}", slice)?;
+        }
+        Ok(())
     }
 }
 
-#[aoc(day17, part1)]
-pub fn num_active_cubes_after_6_cycle_boot(pocket_dimension: &PocketDimension<D3>) -> usize {
-    let mut pocket_dimension = pocket_dimension.clone();
+/// Lifts the initial slice into `N` dimensions and records a frame before
+/// the first and after every cycle.
+pub fn record_boot<const N: usize>(
+    initial_slice: &PocketDimension<3>,
+    cycles: usize,
+    evolution: Evolution,
+    strategy: Strategy,
+) -> Vec<Frame> {
+    let mut pocket_dimension = PocketDimension::<N>::from(initial_slice).with_evolution(evolution);
+    let mut frames = vec![Frame::new(0, &pocket_dimension)];
+    for cycle in 1..=cycles {
+        pocket_dimension.evolve(strategy);
+        frames.push(Frame::new(cycle, &pocket_dimension));
+    }
+    frames
+}
 
-    (0..6).for_each(|_| pocket_dimension.evolve());
+/// Parses the initial slice of the pocket dimension at `z = 0`.
+pub fn try_parse_conway_cubes(input: &str) -> Result<PocketDimension<3>, ParseError> {
+    let z = 0;
+    let slice = Grid::<State>::from_str(input)?;
+    let active_cubes = slice
+        .positions()
+        .filter(|position| slice[*position] == State::Active)
+        .map(|position| [position.col as Coord, position.row as Coord, z])
+        .collect();
+    Ok(PocketDimension::new(Evolution::Full, active_cubes))
+}
 
-    pocket_dimension.num_active()
+#[aoc_generator(day17)]
+pub fn parse_conway_cubes(input: &str) -> PocketDimension<3> {
+    try_parse_conway_cubes(input).unwrap_or_else(|err| panic!("{}", err))
 }
 
-#[aoc(day17, part2)]
-pub fn num_active_hypercubes_after_6_cycle_boot(pocket_dimension: &PocketDimension<D3>) -> usize {
-    let mut pocket_dimension = PocketDimension::<D4>::from(pocket_dimension);
+/// Lifts the initial slice into `N` dimensions and returns the number of
+/// cubes left active after the given number of cycles.
+pub fn num_active_after_boot<const N: usize>(
+    initial_slice: &PocketDimension<3>,
+    cycles: usize,
+    evolution: Evolution,
+    strategy: Strategy,
+) -> usize {
+    let mut pocket_dimension = PocketDimension::<N>::from(initial_slice).with_evolution(evolution);
 
-    (0..6).for_each(|_| pocket_dimension.evolve());
+    (0..cycles).for_each(|_| pocket_dimension.evolve(strategy));
 
     pocket_dimension.num_active()
 }
 
+#[aoc(day17, part1)]
+pub fn num_active_cubes_after_6_cycle_boot(pocket_dimension: &PocketDimension<3>) -> usize {
+    num_active_after_boot::<3>(
+        pocket_dimension,
+        6,
+        Evolution::Symmetric,
+        Strategy::CountNeighbors,
+    )
+}
+
+#[aoc(day17, part2)]
+pub fn num_active_hypercubes_after_6_cycle_boot(pocket_dimension: &PocketDimension<3>) -> usize {
+    num_active_after_boot::<4>(
+        pocket_dimension,
+        6,
+        Evolution::Symmetric,
+        Strategy::CountNeighbors,
+    )
+}
+
 #[cfg(test)]
 mod tests;
@@ -1,4 +1,5 @@
 use super::*;
+use crate::answers::recorded_answer;
 
 const INPUT: &str = include_str!("../../input/2020/day17.txt");
 
@@ -23,6 +24,14 @@ fn parse_conway_cubes_in_example() {
     assert_eq!(cubes.get(Position3D::from([2, 2, 0])), State::Active);
 }
 
+#[test]
+fn try_parse_conway_cubes_with_unexpected_character() {
+    let error = try_parse_conway_cubes(".#.\n..#\n#O#\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (3, 2));
+    assert_eq!(error.kind(), &ParseErrorKind::UnexpectedCharacter('O'));
+}
+
 #[test]
 fn num_active_cubes_after_6_cycle_boot_in_example() {
     let pocket_dimension = parse_conway_cubes(EXAMPLE);
@@ -38,7 +47,7 @@ fn num_active_cubes_after_6_cycle_boot_in_puzzle_input() {
 
     let num_active = num_active_cubes_after_6_cycle_boot(&pocket_dimension);
 
-    assert_eq!(num_active, 242);
+    assert_eq!(num_active.to_string(), recorded_answer(17, 1));
 }
 
 #[test]
@@ -56,5 +65,193 @@ fn num_active_hypercubes_after_6_cycle_boot_in_puzzle_input() {
 
     let num_active = num_active_hypercubes_after_6_cycle_boot(&pocket_dimension);
 
-    assert_eq!(num_active, 2292);
+    assert_eq!(num_active.to_string(), recorded_answer(17, 2));
+}
+
+#[test]
+fn lift_pocket_dimension_into_higher_dimensions() {
+    let pocket_dimension = parse_conway_cubes(EXAMPLE);
+
+    let lifted = PocketDimension::<5>::from(&pocket_dimension);
+
+    assert_eq!(lifted.num_active(), 5);
+    assert_eq!(lifted.get(Position::from([1, 0, 0, 0, 0])), State::Active);
+    assert_eq!(lifted.get(Position::from([1, 0, 0, 0, 1])), State::Inactive);
+}
+
+#[test]
+fn symmetric_evolution_equals_full_evolution_in_example() {
+    let mut full = PocketDimension::<4>::from(&parse_conway_cubes(EXAMPLE));
+    let mut symmetric = full.clone().with_evolution(Evolution::Symmetric);
+
+    for _ in 0..3 {
+        full.evolve(Strategy::ProbeNeighbors);
+        symmetric.evolve(Strategy::ProbeNeighbors);
+
+        assert_eq!(symmetric.num_active(), full.num_active());
+        assert_eq!(symmetric.clone().with_evolution(Evolution::Full), full);
+    }
+}
+
+#[test]
+fn counting_neighbors_equals_probing_neighbors_in_example() {
+    let initial = PocketDimension::<4>::from(&parse_conway_cubes(EXAMPLE));
+
+    for evolution in [Evolution::Full, Evolution::Symmetric] {
+        let mut probed = initial.clone().with_evolution(evolution);
+        let mut counted = probed.clone();
+
+        for _ in 0..3 {
+            probed.evolve(Strategy::ProbeNeighbors);
+            counted.evolve(Strategy::CountNeighbors);
+
+            assert_eq!(counted, probed, "{:?}", evolution);
+        }
+    }
+}
+
+#[test]
+fn get_active_cube_mirrored_in_symmetric_evolution() {
+    let mut pocket_dimension = PocketDimension::<4>::from(&parse_conway_cubes(EXAMPLE))
+        .with_evolution(Evolution::Symmetric);
+
+    pocket_dimension.evolve(Strategy::CountNeighbors);
+
+    assert_eq!(
+        pocket_dimension.get(Position::from([0, 1, -1, 0])),
+        State::Active
+    );
+    assert_eq!(
+        pocket_dimension.get(Position::from([0, 1, 0, -1])),
+        State::Active
+    );
+    assert_eq!(
+        pocket_dimension.get(Position::from([0, 1, 1, 1])),
+        State::Active
+    );
+    assert_eq!(
+        pocket_dimension.get(Position::from([1, 1, 1, 1])),
+        State::Inactive
+    );
+}
+
+#[test]
+fn num_active_after_6_cycle_boot_in_5_dimensions_in_example() {
+    let pocket_dimension = parse_conway_cubes(EXAMPLE);
+
+    let num_active = num_active_after_boot::<5>(
+        &pocket_dimension,
+        6,
+        Evolution::Symmetric,
+        Strategy::CountNeighbors,
+    );
+
+    assert_eq!(num_active, 5760);
+}
+
+#[test]
+fn num_active_after_6_cycle_boot_in_6_dimensions_in_example() {
+    let pocket_dimension = parse_conway_cubes(EXAMPLE);
+
+    let num_active = num_active_after_boot::<6>(
+        &pocket_dimension,
+        6,
+        Evolution::Symmetric,
+        Strategy::CountNeighbors,
+    );
+
+    assert_eq!(num_active, 35936);
+}
+
+#[test]
+fn display_pocket_dimension_after_1_cycle_in_example() {
+    let mut pocket_dimension = parse_conway_cubes(EXAMPLE);
+
+    pocket_dimension.evolve(Strategy::CountNeighbors);
+
+    assert_eq!(
+        pocket_dimension.to_string(),
+        "\
+z=-1
+#..
+..#
+.#.
+
+z=0
+#.#
+.##
+.#.
+
+z=1
+#..
+..#
+.#.
+"
+    );
+}
+
+#[test]
+fn slices_of_symmetric_hypercubes_after_1_cycle_in_example() {
+    let frames = record_boot::<4>(
+        &parse_conway_cubes(EXAMPLE),
+        1,
+        Evolution::Symmetric,
+        Strategy::CountNeighbors,
+    );
+
+    let slices = frames[1].slices();
+
+    assert_eq!(slices.len(), 9);
+    assert_eq!(slices[0].coords(), &[-1, -1]);
+    assert_eq!(slices[0].origin(), [0, 1]);
+    assert_eq!(slices[0].to_string(), "z=-1, w=-1\n#..\n..#\n.#.\n");
+    assert_eq!(slices[4].to_string(), "z=0, w=0\n#.#\n.##\n.#.\n");
+    assert_eq!(slices[8].coords(), &[1, 1]);
+}
+
+#[test]
+fn record_boot_for_2_cycles_in_example() {
+    let frames = record_boot::<3>(
+        &parse_conway_cubes(EXAMPLE),
+        2,
+        Evolution::Full,
+        Strategy::ProbeNeighbors,
+    );
+
+    assert_eq!(frames.len(), 3);
+    assert_eq!(
+        frames[0].to_string(),
+        "Before any cycles:\n\nz=0\n.#.\n..#\n###\n"
+    );
+    assert!(frames[1]
+        .to_string()
+        .starts_with("After 1 cycle:\n\nz=-1\n#..\n"));
+    assert!(frames[2]
+        .to_string()
+        .starts_with("After 2 cycles:\n\nz=-2\n.....\n"));
+}
+
+#[test]
+fn write_frame_as_pgm_image() {
+    let frames = record_boot::<3>(
+        &parse_conway_cubes(EXAMPLE),
+        1,
+        Evolution::Full,
+        Strategy::CountNeighbors,
+    );
+    let mut image = Vec::new();
+
+    frames[1].write_pgm(&mut image).unwrap();
+
+    assert_eq!(
+        String::from_utf8(image).unwrap(),
+        "\
+P2
+11 3
+255
+255 0 0 128 255 0 255 128 255 0 0
+0 0 255 128 0 255 255 128 0 0 255
+0 255 0 128 0 255 0 128 0 255 0
+"
+    );
 }
@@ -92,6 +92,7 @@
 //!
 //! [Advent of Code 2020 - Day 18](https://adventofcode.com/2020/day/18)
 
+use crate::parse::{end_of, ParseError, ParseErrorKind};
 use std::fmt::Debug;
 use std::mem;
 use std::str::FromStr;
@@ -508,9 +509,59 @@ where
     }
 }
 
+/// Checks that the line is a well-formed expression: operands and operators
+/// alternate and parentheses are balanced.
+fn check_math_expression<'a>(input: &str, line: &'a str) -> Result<&'a str, ParseError> {
+    let unexpected = |index: usize, c: char| {
+        ParseError::at(
+            input,
+            &line[index..index + c.len_utf8()],
+            ParseErrorKind::UnexpectedCharacter(c),
+        )
+    };
+    let mut expect_operand = true;
+    let mut open_parens = 0_usize;
+    let mut chars = line.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            _ if c.is_whitespace() => {}
+            '0'..='9' if expect_operand => {
+                while chars.next_if(|(_, c)| c.is_ascii_digit()).is_some() {}
+                expect_operand = false;
+            }
+            '(' if expect_operand => open_parens += 1,
+            ')' if !expect_operand && open_parens > 0 => open_parens -= 1,
+            '+' | '*' if !expect_operand => expect_operand = true,
+            _ => return Err(unexpected(index, c)),
+        }
+    }
+    if expect_operand {
+        return Err(ParseError::at(
+            input,
+            end_of(line),
+            ParseErrorKind::Missing("operand"),
+        ));
+    }
+    if open_parens > 0 {
+        return Err(ParseError::at(
+            input,
+            end_of(line),
+            ParseErrorKind::Missing("')'"),
+        ));
+    }
+    Ok(line)
+}
+
+pub fn try_parse_math_homework(input: &str) -> Result<Vec<String>, ParseError> {
+    input
+        .lines()
+        .map(|line| check_math_expression(input, line).map(String::from))
+        .collect()
+}
+
 #[aoc_generator(day18)]
 pub fn parse_math_homework(input: &str) -> Vec<String> {
-    input.lines().map(|s| String::from(s)).collect()
+    try_parse_math_homework(input).unwrap_or_else(|err| panic!("{}", err))
 }
 
 #[aoc(day18, part1)]
@@ -1,4 +1,5 @@
 use super::*;
+use crate::answers::recorded_answer;
 
 const INPUT: &str = include_str!("../../input/2020/day18.txt");
 
@@ -9,6 +10,54 @@ fn parse_math_homework_in_puzzle_input() {
     assert_eq!(homework.len(), 373);
 }
 
+#[test]
+fn try_parse_math_homework_with_invalid_operator() {
+    let error = try_parse_math_homework("1 + 2 * 3\n2 * 3 - (4 * 5)\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (2, 7));
+    assert_eq!(error.kind(), &ParseErrorKind::UnexpectedCharacter('-'));
+}
+
+#[test]
+fn try_parse_math_homework_with_missing_right_operand() {
+    let error = try_parse_math_homework("1 + 2\n1 +\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (2, 4));
+    assert_eq!(error.kind(), &ParseErrorKind::Missing("operand"));
+}
+
+#[test]
+fn try_parse_math_homework_with_unclosed_parenthesis() {
+    let error = try_parse_math_homework("1 + (2\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (1, 7));
+    assert_eq!(error.kind(), &ParseErrorKind::Missing("')'"));
+}
+
+#[test]
+fn try_parse_math_homework_with_unopened_parenthesis() {
+    let error = try_parse_math_homework("(1 + 2)) * 3\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (1, 8));
+    assert_eq!(error.kind(), &ParseErrorKind::UnexpectedCharacter(')'));
+}
+
+#[test]
+fn try_parse_math_homework_with_two_operands_in_a_row() {
+    let error = try_parse_math_homework("2 * 3 (4 + 5)\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (1, 7));
+    assert_eq!(error.kind(), &ParseErrorKind::UnexpectedCharacter('('));
+}
+
+#[test]
+fn try_parse_math_homework_with_empty_line() {
+    let error = try_parse_math_homework("1 + 2\n\n3 * 4\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (2, 1));
+    assert_eq!(error.kind(), &ParseErrorKind::Missing("operand"));
+}
+
 #[test]
 fn parse_tokens_example1() {
     let tokens = ArithmeticLexer::new("1 + 2 * 3 + 4 * 5 + 6".chars())
@@ -230,7 +279,7 @@ fn sum_of_math_results_with_equal_precedence_in_puzzle_input() {
 
     let sum = sum_of_math_results_with_equal_precedence(&homework);
 
-    assert_eq!(sum, 5374004645253);
+    assert_eq!(sum.to_string(), recorded_answer(18, 1));
 }
 
 #[test]
@@ -287,5 +336,5 @@ fn sum_of_math_results_with_custom_precedence_in_puzzle_input() {
 
     let sum = sum_of_math_results_with_custom_precedence(&homework);
 
-    assert_eq!(sum, 88782789402798);
+    assert_eq!(sum.to_string(), recorded_answer(18, 2));
 }
@@ -190,10 +190,9 @@
 //!
 //! [Advent of Code 2020 - Day 19](https://adventofcode.com/2020/day/19)
 
+use crate::parse::{end_of, parse_number, ParseError, ParseErrorKind};
 use hashbrown::HashMap;
 use std::iter::FromIterator;
-use std::num::ParseIntError;
-use std::str::FromStr;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Expr {
@@ -204,19 +203,9 @@ pub enum Expr {
     /// Matches the left sequence of sub-patterns or the right sequence of sub-
     /// pattern.
     Or(Vec<usize>, Vec<usize>),
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct State {
-    id: usize,
-    op: Logic,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Logic {
-    And,
-    OrLeft { start: usize, prev_result: bool },
-    OrRight { prev_result: bool },
+    /// Placeholder for an id that has no rule defined. Never matches, just
+    /// like ids beyond the last rule.
+    Undefined,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -229,7 +218,11 @@ impl Pattern {
         Self { rules }
     }
 
+    /// Replaces the rule with the given id, defining it if necessary.
     pub fn patch(&mut self, id: usize, rule: Expr) {
+        if id >= self.rules.len() {
+            self.rules.resize(id + 1, Expr::Undefined);
+        }
         self.rules[id] = rule;
     }
 
@@ -237,77 +230,52 @@ impl Pattern {
         if self.rules.is_empty() {
             return text.is_empty();
         }
-        use Logic::*;
         let chars = text.chars().collect::<Vec<_>>();
-        let mut result = true;
-        let mut current = 0;
-        let mut open = vec![State { id: 0, op: And }];
-        let mut prev_or_ops = vec![];
-        while let Some(State { id, op }) = open.pop() {
-            match &self.rules[id] {
-                Expr::Char(c) => {
-                    if chars[current] != *c {
-                        result = false;
-                        // go back to previous OR operation
-                        if let Some(prev_or_op) = prev_or_ops.pop() {
-                            open.drain(prev_or_op..);
-                        } else {
-                            return false;
-                        }
-                    }
-                    current += 1;
-                }
-                Expr::Seq(seq) => {
-                    open.extend(seq.iter().rev().map(|id| State { id: *id, op: And }));
+        self.end_positions(0, &chars, 0).contains(&chars.len())
+    }
+
+    /// Returns all positions in `chars` where a match of rule `id` starting at
+    /// position `start` may end.
+    ///
+    /// Collecting all possible end positions instead of stopping at the first
+    /// match makes it possible to match looping rules like `8: 42 | 42 8`,
+    /// which can consume a varying number of characters.
+    pub fn end_positions(&self, id: usize, chars: &[char], start: usize) -> Vec<usize> {
+        match self.rules.get(id).unwrap_or(&Expr::Undefined) {
+            Expr::Char(c) => {
+                if chars.get(start) == Some(c) {
+                    vec![start + 1]
+                } else {
+                    vec![]
                 }
-                Expr::Or(left_seq, right_seq) => match op {
-                    And => {
-                        open.push(State {
-                            id,
-                            op: OrLeft {
-                                start: current,
-                                prev_result: result,
-                            },
-                        });
-                        prev_or_ops.push(open.len());
-                        open.extend(left_seq.iter().rev().map(|id| State { id: *id, op: And }));
-                        result = true;
-                    }
-                    OrLeft { start, prev_result } => {
-                        if result {
-                            prev_or_ops.pop();
-                            result = prev_result;
-                        } else {
-                            open.push(State {
-                                id,
-                                op: OrRight { prev_result },
-                            });
-                            prev_or_ops.push(open.len());
-                            open.extend(
-                                right_seq.iter().rev().map(|id| State { id: *id, op: And }),
-                            );
-                            current = start;
-                            result = true;
-                        }
-                    }
-                    OrRight { prev_result } => {
-                        if result {
-                            prev_or_ops.pop();
-                            result = prev_result;
-                        } else {
-                            result = false;
-                            // go back to previous OR operation
-                            if let Some(prev_or_op) = prev_or_ops.pop() {
-                                open.drain(prev_or_op..);
-                            } else {
-                                return false;
-                            }
-                        }
-                    }
-                },
             }
+            Expr::Seq(seq) => self.seq_end_positions(seq, chars, start),
+            Expr::Undefined => vec![],
+            Expr::Or(left_seq, right_seq) => {
+                let mut ends = self.seq_end_positions(left_seq, chars, start);
+                ends.extend(self.seq_end_positions(right_seq, chars, start));
+                ends.sort_unstable();
+                ends.dedup();
+                ends
+            }
+        }
+    }
+
+    fn seq_end_positions(&self, seq: &[usize], chars: &[char], start: usize) -> Vec<usize> {
+        let mut positions = vec![start];
+        for id in seq {
+            let mut ends = positions
+                .iter()
+                .flat_map(|position| self.end_positions(*id, chars, *position))
+                .collect::<Vec<_>>();
+            if ends.is_empty() {
+                return ends;
+            }
+            ends.sort_unstable();
+            ends.dedup();
+            positions = ends;
         }
-        result && current == chars.len()
+        positions
     }
 }
 
@@ -317,65 +285,82 @@ pub struct MonsterMessages {
     pub messages: Vec<String>,
 }
 
-fn parse_pattern_id_seq(input: &str) -> Result<Vec<usize>, ParseIntError> {
-    input
-        .trim()
-        .split(' ')
-        .map(|s| usize::from_str(s))
+/// Parses a sequence of rule ids and records each of them together with its
+/// token in `references`.
+fn parse_pattern_id_seq<'a>(
+    input: &str,
+    s: &'a str,
+    references: &mut Vec<(usize, &'a str)>,
+) -> Result<Vec<usize>, ParseError> {
+    s.split_whitespace()
+        .map(|token| {
+            let id = parse_number(input, token)?;
+            references.push((id, token));
+            Ok(id)
+        })
         .collect()
 }
 
-#[aoc_generator(day19)]
-pub fn parse_monster_messages(input: &str) -> MonsterMessages {
+pub fn try_parse_monster_messages(input: &str) -> Result<MonsterMessages, ParseError> {
     let mut pattern_definitions = HashMap::new();
+    let mut references = Vec::new();
 
     let mut lines = input.trim().lines();
     while let Some(line) = lines.next() {
         if line.is_empty() {
             break;
         }
-        let id_sep = line.chars().position(|c| c == ':').unwrap();
-        let id_str = &line[0..id_sep];
-        let id = usize::from_str(id_str).unwrap();
+        let id_sep = line.find(':').ok_or_else(|| {
+            ParseError::at(
+                input,
+                end_of(line),
+                ParseErrorKind::Missing("':' after rule id"),
+            )
+        })?;
+        let id: usize = parse_number(input, &line[0..id_sep])?;
         let pattern_str = &line[id_sep + 1..];
-        if let Some(literal) = pattern_str
-            .chars()
-            .filter(|c| c.is_ascii_alphabetic())
-            .next()
-        {
+        if let Some(literal) = pattern_str.chars().find(|c| c.is_ascii_alphabetic()) {
             pattern_definitions.insert(id, Expr::Char(literal));
-        } else if let Some(or_op) = pattern_str.chars().position(|c| c == '|') {
-            let left_pattern_str = &pattern_str[0..or_op];
-            let right_pattern_str = &pattern_str[or_op + 1..];
-            let left_pattern_seq = parse_pattern_id_seq(left_pattern_str).unwrap();
-            let right_pattern_seq = parse_pattern_id_seq(right_pattern_str).unwrap();
+        } else if let Some(or_op) = pattern_str.find('|') {
+            let left_pattern_seq =
+                parse_pattern_id_seq(input, &pattern_str[0..or_op], &mut references)?;
+            let right_pattern_seq =
+                parse_pattern_id_seq(input, &pattern_str[or_op + 1..], &mut references)?;
             pattern_definitions.insert(id, Expr::Or(left_pattern_seq, right_pattern_seq));
         } else {
-            let pattern_seq = parse_pattern_id_seq(pattern_str).unwrap();
+            let pattern_seq = parse_pattern_id_seq(input, pattern_str, &mut references)?;
             pattern_definitions.insert(id, Expr::Seq(pattern_seq));
         }
     }
-    let mut pattern = Vec::from_iter(pattern_definitions.into_iter());
-    pattern.sort_by_key(|entry| entry.0);
-    debug_assert_eq!(
-        pattern
-            .iter()
-            .enumerate()
-            .filter(|(idx, (id, _))| *idx != *id)
-            .collect::<Vec<_>>(),
-        vec![]
+    if let Some((_, token)) = references
+        .iter()
+        .find(|(id, _)| !pattern_definitions.contains_key(id))
+    {
+        return Err(ParseError::at(
+            input,
+            token,
+            ParseErrorKind::Missing("definition of rule"),
+        ));
+    }
+    let num_rules = pattern_definitions
+        .keys()
+        .max()
+        .map_or(0, |max_id| max_id + 1);
+    let rules = Vec::from_iter(
+        (0..num_rules).map(|id| pattern_definitions.remove(&id).unwrap_or(Expr::Undefined)),
     );
-    let rules = Vec::from_iter(pattern.into_iter().map(|(_, pat)| pat));
 
     let messages = lines
         .filter(|ln| !ln.is_empty())
         .map(String::from)
         .collect();
 
-    MonsterMessages {
-        rules: rules,
-        messages,
-    }
+    Ok(MonsterMessages { rules, messages })
+}
+
+#[aoc_generator(day19)]
+pub fn parse_monster_messages(input: &str) -> MonsterMessages {
+    try_parse_monster_messages(input).unwrap_or_else(|err| panic!("{}", err))
 }
 
 #[aoc(day19, part1)]
@@ -389,7 +374,7 @@ pub fn count_matching_messages(monster_messages: &MonsterMessages) -> usize {
         .count()
 }
 
-//#[aoc(day19, part2)]
+#[aoc(day19, part2)]
 pub fn count_matching_messages_patched_rules(monster_messages: &MonsterMessages) -> usize {
     let mut pattern = Pattern::new(monster_messages.rules.clone());
     pattern.patch(8, Expr::Or(vec![42], vec![42, 8]));
@@ -1,4 +1,5 @@
 use super::*;
+use crate::answers::recorded_answer;
 
 const INPUT: &str = include_str!("../../input/2020/day19.txt");
 
@@ -104,6 +105,38 @@ fn parse_monster_messages_in_puzzle_input() {
     assert_eq!(monster_messages.rules[97], Expr::Char('b'));
 }
 
+#[test]
+fn try_parse_monster_messages_with_invalid_rule_reference() {
+    let error = try_parse_monster_messages("0: 1 2\n1: \"a\"\n2: 1 3 | 3 -1\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (3, 12));
+    assert_eq!(error.snippet(), "-1");
+}
+
+#[test]
+fn try_parse_monster_messages_with_reference_beyond_the_last_rule() {
+    let error = try_parse_monster_messages("0: 1 2\n1: \"a\"\n2: 1 | 5\n\nab\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (3, 8));
+    assert_eq!(error.kind(), &ParseErrorKind::Missing("definition of rule"));
+}
+
+#[test]
+fn try_parse_monster_messages_with_reference_to_gap() {
+    let error = try_parse_monster_messages("0: 1 3\n1: \"a\"\n4: \"b\"\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (1, 6));
+    assert_eq!(error.snippet(), "3");
+}
+
+#[test]
+fn pattern_with_unknown_rule_never_matches() {
+    let pattern = Pattern::new(vec![Expr::Seq(vec![1, 7]), Expr::Char('a')]);
+
+    assert!(!pattern.matches("a"));
+    assert!(!pattern.matches("aa"));
+}
+
 #[test]
 fn first_message_matches_pattern_in_example() {
     let monster_messages = parse_monster_messages(EXAMPLE);
@@ -160,7 +193,7 @@ fn count_matching_messages_in_puzzle_input() {
     let count = count_matching_messages(&monster_messages);
 
     assert_ne!(count, 14);
-    assert_eq!(count, 198);
+    assert_eq!(count.to_string(), recorded_answer(19, 1));
 }
 
 #[test]
@@ -188,5 +221,5 @@ fn count_matching_messages_patched_rules_in_puzzle_input() {
     let count = count_matching_messages_patched_rules(&monster_messages);
 
     assert_ne!(count, 14);
-    assert_eq!(count, 198);
+    assert_eq!(count.to_string(), recorded_answer(19, 2));
 }
@@ -190,31 +190,51 @@
 //! Assemble the tiles into an image. What do you get if you multiply together
 //! the IDs of the four corner tiles?
 //!
+//! ## Part 2
 //!
+//! Now, you're ready to check the image for sea monsters.
+//!
+//! The borders of each tile are not part of the actual image; start by
+//! removing them. Then remove the gaps between the tiles to form the actual
+//! image.
+//!
+//! Now, you're ready to search for sea monsters! Because your image is
+//! monochrome, a sea monster will look like this:
+//!
+//! ```text
+//!                   #
+//! #    ##    ##    ###
+//!  #  #  #  #  #  #
+//! ```
+//!
+//! When looking for this sea monster, you're looking for the pattern marked
+//! with `#` (the spaces can be anything). Unfortunately, the sea monster might
+//! be hiding anywhere in the image, and you might need to rotate or flip your
+//! image before it's oriented correctly to find sea monsters.
+//!
+//! In the example above, after flipping and rotating it to the appropriate
+//! orientation, there are two sea monsters.
+//!
+//! Determine how rough the waters are in the sea monsters' habitat by counting
+//! the number of `#` that are not part of a sea monster. In the above example,
+//! the habitat's water roughness is 273.
+//!
+//! How many `#` are not part of a sea monster?
 //!
 //! [Advent of Code 2020 - Day 20](https://adventofcode.com/2020/day/20)
 
+use crate::grid::{Grid, Position};
+use crate::parse::{end_of, parse_number, ParseError, ParseErrorKind};
 use fxhash::FxBuildHasher;
 use hashbrown::HashMap;
+use hashbrown::HashSet;
 use std::convert::TryFrom;
-use std::iter::FromIterator;
-use std::mem;
+use std::fmt;
 use std::str::FromStr;
 
-pub type Id = u16;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Angle {
-    D90,
-    D180,
-    D270,
-}
+pub use crate::grid::{Angle, Orientation};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Orientation {
-    Horizontal,
-    Vertical,
-}
+pub type Id = u16;
 
 #[allow(missing_copy_implementations)]
 #[derive(Debug, Clone, PartialEq)]
@@ -222,7 +242,7 @@ pub enum ParseColorError {
     InvalidCharacter(char),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Color {
     Black,
     White,
@@ -240,6 +260,15 @@ impl TryFrom<char> for Color {
     }
 }
 
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Color::Black => f.write_str("."),
+            Color::White => f.write_str("#"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ParseTileError {
     NotEnoughPixel,
@@ -254,36 +283,27 @@ impl From<ParseColorError> for ParseTileError {
 
 const TILE_LEN: usize = 10;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Tile {
     pub id: Id,
-    pub pixels: [[Color; TILE_LEN]; TILE_LEN],
+    pub pixels: Grid<Color>,
 }
 
 impl FromStr for Tile {
     type Err = ParseTileError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut img = [[Color::Black; TILE_LEN]; TILE_LEN];
-        let mut chars = s.chars();
+        let mut pixels = Grid::new(TILE_LEN, TILE_LEN, Color::Black);
+        let mut colors = s.chars().filter(|c| !c.is_whitespace());
         for row in 0..TILE_LEN {
-            for col in 0..TILE_LEN {
-                loop {
-                    if let Some(c) = chars.next() {
-                        if !c.is_whitespace() {
-                            let color = Color::try_from(c)?;
-                            img[row][col] = color;
-                            break;
-                        }
-                    } else {
-                        return Err(ParseTileError::NotEnoughPixel);
-                    }
-                }
+            for pixel in pixels.row_mut(row) {
+                let c = colors.next().ok_or(ParseTileError::NotEnoughPixel)?;
+                *pixel = Color::try_from(c)?;
             }
         }
         Ok(Self {
             id: Id::default(),
-            pixels: img,
+            pixels,
         })
     }
 }
@@ -292,10 +312,10 @@ impl Tile {
     pub fn edges(&self) -> Edges {
         let [mut top, mut right, mut bottom, mut left] = [[Color::Black; TILE_LEN]; NUM_EDGES];
         for i in 0..TILE_LEN {
-            top[i] = self.pixels[0][i];
-            right[i] = self.pixels[i][TILE_LEN - 1];
-            bottom[i] = self.pixels[TILE_LEN - 1][i];
-            left[i] = self.pixels[i][0];
+            top[i] = self.pixels[Position::new(0, i)];
+            right[i] = self.pixels[Position::new(i, TILE_LEN - 1)];
+            bottom[i] = self.pixels[Position::new(TILE_LEN - 1, i)];
+            left[i] = self.pixels[Position::new(i, 0)];
         }
         Edges::from([
             Edge::from(top),
@@ -306,57 +326,15 @@ impl Tile {
     }
 
     pub fn rotate(&mut self, angle: Angle) {
-        match angle {
-            Angle::D90 => {
-                let mut rotated = [[Color::Black; TILE_LEN]; TILE_LEN];
-                for row in 0..TILE_LEN {
-                    let rot_col = TILE_LEN - 1 - row;
-                    for col in 0..TILE_LEN {
-                        rotated[col][rot_col] = self.pixels[row][col]
-                    }
-                }
-                mem::swap(&mut self.pixels, &mut rotated);
-            }
-            Angle::D180 => {
-                let mut rotated = [[Color::Black; TILE_LEN]; TILE_LEN];
-                for row in 0..TILE_LEN {
-                    let rot_row = TILE_LEN - 1 - row;
-                    for col in 0..TILE_LEN {
-                        let rot_col = TILE_LEN - 1 - col;
-                        rotated[rot_row][rot_col] = self.pixels[row][col]
-                    }
-                }
-                mem::swap(&mut self.pixels, &mut rotated);
-            }
-            Angle::D270 => {
-                let mut rotated = [[Color::Black; TILE_LEN]; TILE_LEN];
-                for col in 0..TILE_LEN {
-                    let rot_row = TILE_LEN - 1 - col;
-                    for row in 0..TILE_LEN {
-                        rotated[rot_row][row] = self.pixels[row][col]
-                    }
-                }
-                mem::swap(&mut self.pixels, &mut rotated);
-            }
-        }
+        self.pixels.rotate(angle);
     }
 
     pub fn flip(&mut self, orientation: Orientation) {
-        match orientation {
-            Orientation::Horizontal => {
-                for col_a in 0..TILE_LEN / 2 {
-                    let col_b = TILE_LEN - 1 - col_a;
-                    for row in 0..TILE_LEN {
-                        self.pixels[row].swap(col_a, col_b);
-                    }
-                }
-            }
-            Orientation::Vertical => {
-                for row in 0..TILE_LEN / 2 {
-                    self.pixels.swap(row, TILE_LEN - 1 - row);
-                }
-            }
-        }
+        self.pixels.flip(orientation);
+    }
+
+    pub fn transform(&mut self, rotate: Option<Angle>, flip: Option<Orientation>) {
+        self.pixels.transform(rotate, flip);
     }
 }
 
@@ -376,7 +354,7 @@ const EDGE_LOCATIONS: [EdgeLocation; NUM_EDGES] = [
     EdgeLocation::Left,
 ];
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Edge([Color; TILE_LEN]);
 
 impl From<[Color; TILE_LEN]> for Edge {
@@ -434,6 +412,17 @@ impl Edges {
             Orientation::Vertical => Edges([bottom, right.flip(), top, left.flip()]),
         }
     }
+
+    pub fn transform(self, rotate: Option<Angle>, flip: Option<Orientation>) -> Self {
+        let rotated = match rotate {
+            Some(angle) => self.rotate(angle),
+            None => self,
+        };
+        match flip {
+            Some(orientation) => rotated.flip(orientation),
+            None => rotated,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -441,157 +430,319 @@ pub struct Image {
     pub tiles: Vec<Vec<Tile>>,
 }
 
-#[aoc_generator(day20)]
-pub fn parse_image_tiles(input: &str) -> Vec<Tile> {
+impl Image {
+    /// Returns the tiles in the top left, top right, bottom left and bottom
+    /// right corner of the image, or `None` if the image is empty.
+    pub fn corners(&self) -> Option<[&Tile; 4]> {
+        let first_row = self.tiles.first()?;
+        let last_row = self.tiles.last()?;
+        Some([
+            first_row.first()?,
+            first_row.last()?,
+            last_row.first()?,
+            last_row.last()?,
+        ])
+    }
+
+    /// Removes the border of each tile and joins the remaining pixels into
+    /// one raster.
+    pub fn stitch(&self) -> Raster {
+        Raster::from_rows(self.tiles.iter().flat_map(|tile_row| {
+            (1..TILE_LEN - 1).map(move |row| {
+                tile_row
+                    .iter()
+                    .flat_map(move |tile| tile.pixels.row(row)[1..TILE_LEN - 1].iter().copied())
+            })
+        }))
+    }
+}
+
+/// The pixels of the reassembled image.
+pub type Raster = Grid<Color>;
+
+pub const SEA_MONSTER: &str = concat!(
+    "                  # \n",
+    "#    ##    ##    ###\n",
+    " #  #  #  #  #  #   \n",
+);
+
+/// A pattern of pixels that must be `White` while all other pixels within
+/// its bounds can have any color.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    height: usize,
+    width: usize,
+    offsets: Vec<(usize, usize)>,
+}
+
+impl From<&str> for Pattern {
+    fn from(s: &str) -> Self {
+        let mut height = 0;
+        let mut width = 0;
+        let mut offsets = Vec::new();
+        for (row, line) in s.lines().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                if c == '#' {
+                    offsets.push((row, col));
+                    height = height.max(row + 1);
+                    width = width.max(col + 1);
+                }
+            }
+        }
+        Self {
+            height,
+            width,
+            offsets,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sightings {
+    pub rotate: Option<Angle>,
+    pub flip: Option<Orientation>,
+    pub positions: Vec<(usize, usize)>,
+}
+
+impl Pattern {
+    fn matches_at(&self, raster: &Raster, row: usize, col: usize) -> bool {
+        self.offsets
+            .iter()
+            .all(|(d_row, d_col)| raster[Position::new(row + d_row, col + d_col)] == Color::White)
+    }
+
+    /// Returns the top left position (row, column) of each occurrence of this
+    /// pattern in the raster as it is oriented.
+    pub fn find(&self, raster: &Raster) -> Vec<(usize, usize)> {
+        if self.height > raster.height() || self.width > raster.width() {
+            return Vec::new();
+        }
+        let mut positions = Vec::new();
+        for row in 0..=raster.height() - self.height {
+            for col in 0..=raster.width() - self.width {
+                if self.matches_at(raster, row, col) {
+                    positions.push((row, col));
+                }
+            }
+        }
+        positions
+    }
+
+    /// Searches the raster in each of its eight orientations and returns the
+    /// first orientation in which this pattern occurs.
+    pub fn search(&self, raster: &Raster) -> Option<Sightings> {
+        TRANSFORMATIONS.iter().find_map(|(rotate, flip)| {
+            let mut transformed = raster.clone();
+            transformed.transform(*rotate, *flip);
+            let positions = self.find(&transformed);
+            if positions.is_empty() {
+                None
+            } else {
+                Some(Sightings {
+                    rotate: *rotate,
+                    flip: *flip,
+                    positions,
+                })
+            }
+        })
+    }
+
+    /// Returns all pixels covered by this pattern when placed at the given
+    /// positions.
+    pub fn covered_pixels(&self, positions: &[(usize, usize)]) -> HashSet<(usize, usize)> {
+        positions
+            .iter()
+            .flat_map(|(row, col)| {
+                self.offsets
+                    .iter()
+                    .map(move |(d_row, d_col)| (row + d_row, col + d_col))
+            })
+            .collect()
+    }
+}
+
+/// Returns the number of tiles along each side of a square image made of
+/// `num_tiles` tiles, or `None` if they do not form a non-empty square.
+pub fn image_side(num_tiles: usize) -> Option<usize> {
+    (1..=num_tiles)
+        .take_while(|side| side * side <= num_tiles)
+        .find(|side| side * side == num_tiles)
+}
+
+/// Parses the tiles, which must be separated by blank lines only and form a
+/// non-empty square image.
+pub fn try_parse_image_tiles(input: &str) -> Result<Vec<Tile>, ParseError> {
     let mut tiles = Vec::new();
     let mut lines = input.lines();
     while let Some(line) = lines.next() {
-        if line.is_empty() {
+        if line.trim().is_empty() {
             continue;
         }
-        if line.starts_with("Tile") {
-            let id_str = line
-                .chars()
-                .skip(5)
-                .filter(|c| c.is_ascii_digit())
-                .collect::<String>();
-            let id = u16::from_str(&id_str).unwrap();
-            let mut pixes_str = String::from(lines.next().unwrap());
-            for _ in 0..9 {
-                pixes_str.push_str(lines.next().unwrap());
+        if !line.starts_with("Tile") {
+            return Err(ParseError::at(
+                input,
+                line,
+                ParseErrorKind::Invalid("tile header"),
+            ));
+        }
+        let id_str = line[4..].trim().trim_end_matches(':');
+        let id = parse_number(input, id_str)?;
+        let mut pixels = Grid::new(TILE_LEN, TILE_LEN, Color::Black);
+        for row in 0..TILE_LEN {
+            let pixel_line = lines.next().map(str::trim_end).ok_or_else(|| {
+                ParseError::at(
+                    input,
+                    end_of(input),
+                    ParseErrorKind::Missing("row of pixels"),
+                )
+            })?;
+            let mut colors = pixel_line.char_indices().map(|(index, c)| {
+                Color::try_from(c).map_err(|_| {
+                    ParseError::at(
+                        input,
+                        &pixel_line[index..index + c.len_utf8()],
+                        ParseErrorKind::UnexpectedCharacter(c),
+                    )
+                })
+            });
+            for pixel in pixels.row_mut(row) {
+                *pixel = colors.next().unwrap_or_else(|| {
+                    Err(ParseError::at(
+                        input,
+                        end_of(pixel_line),
+                        ParseErrorKind::Missing("pixel"),
+                    ))
+                })?;
+            }
+            if colors.next().is_some() {
+                return Err(ParseError::at(
+                    input,
+                    pixel_line,
+                    ParseErrorKind::Invalid("row of pixels"),
+                ));
             }
-            let mut tile = Tile::from_str(&pixes_str).unwrap();
-            tile.id = id;
-            tiles.push(tile);
         }
+        tiles.push(Tile { id, pixels });
+    }
+    if tiles.is_empty() {
+        return Err(ParseError::at(
+            input,
+            end_of(input),
+            ParseErrorKind::Missing("tile"),
+        ));
+    }
+    if image_side(tiles.len()).is_none() {
+        return Err(ParseError::at(
+            input,
+            end_of(input.trim_end()),
+            ParseErrorKind::Invalid("number of tiles, it must be a square number"),
+        ));
     }
-    tiles
+    Ok(tiles)
+}
+
+#[aoc_generator(day20)]
+pub fn parse_image_tiles(input: &str) -> Vec<Tile> {
+    try_parse_image_tiles(input).unwrap_or_else(|err| panic!("{}", err))
 }
 
-const PROCESSING: [(Option<Angle>, Option<Orientation>); 11] = [
+/// The eight distinct orientations a tile can have, expressed as a rotation
+/// followed by a flip.
+const TRANSFORMATIONS: [(Option<Angle>, Option<Orientation>); 8] = [
+    (None, None),
     (Some(Angle::D90), None),
     (Some(Angle::D180), None),
     (Some(Angle::D270), None),
+    (None, Some(Orientation::Horizontal)),
     (Some(Angle::D90), Some(Orientation::Horizontal)),
     (Some(Angle::D180), Some(Orientation::Horizontal)),
     (Some(Angle::D270), Some(Orientation::Horizontal)),
-    (Some(Angle::D90), Some(Orientation::Vertical)),
-    (Some(Angle::D180), Some(Orientation::Vertical)),
-    (Some(Angle::D270), Some(Orientation::Vertical)),
-    (None, Some(Orientation::Horizontal)),
-    (None, Some(Orientation::Vertical)),
 ];
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct TileEdge {
-    id: Id,
-    location: EdgeLocation,
-    edge: Edge,
-    flipped: Option<Orientation>,
-    rotated: Option<Angle>,
-}
-
-fn reassemble_image(tiles: &[Tile]) -> Image {
-    let tile_map =
-        HashMap::<_, _, FxBuildHasher>::from_iter(tiles.iter().map(|tile| (tile.id, tile)));
-
-    let original_tile_edges = Vec::from_iter(
-        tiles
-            .iter()
-            .map(|tile| {
-                let edges = tile.edges();
-                EDGE_LOCATIONS
-                    .iter()
-                    .map(|loc| TileEdge {
-                        id: tile.id,
-                        location: *loc,
-                        edge: edges.edge(*loc),
-                        flipped: None,
-                        rotated: None,
-                    })
-                    .collect::<Vec<_>>()
-            })
-            .flatten(),
-    );
-
-    let mut processed_tile_edges = Vec::with_capacity(original_tile_edges.len());
+/// Counts how many tiles have each edge, regardless of whether the edge is
+/// read forwards or backwards.
+fn count_edges(tiles: &[Tile]) -> HashMap<Edge, usize, FxBuildHasher> {
+    let mut edge_count = HashMap::with_hasher(FxBuildHasher::default());
     for tile in tiles {
         let edges = tile.edges();
-        for (rotate, flip) in &PROCESSING {
-            let modified_edges = match (rotate, flip) {
-                (Some(angle), Some(orientation)) => edges.rotate(*angle).flip(*orientation),
-                (Some(angle), None) => edges.rotate(*angle),
-                (None, Some(orientation)) => edges.flip(*orientation),
-                (None, None) => edges,
-            };
-            for located in &EDGE_LOCATIONS {
-                processed_tile_edges.push(TileEdge {
-                    id: tile.id,
-                    location: *located,
-                    edge: modified_edges.edge(*located),
-                    flipped: *flip,
-                    rotated: *rotate,
-                })
+        for location in &EDGE_LOCATIONS {
+            let edge = edges.edge(*location);
+            *edge_count.entry(edge).or_insert(0) += 1;
+            let flipped = edge.flip();
+            if flipped != edge {
+                *edge_count.entry(flipped).or_insert(0) += 1;
             }
         }
     }
+    edge_count
+}
 
-    let tile_edges = Vec::from_iter(
-        original_tile_edges
-            .into_iter()
-            .chain(processed_tile_edges.into_iter()),
-    );
-
-    let mut possible_neighbors: Vec<(TileEdge, TileEdge)> = Vec::with_capacity(tile_edges.len());
-    for (i, edge1) in tile_edges.iter().enumerate() {
-        for edge2 in tile_edges.iter().skip(i + 1) {
-            match (edge1.location, edge2.location) {
-                (EdgeLocation::Top, EdgeLocation::Bottom) => {
-                    if edge1.edge == edge2.edge {
-                        possible_neighbors.push((edge2.clone(), edge1.clone()));
-                    }
-                }
-                (EdgeLocation::Bottom, EdgeLocation::Top) => {
-                    if edge1.edge == edge2.edge {
-                        possible_neighbors.push((edge1.clone(), edge2.clone()));
-                    }
-                }
-                (EdgeLocation::Left, EdgeLocation::Right) => {
-                    if edge1.edge == edge2.edge {
-                        possible_neighbors.push((edge2.clone(), edge1.clone()));
-                    }
-                }
-                (EdgeLocation::Right, EdgeLocation::Left) => {
-                    if edge1.edge == edge2.edge {
-                        possible_neighbors.push((edge1.clone(), edge2.clone()));
-                    }
-                }
-                _ => {}
+/// Places the tiles into a square image, oriented so that their borders line
+/// up. Returns `None` if the tiles do not form a non-empty square or do not
+/// fit together.
+pub fn reassemble_image(tiles: &[Tile]) -> Option<Image> {
+    let side = image_side(tiles.len())?;
+
+    let edge_count = count_edges(tiles);
+    let is_outer_edge = |edge: Edge| edge_count.get(&edge) == Some(&1);
+
+    let mut remaining = tiles.to_vec();
+    let mut image_tiles: Vec<Vec<Tile>> = Vec::with_capacity(side);
+    for _ in 0..side {
+        let mut placed: Vec<Tile> = Vec::with_capacity(side);
+        for col in 0..side {
+            let top = image_tiles
+                .last()
+                .map(|above| above[col].edges().edge(EdgeLocation::Bottom));
+            let left = placed
+                .last()
+                .map(|tile| tile.edges().edge(EdgeLocation::Right));
+            let fits = |edges: Edges| {
+                let top_fits = match top {
+                    Some(edge) => edges.edge(EdgeLocation::Top) == edge,
+                    None => is_outer_edge(edges.edge(EdgeLocation::Top)),
+                };
+                let left_fits = match left {
+                    Some(edge) => edges.edge(EdgeLocation::Left) == edge,
+                    None => is_outer_edge(edges.edge(EdgeLocation::Left)),
+                };
+                top_fits && left_fits
             };
+
+            let (index, (rotate, flip)) =
+                remaining.iter().enumerate().find_map(|(index, tile)| {
+                    let edges = tile.edges();
+                    TRANSFORMATIONS
+                        .iter()
+                        .find(|(rotate, flip)| fits(edges.transform(*rotate, *flip)))
+                        .map(|transformation| (index, *transformation))
+                })?;
+
+            let mut tile = remaining.swap_remove(index);
+            tile.transform(rotate, flip);
+            placed.push(tile);
         }
+        image_tiles.push(placed);
     }
 
-    let edges_without_neighbor = tile_edges
-        .iter()
-        .filter(|tile| {
-            !possible_neighbors
-                .iter()
-                .any(|(e1, e2)| e1.edge == tile.edge || e2.edge == tile.edge)
-        })
-        .collect::<Vec<_>>();
-
-    dbg!(
-        tile_edges.len(),
-        possible_neighbors.len(),
-        edges_without_neighbor.len()
-    );
-
-    todo!()
+    Some(Image { tiles: image_tiles })
 }
 
 #[aoc(day20, part1)]
-pub fn checksum_of_reassembled_image(tiles: &[Tile]) -> u64 {
-    todo!()
+pub fn checksum_of_reassembled_image(tiles: &[Tile]) -> Option<u64> {
+    let image = reassemble_image(tiles)?;
+    let corners = image.corners()?;
+    Some(corners.iter().map(|tile| u64::from(tile.id)).product())
+}
+
+#[aoc(day20, part2)]
+pub fn water_roughness(tiles: &[Tile]) -> Option<usize> {
+    let image = reassemble_image(tiles)?;
+    let mut raster = image.stitch();
+    let sea_monster = Pattern::from(SEA_MONSTER);
+    let sightings = sea_monster.search(&raster)?;
+    raster.transform(sightings.rotate, sightings.flip);
+    Some(raster.count(&Color::White) - sea_monster.covered_pixels(&sightings.positions).len())
 }
 
 #[cfg(test)]
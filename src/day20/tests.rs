@@ -1,4 +1,7 @@
 use super::*;
+use crate::answers::recorded_answer;
+use crate::registry::Answer;
+use proptest::collection::vec;
 use proptest::prelude::*;
 use Color::*;
 
@@ -235,129 +238,8 @@ fn any_color() -> impl Strategy<Value = Color> {
 }
 
 fn any_tile() -> impl Strategy<Value = Tile> {
-    let pixels = [
-        [
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-        ],
-        [
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-        ],
-        [
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-        ],
-        [
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-        ],
-        [
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-        ],
-        [
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-        ],
-        [
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-        ],
-        [
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-        ],
-        [
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-        ],
-        [
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-            any_color(),
-        ],
-    ]
-    .boxed();
+    let pixels = vec(any_color(), TILE_LEN * TILE_LEN)
+        .prop_map(|pixels| Grid::from_cells(TILE_LEN, TILE_LEN, pixels));
     (1u16..=9999, pixels).prop_map(|(id, pixels)| Tile { id, pixels })
 }
 
@@ -388,7 +270,7 @@ fn parse_tile_1489_from_str() {
     assert_eq!(tile.id, 0);
     assert_eq!(
         tile.pixels,
-        [
+        Grid::from_rows(vec![
             [White, White, Black, White, Black, White, Black, Black, Black, Black],
             [Black, Black, White, White, Black, Black, Black, White, Black, Black],
             [Black, White, White, Black, Black, White, White, Black, Black, Black],
@@ -399,7 +281,7 @@ fn parse_tile_1489_from_str() {
             [White, White, Black, White, Black, Black, Black, White, White, Black],
             [Black, Black, White, White, Black, White, White, Black, White, White],
             [White, White, White, Black, White, White, Black, White, Black, Black],
-        ]
+        ])
     );
 }
 
@@ -653,11 +535,227 @@ fn parse_image_tiles_in_puzzle_input() {
     assert_eq!(tiles.len(), 1728 / 12);
 }
 
+#[test]
+fn try_parse_image_tiles_with_short_row() {
+    let error = try_parse_image_tiles(concat!(
+        "Tile 2311:\n",
+        "..##.#..#.\n",
+        "##..#.....\n",
+        "#...##..#.\n",
+        "####.#...#\n",
+        "##.##.###\n",
+    ))
+    .unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (6, 10));
+    assert_eq!(error.kind(), &ParseErrorKind::Missing("pixel"));
+}
+
+#[test]
+fn try_parse_image_tiles_with_stray_text_between_tiles() {
+    let input = format!(
+        "Tile 1489:\n{}some notes\n\nTile 1489:\n{}",
+        TILE_1489, TILE_1489
+    );
+
+    let error = try_parse_image_tiles(&input).unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (12, 1));
+    assert_eq!(error.kind(), &ParseErrorKind::Invalid("tile header"));
+}
+
+#[test]
+fn try_parse_image_tiles_without_tiles() {
+    let error = try_parse_image_tiles("\n").unwrap_err();
+
+    assert_eq!(error.kind(), &ParseErrorKind::Missing("tile"));
+}
+
+#[test]
+fn try_parse_image_tiles_with_number_of_tiles_that_is_not_square() {
+    let input = format!("Tile 1489:\n{}\nTile 1489:\n{}", TILE_1489, TILE_1489);
+
+    let error = try_parse_image_tiles(&input).unwrap_err();
+
+    assert_eq!(
+        error.kind(),
+        &ParseErrorKind::Invalid("number of tiles, it must be a square number")
+    );
+}
+
+#[test]
+fn image_side_of_square_numbers_of_tiles() {
+    assert_eq!(image_side(0), None);
+    assert_eq!(image_side(1), Some(1));
+    assert_eq!(image_side(8), None);
+    assert_eq!(image_side(9), Some(3));
+    assert_eq!(image_side(144), Some(12));
+}
+
+proptest! {
+    #[test]
+    fn transforming_any_tile_gives_the_same_edges_as_transforming_its_edges(
+        tile in any_tile(),
+        transformation in 0..TRANSFORMATIONS.len(),
+    ) {
+        let (rotate, flip) = TRANSFORMATIONS[transformation];
+        let mut transformed = tile.clone();
+        transformed.transform(rotate, flip);
+
+        prop_assert_eq!(transformed.edges(), tile.edges().transform(rotate, flip));
+    }
+}
+
 #[test]
 fn reassemble_image_in_example() {
     let tiles = parse_image_tiles(EXAMPLE);
 
-    let image = reassemble_image(&tiles);
+    let image = reassemble_image(&tiles).unwrap();
+
+    assert_eq!(image.tiles.len(), 3);
+    assert!(image.tiles.iter().all(|row| row.len() == 3));
+    let mut corner_ids = image
+        .corners()
+        .unwrap()
+        .iter()
+        .map(|tile| tile.id)
+        .collect::<Vec<_>>();
+    corner_ids.sort_unstable();
+    assert_eq!(corner_ids, vec![1171, 1951, 2971, 3079]);
+    assert_eq!(image.tiles[1][1].id, 1427);
+    for row in 0..3 {
+        for col in 0..3 {
+            let edges = image.tiles[row][col].edges();
+            if col > 0 {
+                let left = image.tiles[row][col - 1].edges();
+                assert_eq!(
+                    edges.edge(EdgeLocation::Left),
+                    left.edge(EdgeLocation::Right)
+                );
+            }
+            if row > 0 {
+                let above = image.tiles[row - 1][col].edges();
+                assert_eq!(
+                    edges.edge(EdgeLocation::Top),
+                    above.edge(EdgeLocation::Bottom)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn checksum_of_reassembled_image_in_example() {
+    let tiles = parse_image_tiles(EXAMPLE);
+
+    let checksum = checksum_of_reassembled_image(&tiles);
+
+    assert_eq!(checksum, Some(20899048083289));
+}
+
+#[test]
+fn checksum_of_reassembled_image_in_puzzle_input() {
+    let tiles = parse_image_tiles(INPUT);
+
+    let checksum = checksum_of_reassembled_image(&tiles);
+
+    assert_eq!(checksum.to_answer(), recorded_answer(20, 1));
+}
+
+/// Removes the borders and gaps from the reassembled image as shown in the
+/// puzzle description.
+fn stitched_example_img() -> Raster {
+    let rows = EXAMPLE_IMG
+        .split("\n\n")
+        .flat_map(|tile_row| {
+            let lines = tile_row.lines().collect::<Vec<_>>();
+            lines[1..TILE_LEN - 1]
+                .iter()
+                .map(|line| {
+                    line.split(' ')
+                        .map(|tile_line| &tile_line[1..TILE_LEN - 1])
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    Raster::from_str(&rows.join("\n")).unwrap()
+}
+
+#[test]
+fn reassemble_image_without_tiles() {
+    assert_eq!(reassemble_image(&[]), None);
+}
+
+#[test]
+fn reassemble_image_of_tiles_that_do_not_fit() {
+    let tile = Tile::from_str(TILE_1489).unwrap();
+    let mut tiles = vec![tile; 4];
+    for (id, tile) in tiles.iter_mut().enumerate() {
+        tile.id = id as Id;
+    }
+
+    assert_eq!(reassemble_image(&tiles), None);
+    assert_eq!(checksum_of_reassembled_image(&tiles), None);
+    assert_eq!(water_roughness(&tiles), None);
+}
+
+#[test]
+fn corners_of_empty_image() {
+    let image = Image { tiles: vec![] };
+
+    assert_eq!(image.corners(), None);
+}
+
+#[test]
+fn stitch_image_in_example() {
+    let tiles = parse_image_tiles(EXAMPLE);
+    let image = reassemble_image(&tiles).unwrap();
+
+    let raster = image.stitch();
+
+    assert_eq!(raster.height(), 24);
+    assert_eq!(raster.width(), 24);
+    let expected = stitched_example_img();
+    assert!(TRANSFORMATIONS.iter().any(|(rotate, flip)| {
+        let mut transformed = raster.clone();
+        transformed.transform(*rotate, *flip);
+        transformed == expected
+    }));
+}
+
+#[test]
+fn display_raster() {
+    let raster = Raster::from_str(TILE_1489).unwrap();
+
+    assert_eq!(raster.to_string(), TILE_1489);
+}
+
+#[test]
+fn search_sea_monsters_in_example() {
+    let raster = stitched_example_img();
+    let sea_monster = Pattern::from(SEA_MONSTER);
+
+    let sightings = sea_monster.search(&raster).unwrap();
+
+    assert_eq!(sightings.positions.len(), 2);
+    assert_eq!(sea_monster.covered_pixels(&sightings.positions).len(), 30);
+}
+
+#[test]
+fn water_roughness_in_example() {
+    let tiles = parse_image_tiles(EXAMPLE);
+
+    let roughness = water_roughness(&tiles);
+
+    assert_eq!(roughness, Some(273));
+}
+
+#[test]
+fn water_roughness_in_puzzle_input() {
+    let tiles = parse_image_tiles(INPUT);
+
+    let roughness = water_roughness(&tiles);
 
-    assert_eq!(image.tiles.len(), 9);
+    assert_eq!(roughness.to_answer(), recorded_answer(20, 2));
 }
@@ -0,0 +1,175 @@
+//! # Day 21: Allergen Assessment
+//!
+//! You reach the train's last stop and the closest you can get to your
+//! vacation island without getting wet. There aren't even any boats here, but
+//! nobody said anything about flying!
+//!
+//! You don't speak the local language, so you can't read any ingredients
+//! lists. However, sometimes, allergens are listed in a language you do
+//! understand. You should be able to use this information to determine which
+//! ingredient contains which allergen and work out which foods are safe to
+//! take with you on your trip.
+//!
+//! You start by compiling a list of foods (your puzzle input), one food per
+//! line. Each line includes that food's ingredients list followed by some or
+//! all of the allergens the food contains.
+//!
+//! Each allergen is found in exactly one ingredient. Each ingredient contains
+//! zero or one allergen. Allergens aren't always marked; when they're listed
+//! (as in `(contains nuts, shellfish)` after an ingredients list), the
+//! ingredient that contains each listed allergen will be somewhere in the
+//! corresponding ingredients list. However, even if an allergen isn't listed,
+//! the ingredient that contains that allergen could still be present: maybe
+//! they forgot to label it, or maybe it was labeled in a language you don't
+//! know.
+//!
+//! For example, consider the following list of foods:
+//!
+//! ```text
+//! mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+//! trh fvjkl sbzzf mxmxvkd (contains dairy)
+//! sqjhc fvjkl (contains soy)
+//! sqjhc mxmxvkd sbzzf (contains fish)
+//! ```
+//!
+//! The first food in the list has four ingredients (written in a language you
+//! don't understand): `mxmxvkd`, `kfcds`, `sqjhc`, and `nhms`. While the food
+//! might contain other allergens, a few allergens the food definitely contains
+//! are listed afterward: `dairy` and `fish`.
+//!
+//! The first step is to determine which ingredients can't possibly contain any
+//! of the allergens in any food in your list. In the above example, none of
+//! the ingredients `kfcds`, `nhms`, `sbzzf`, or `trh` can contain an allergen.
+//! Counting the number of times any of these ingredients appear in any
+//! ingredients list produces 5: they all appear once each except `sbzzf`,
+//! which appears twice.
+//!
+//! Determine which ingredients cannot possibly contain any of the allergens in
+//! your list. How many times do any of those ingredients appear?
+//!
+//! ## Part 2
+//!
+//! Now that you've isolated the inert ingredients, you should have enough
+//! information to figure out which ingredient contains which allergen.
+//!
+//! In the above example:
+//!
+//! * `mxmxvkd` contains `dairy`.
+//! * `sqjhc` contains `fish`.
+//! * `fvjkl` contains `soy`.
+//!
+//! Arrange the ingredients alphabetically by their allergen and separate them
+//! by commas to produce your canonical dangerous ingredient list. (There
+//! should not be any spaces in your canonical dangerous ingredient list.) In
+//! the above example, this would be `mxmxvkd,sqjhc,fvjkl`.
+//!
+//! Time to stock your raft with supplies. What is your canonical dangerous
+//! ingredient list?
+//!
+//! [Advent of Code 2020 - Day 21](https://adventofcode.com/2020/day/21)
+
+use crate::assignment::assign_by_elimination;
+use crate::parse::{end_of, ParseError, ParseErrorKind};
+use hashbrown::{HashMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Food {
+    pub ingredients: Vec<String>,
+    pub allergens: Vec<String>,
+}
+
+pub fn try_parse_food_list(input: &str) -> Result<Vec<Food>, ParseError> {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let (ingredients_str, allergens_str) = match line.find(" (contains ") {
+                Some(index) => {
+                    let allergens_str = &line[index + 11..];
+                    if !allergens_str.ends_with(')') {
+                        return Err(ParseError::at(
+                            input,
+                            end_of(line),
+                            ParseErrorKind::Missing("')'"),
+                        ));
+                    }
+                    (&line[..index], &allergens_str[..allergens_str.len() - 1])
+                }
+                None => (line, ""),
+            };
+            let ingredients = ingredients_str
+                .split_whitespace()
+                .map(String::from)
+                .collect();
+            let allergens = allergens_str
+                .split(',')
+                .map(str::trim)
+                .filter(|allergen| !allergen.is_empty())
+                .map(String::from)
+                .collect();
+            Ok(Food {
+                ingredients,
+                allergens,
+            })
+        })
+        .collect()
+}
+
+#[aoc_generator(day21)]
+pub fn parse_food_list(input: &str) -> Vec<Food> {
+    try_parse_food_list(input).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Determines for each allergen the ingredients that may contain it.
+///
+/// An ingredient may contain an allergen if it is listed in every food that
+/// is known to contain the allergen.
+fn possible_ingredients_by_allergen(foods: &[Food]) -> HashMap<&str, HashSet<&str>> {
+    let mut candidates: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for food in foods {
+        let ingredients = food
+            .ingredients
+            .iter()
+            .map(String::as_str)
+            .collect::<HashSet<_>>();
+        for allergen in &food.allergens {
+            candidates
+                .entry(allergen)
+                .and_modify(|possible| {
+                    possible.retain(|ingredient| ingredients.contains(ingredient))
+                })
+                .or_insert_with(|| ingredients.clone());
+        }
+    }
+    candidates
+}
+
+#[aoc(day21, part1)]
+pub fn number_of_appearances_of_allergen_free_ingredients(foods: &[Food]) -> usize {
+    let candidates = possible_ingredients_by_allergen(foods);
+    let possibly_dangerous = candidates.values().flatten().collect::<HashSet<_>>();
+    foods
+        .iter()
+        .flat_map(|food| food.ingredients.iter())
+        .filter(|ingredient| !possibly_dangerous.contains(&ingredient.as_str()))
+        .count()
+}
+
+#[aoc(day21, part2)]
+pub fn canonical_dangerous_ingredient_list(foods: &[Food]) -> String {
+    let candidates = possible_ingredients_by_allergen(foods);
+    let mut dangerous_ingredients = assign_by_elimination(candidates)
+        .expect("no unique allergen assignment found!")
+        .into_iter()
+        .collect::<Vec<_>>();
+    dangerous_ingredients.sort_unstable();
+    dangerous_ingredients
+        .into_iter()
+        .map(|(_, ingredient)| ingredient)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,110 @@
+use super::*;
+use crate::answers::recorded_answer;
+use std::iter::FromIterator;
+
+const INPUT: &str = include_str!("../../input/2020/day21.txt");
+
+const EXAMPLE: &str = "\
+mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+trh fvjkl sbzzf mxmxvkd (contains dairy)
+sqjhc fvjkl (contains soy)
+sqjhc mxmxvkd sbzzf (contains fish)
+";
+
+#[test]
+fn parse_food_list_in_example() {
+    let foods = parse_food_list(EXAMPLE);
+
+    assert_eq!(foods.len(), 4);
+    assert_eq!(
+        foods[0],
+        Food {
+            ingredients: vec![
+                "mxmxvkd".into(),
+                "kfcds".into(),
+                "sqjhc".into(),
+                "nhms".into()
+            ],
+            allergens: vec!["dairy".into(), "fish".into()],
+        }
+    );
+    assert_eq!(
+        foods[2],
+        Food {
+            ingredients: vec!["sqjhc".into(), "fvjkl".into()],
+            allergens: vec!["soy".into()],
+        }
+    );
+}
+
+#[test]
+fn parse_food_list_in_puzzle_input() {
+    let foods = parse_food_list(INPUT);
+
+    assert_eq!(foods.len(), INPUT.lines().count());
+    assert!(foods.iter().all(|food| !food.allergens.is_empty()));
+}
+
+#[test]
+fn try_parse_food_list_with_unclosed_allergen_list() {
+    let error =
+        try_parse_food_list("mxmxvkd kfcds (contains dairy, fish)\nsqjhc fvjkl (contains soy\n")
+            .unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (2, 26));
+    assert_eq!(error.kind(), &ParseErrorKind::Missing("')'"));
+}
+
+#[test]
+fn possible_ingredients_by_allergen_in_example() {
+    let foods = parse_food_list(EXAMPLE);
+
+    let candidates = possible_ingredients_by_allergen(&foods);
+
+    assert_eq!(candidates.len(), 3);
+    assert_eq!(candidates["dairy"], HashSet::from_iter(vec!["mxmxvkd"]));
+    assert_eq!(
+        candidates["fish"],
+        HashSet::from_iter(vec!["mxmxvkd", "sqjhc"])
+    );
+    assert_eq!(
+        candidates["soy"],
+        HashSet::from_iter(vec!["sqjhc", "fvjkl"])
+    );
+}
+
+#[test]
+fn number_of_appearances_of_allergen_free_ingredients_in_example() {
+    let foods = parse_food_list(EXAMPLE);
+
+    let appearances = number_of_appearances_of_allergen_free_ingredients(&foods);
+
+    assert_eq!(appearances, 5);
+}
+
+#[test]
+fn number_of_appearances_of_allergen_free_ingredients_in_puzzle_input() {
+    let foods = parse_food_list(INPUT);
+
+    let appearances = number_of_appearances_of_allergen_free_ingredients(&foods);
+
+    assert_eq!(appearances.to_string(), recorded_answer(21, 1));
+}
+
+#[test]
+fn canonical_dangerous_ingredient_list_in_example() {
+    let foods = parse_food_list(EXAMPLE);
+
+    let dangerous_ingredients = canonical_dangerous_ingredient_list(&foods);
+
+    assert_eq!(dangerous_ingredients, "mxmxvkd,sqjhc,fvjkl");
+}
+
+#[test]
+fn canonical_dangerous_ingredient_list_in_puzzle_input() {
+    let foods = parse_food_list(INPUT);
+
+    let dangerous_ingredients = canonical_dangerous_ingredient_list(&foods);
+
+    assert_eq!(dangerous_ingredients, recorded_answer(21, 2));
+}
@@ -0,0 +1,287 @@
+//! # Day 22: Crab Combat
+//!
+//! It only takes a few hours of sailing the ocean on a raft for boredom to
+//! sink in. Fortunately, you brought a small deck of space cards! You'd like
+//! to play a game of Combat, and there's even an opponent available: a small
+//! crab that climbed aboard your raft before you left.
+//!
+//! Fortunately, it doesn't take long to teach the crab the rules.
+//!
+//! Before the game starts, split the cards so each player has their own deck
+//! (your puzzle input). Then, the game consists of a series of rounds: both
+//! players draw their top card, and the player with the higher-valued card
+//! wins the round. The winner keeps both cards, placing them on the bottom of
+//! their own deck so that the winner's card is above the other card. If this
+//! causes a player to have all of the cards, they win, and the game ends.
+//!
+//! For example, consider the following starting decks:
+//!
+//! ```text
+//! Player 1:
+//! 9
+//! 2
+//! 6
+//! 3
+//! 1
+//!
+//! Player 2:
+//! 5
+//! 8
+//! 4
+//! 7
+//! 10
+//! ```
+//!
+//! This arrangement means that player 1's deck contains 5 cards, with 9 on
+//! top and 1 on the bottom; player 2's deck also contains 5 cards, with 5 on
+//! top and 10 on the bottom.
+//!
+//! After 29 rounds player 2 has all of the cards:
+//!
+//! ```text
+//! == Post-game results ==
+//! Player 1's deck:
+//! Player 2's deck: 3, 2, 10, 6, 8, 5, 9, 4, 7, 1
+//! ```
+//!
+//! Once the game ends, you can calculate the winning player's score. The
+//! bottom card in their deck is worth the value of the card multiplied by 1,
+//! the second-from-the-bottom card is worth the value of the card multiplied
+//! by 2, and so on. With 10 cards, the top card is worth the value on the
+//! card multiplied by 10. In this example, the winning player's score is 306.
+//!
+//! Play the small crab in a game of Combat using the two decks you just dealt.
+//! What is the winning player's score?
+//!
+//! ## Part 2
+//!
+//! You lost to the small crab! Fortunately, crabs aren't very good at
+//! recursion. To defend your honor as a Raft Captain, you challenge the small
+//! crab to a game of Recursive Combat.
+//!
+//! Recursive Combat still starts by splitting the cards into two decks. Then,
+//! the game consists of a series of rounds with a few changes:
+//!
+//! * Before either player deals a card, if there was a previous round in this
+//!   game that had exactly the same cards in the same order in the same
+//!   players' decks, the game instantly ends in a win for player 1. Previous
+//!   rounds from other games are not considered.
+//! * Otherwise, this round's cards must be in a new configuration; the players
+//!   begin the round by each drawing the top card of their deck as normal.
+//! * If both players have at least as many cards remaining in their deck as
+//!   the value of the card they just drew, the winner of the round is
+//!   determined by playing a new game of Recursive Combat. To play a sub-game,
+//!   each player creates a new deck by making a copy of the next cards in
+//!   their deck (the quantity of cards copied is equal to the number on the
+//!   card they drew to trigger the sub-game).
+//! * Otherwise, at least one player must not have enough cards left in their
+//!   deck to recurse; the winner of the round is the player with the
+//!   higher-value card.
+//!
+//! As in regular Combat, the winner of the round (even if they won the round
+//! by winning a sub-game) takes the two cards dealt at the beginning of the
+//! round and places them on the bottom of their own deck (again so that the
+//! winner's card is above the other card).
+//!
+//! In the above example, player 2 wins the game of Recursive Combat with a
+//! score of 291.
+//!
+//! Defend your honor as Raft Captain by playing the small crab in a game of
+//! Recursive Combat using the same two decks as before. What is the winning
+//! player's score?
+//!
+//! [Advent of Code 2020 - Day 22](https://adventofcode.com/2020/day/22)
+
+use crate::parse::{end_of, parse_number, ParseError, ParseErrorKind};
+use fxhash::FxBuildHasher;
+use hashbrown::HashSet;
+use std::collections::VecDeque;
+
+pub type Card = u8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Player {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Deck {
+    cards: VecDeque<Card>,
+}
+
+impl Deck {
+    pub fn new(cards: impl IntoIterator<Item = Card>) -> Self {
+        Self {
+            cards: cards.into_iter().collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    pub fn cards(&self) -> impl Iterator<Item = Card> + '_ {
+        self.cards.iter().copied()
+    }
+
+    pub fn highest_card(&self) -> Option<Card> {
+        self.cards.iter().copied().max()
+    }
+
+    pub fn draw(&mut self) -> Option<Card> {
+        self.cards.pop_front()
+    }
+
+    /// Places the two cards of a won round on the bottom of this deck, the
+    /// winner's card above the other card.
+    pub fn put_to_bottom(&mut self, winner_card: Card, loser_card: Card) {
+        self.cards.push_back(winner_card);
+        self.cards.push_back(loser_card);
+    }
+
+    /// Creates a new deck from a copy of the given number of cards from the
+    /// top of this deck.
+    pub fn copy_top(&self, num_cards: usize) -> Self {
+        Self::new(self.cards.iter().take(num_cards).copied())
+    }
+
+    pub fn score(&self) -> u64 {
+        self.cards
+            .iter()
+            .rev()
+            .zip(1..)
+            .map(|(card, factor)| u64::from(*card) * factor)
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decks {
+    pub player1: Deck,
+    pub player2: Deck,
+}
+
+/// Parses the decks of exactly two players, each starting with a line like
+/// `Player 1:`.
+pub fn try_parse_decks(input: &str) -> Result<Decks, ParseError> {
+    let mut decks = Vec::with_capacity(2);
+    for line in input.lines().map(str::trim) {
+        if line.starts_with("Player") {
+            if decks.len() == 2 {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    ParseErrorKind::Invalid("player, there must be exactly 2 players"),
+                ));
+            }
+            decks.push(Vec::new());
+        } else if !line.is_empty() {
+            let cards = decks.last_mut().ok_or_else(|| {
+                ParseError::at(input, line, ParseErrorKind::Missing("player of the deck"))
+            })?;
+            cards.push(parse_number(input, line)?);
+        }
+    }
+    let missing_deck =
+        |player| ParseError::at(input, end_of(input), ParseErrorKind::Missing(player));
+    let mut decks = decks.into_iter().map(Deck::new);
+    let player1 = decks
+        .next()
+        .ok_or_else(|| missing_deck("deck for player 1"))?;
+    let player2 = decks
+        .next()
+        .ok_or_else(|| missing_deck("deck for player 2"))?;
+    Ok(Decks { player1, player2 })
+}
+
+#[aoc_generator(day22)]
+pub fn parse_decks(input: &str) -> Decks {
+    try_parse_decks(input).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Plays a game of Combat and returns the winner and their final deck.
+///
+/// Some decks make the game go on forever. Like in Recursive Combat, player 1
+/// wins the game as soon as a round repeats.
+pub fn play_combat(mut player1: Deck, mut player2: Deck) -> (Player, Deck) {
+    let mut previous_rounds = HashSet::with_hasher(FxBuildHasher::default());
+    while !player1.is_empty() && !player2.is_empty() {
+        if !previous_rounds.insert((player1.clone(), player2.clone())) {
+            return (Player::One, player1);
+        }
+        let card1 = player1.draw().expect("deck of player 1 is empty");
+        let card2 = player2.draw().expect("deck of player 2 is empty");
+        if card1 > card2 {
+            player1.put_to_bottom(card1, card2);
+        } else {
+            player2.put_to_bottom(card2, card1);
+        }
+    }
+    if player2.is_empty() {
+        (Player::One, player1)
+    } else {
+        (Player::Two, player2)
+    }
+}
+
+/// Plays a game of Recursive Combat and returns the winner and their final
+/// deck.
+pub fn play_recursive_combat(mut player1: Deck, mut player2: Deck) -> (Player, Deck) {
+    let mut previous_rounds = HashSet::with_hasher(FxBuildHasher::default());
+    while !player1.is_empty() && !player2.is_empty() {
+        if !previous_rounds.insert((player1.clone(), player2.clone())) {
+            return (Player::One, player1);
+        }
+        let card1 = player1.draw().expect("deck of player 1 is empty");
+        let card2 = player2.draw().expect("deck of player 2 is empty");
+        let winner = if player1.len() >= usize::from(card1) && player2.len() >= usize::from(card2) {
+            play_recursive_sub_game(
+                player1.copy_top(usize::from(card1)),
+                player2.copy_top(usize::from(card2)),
+            )
+        } else if card1 > card2 {
+            Player::One
+        } else {
+            Player::Two
+        };
+        match winner {
+            Player::One => player1.put_to_bottom(card1, card2),
+            Player::Two => player2.put_to_bottom(card2, card1),
+        }
+    }
+    if player2.is_empty() {
+        (Player::One, player1)
+    } else {
+        (Player::Two, player2)
+    }
+}
+
+fn play_recursive_sub_game(player1: Deck, player2: Deck) -> Player {
+    // Player 1 can never lose the highest card. So either the game ends
+    // because player 2 runs out of cards or because a round repeats. In both
+    // cases player 1 wins the sub-game.
+    if player1.highest_card() > player2.highest_card() {
+        return Player::One;
+    }
+    play_recursive_combat(player1, player2).0
+}
+
+#[aoc(day22, part1)]
+pub fn winning_players_score_in_combat(decks: &Decks) -> u64 {
+    let (_, deck) = play_combat(decks.player1.clone(), decks.player2.clone());
+    deck.score()
+}
+
+#[aoc(day22, part2)]
+pub fn winning_players_score_in_recursive_combat(decks: &Decks) -> u64 {
+    let (_, deck) = play_recursive_combat(decks.player1.clone(), decks.player2.clone());
+    deck.score()
+}
+
+#[cfg(test)]
+mod tests;
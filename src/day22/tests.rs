@@ -0,0 +1,163 @@
+use super::*;
+use crate::answers::recorded_answer;
+
+const INPUT: &str = include_str!("../../input/2020/day22.txt");
+
+const EXAMPLE: &str = "\
+Player 1:
+9
+2
+6
+3
+1
+
+Player 2:
+5
+8
+4
+7
+10
+";
+
+const EXAMPLE_INFINITE_GAME: &str = "\
+Player 1:
+43
+19
+
+Player 2:
+2
+29
+14
+";
+
+#[test]
+fn parse_decks_in_example() {
+    let decks = parse_decks(EXAMPLE);
+
+    assert_eq!(decks.player1, Deck::new(vec![9, 2, 6, 3, 1]));
+    assert_eq!(decks.player2, Deck::new(vec![5, 8, 4, 7, 10]));
+}
+
+#[test]
+fn parse_decks_in_puzzle_input() {
+    let decks = parse_decks(INPUT);
+
+    assert_eq!(decks.player1.len(), 25);
+    assert_eq!(decks.player2.len(), 25);
+}
+
+#[test]
+fn try_parse_decks_with_invalid_card() {
+    let error = try_parse_decks("Player 1:\n9\n2\n\nPlayer 2:\n5\n8k\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (7, 1));
+    assert_eq!(error.snippet(), "8k");
+}
+
+#[test]
+fn try_parse_decks_with_three_players() {
+    let error = try_parse_decks("Player 1:\n9\n\nPlayer 2:\n5\n\nPlayer 3:\n7\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (7, 1));
+    assert_eq!(
+        error.kind(),
+        &ParseErrorKind::Invalid("player, there must be exactly 2 players")
+    );
+}
+
+#[test]
+fn try_parse_decks_with_one_player() {
+    let error = try_parse_decks("Player 1:\n9\n2\n").unwrap_err();
+
+    assert_eq!(error.kind(), &ParseErrorKind::Missing("deck for player 2"));
+}
+
+#[test]
+fn try_parse_decks_with_cards_before_first_player() {
+    let error = try_parse_decks("9\nPlayer 1:\n2\n\nPlayer 2:\n5\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (1, 1));
+    assert_eq!(error.kind(), &ParseErrorKind::Missing("player of the deck"));
+}
+
+#[test]
+fn score_of_deck() {
+    let deck = Deck::new(vec![3, 2, 10, 6, 8, 5, 9, 4, 7, 1]);
+
+    assert_eq!(deck.score(), 306);
+}
+
+#[test]
+fn play_combat_in_example() {
+    let decks = parse_decks(EXAMPLE);
+
+    let (winner, deck) = play_combat(decks.player1, decks.player2);
+
+    assert_eq!(winner, Player::Two);
+    assert_eq!(deck, Deck::new(vec![3, 2, 10, 6, 8, 5, 9, 4, 7, 1]));
+}
+
+#[test]
+fn play_combat_terminates_on_repeated_round() {
+    let decks = parse_decks(EXAMPLE_INFINITE_GAME);
+
+    let (winner, deck) = play_combat(decks.player1, decks.player2);
+
+    assert_eq!(winner, Player::One);
+    assert_eq!(deck, Deck::new(vec![43, 19]));
+}
+
+#[test]
+fn winning_players_score_in_combat_in_example() {
+    let decks = parse_decks(EXAMPLE);
+
+    let score = winning_players_score_in_combat(&decks);
+
+    assert_eq!(score, 306);
+}
+
+#[test]
+fn winning_players_score_in_combat_in_puzzle_input() {
+    let decks = parse_decks(INPUT);
+
+    let score = winning_players_score_in_combat(&decks);
+
+    assert_eq!(score.to_string(), recorded_answer(22, 1));
+}
+
+#[test]
+fn play_recursive_combat_in_example() {
+    let decks = parse_decks(EXAMPLE);
+
+    let (winner, deck) = play_recursive_combat(decks.player1, decks.player2);
+
+    assert_eq!(winner, Player::Two);
+    assert_eq!(deck, Deck::new(vec![7, 5, 6, 2, 4, 1, 10, 8, 9, 3]));
+}
+
+#[test]
+fn play_recursive_combat_terminates_on_repeated_round() {
+    let decks = parse_decks(EXAMPLE_INFINITE_GAME);
+
+    let (winner, _) = play_recursive_combat(decks.player1, decks.player2);
+
+    assert_eq!(winner, Player::One);
+}
+
+#[test]
+fn winning_players_score_in_recursive_combat_in_example() {
+    let decks = parse_decks(EXAMPLE);
+
+    let score = winning_players_score_in_recursive_combat(&decks);
+
+    assert_eq!(score, 291);
+}
+
+#[test]
+fn winning_players_score_in_recursive_combat_in_puzzle_input() {
+    let decks = parse_decks(INPUT);
+
+    let score = winning_players_score_in_recursive_combat(&decks);
+
+    assert_eq!(score.to_string(), recorded_answer(22, 2));
+}
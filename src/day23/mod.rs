@@ -0,0 +1,269 @@
+//! # Day 23: Crab Cups
+//!
+//! The small crab challenges you to a game! The crab is going to mix up some
+//! cups, and you have to predict where they'll end up.
+//!
+//! The cups will be arranged in a circle and labeled clockwise (your puzzle
+//! input). For example, if your labeling were `32415`, there would be five
+//! cups in the circle; going clockwise around the circle from the first cup,
+//! the cups would be labeled `3`, `2`, `4`, `1`, `5`, and then back to `3`
+//! again.
+//!
+//! Before the crab starts, it will designate the first cup in your list as the
+//! current cup. The crab is then going to do 100 moves.
+//!
+//! Each move, the crab does the following actions:
+//!
+//! * The crab picks up the three cups that are immediately clockwise of the
+//!   current cup. They are removed from the circle; cup spacing is adjusted as
+//!   necessary to maintain the circle.
+//! * The crab selects a destination cup: the cup with a label equal to the
+//!   current cup's label minus one. If this would select one of the cups that
+//!   was just picked up, the crab will keep subtracting one until it finds a
+//!   cup that wasn't just picked up. If at any point in this process the value
+//!   goes below the lowest value on any cup's label, it wraps around to the
+//!   highest value on any cup's label instead.
+//! * The crab places the cups it just picked up so that they are immediately
+//!   clockwise of the destination cup. They keep the same order as when they
+//!   were picked up.
+//! * The crab selects a new current cup: the cup which is immediately
+//!   clockwise of the current cup.
+//!
+//! For example, suppose your cup labeling were `389125467`. After 10 moves,
+//! the cups clockwise from cup 1 are labeled `92658374`. After the crab is
+//! done with 100 moves, the cups after cup 1 are `67384529`.
+//!
+//! Using your labeling, simulate 100 moves. What are the labels on the cups
+//! after cup 1?
+//!
+//! ## Part 2
+//!
+//! Due to what you can only assume is a mistranslation (you're not exactly
+//! fluent in Crab), you are quite surprised when the crab starts arranging
+//! many cups in a circle on your raft - one million (1000000) in total.
+//!
+//! Your labeling is still correct for the first few cups; after that, the
+//! remaining cups are just numbered in an increasing fashion starting from the
+//! number after the highest number in your list and proceeding one by one
+//! until one million is reached.
+//!
+//! Again, the crab is going to do some moves - ten million (10000000) moves!
+//!
+//! The crab is going to hide your stars - one each - under the two cups that
+//! will end up immediately clockwise of cup 1.
+//!
+//! In the above example (`389125467`), this would be `934001` and then
+//! `159792`; multiplying these together produces `149245887792`.
+//!
+//! Determine which two cups will end up immediately clockwise of cup 1. What
+//! do you get if you multiply their labels together?
+//!
+//! [Advent of Code 2020 - Day 23](https://adventofcode.com/2020/day/23)
+
+use crate::parse::{end_of, ParseError, ParseErrorKind};
+
+pub type Cup = u32;
+
+/// A circle of cups stored as a singly linked list in a flat vector.
+///
+/// The element at index `cup` holds the label of the cup immediately
+/// clockwise of `cup`. Index 0 is not used as cups are labeled starting at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CupCircle {
+    next: Vec<Cup>,
+    current: Cup,
+}
+
+impl CupCircle {
+    /// Arranges the given cups in a circle, filled up with cups labeled in
+    /// increasing order until the circle contains `num_cups` cups.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the labeling is not a permutation of 1 up to its length, if
+    /// it contains more than `num_cups` cups or if `num_cups` is less than
+    /// [`MIN_CUPS`].
+    pub fn new(labeling: &[Cup], num_cups: usize) -> Self {
+        assert!(
+            num_cups >= MIN_CUPS,
+            "circle must contain at least {} cups",
+            MIN_CUPS
+        );
+        assert!(
+            labeling.len() <= num_cups,
+            "labeling contains more than {} cups",
+            num_cups
+        );
+        let mut used = vec![false; labeling.len() + 1];
+        for cup in labeling {
+            let label = *cup as usize;
+            assert!(
+                (1..=labeling.len()).contains(&label) && !used[label],
+                "labeling is not a permutation of the cups 1 to {}",
+                labeling.len()
+            );
+            used[label] = true;
+        }
+        let cups = labeling
+            .iter()
+            .copied()
+            .chain(labeling.len() as Cup + 1..=num_cups as Cup)
+            .collect::<Vec<_>>();
+
+        let mut next = vec![0; num_cups + 1];
+        for pair in cups.windows(2) {
+            next[pair[0] as usize] = pair[1];
+        }
+        let current = cups.first().copied().unwrap_or(0);
+        if let Some(last) = cups.last() {
+            next[*last as usize] = current;
+        }
+        Self { next, current }
+    }
+
+    pub fn len(&self) -> usize {
+        self.next.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn current(&self) -> Cup {
+        self.current
+    }
+
+    /// Returns the cup immediately clockwise of the given cup.
+    pub fn next_to(&self, cup: Cup) -> Cup {
+        self.next[cup as usize]
+    }
+
+    /// Returns all other cups in clockwise order starting at the cup
+    /// immediately clockwise of the given cup.
+    pub fn cups_after(&self, cup: Cup) -> CupsAfter<'_> {
+        CupsAfter {
+            circle: self,
+            start: cup,
+            cup,
+        }
+    }
+
+    pub fn make_move(&mut self) {
+        let current = self.current;
+        let picked1 = self.next[current as usize];
+        let picked2 = self.next[picked1 as usize];
+        let picked3 = self.next[picked2 as usize];
+
+        let highest = self.len() as Cup;
+        let mut destination = current;
+        loop {
+            destination = if destination == 1 {
+                highest
+            } else {
+                destination - 1
+            };
+            if destination != picked1 && destination != picked2 && destination != picked3 {
+                break;
+            }
+        }
+
+        self.next[current as usize] = self.next[picked3 as usize];
+        self.next[picked3 as usize] = self.next[destination as usize];
+        self.next[destination as usize] = picked1;
+        self.current = self.next[current as usize];
+    }
+}
+
+#[derive(Debug)]
+pub struct CupsAfter<'a> {
+    circle: &'a CupCircle,
+    start: Cup,
+    cup: Cup,
+}
+
+impl<'a> Iterator for CupsAfter<'a> {
+    type Item = Cup;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.circle.next_to(self.cup);
+        if next == self.start {
+            None
+        } else {
+            self.cup = next;
+            Some(next)
+        }
+    }
+}
+
+/// The number of cups needed to always find a destination cup that is not
+/// picked up.
+pub const MIN_CUPS: usize = 4;
+
+/// Parses the labels of the cups, which must be a permutation of 1 up to
+/// the number of cups, with at least [`MIN_CUPS`] cups.
+pub fn try_parse_cup_labeling(input: &str) -> Result<Vec<Cup>, ParseError> {
+    let labeling = input.trim();
+    let mut labels = Vec::with_capacity(labeling.len());
+    for (index, c) in labeling.char_indices() {
+        let token = &labeling[index..index + c.len_utf8()];
+        let label = c
+            .to_digit(10)
+            .ok_or_else(|| ParseError::at(input, token, ParseErrorKind::UnexpectedCharacter(c)))?;
+        labels.push((token, label));
+    }
+    if labels.len() < MIN_CUPS {
+        return Err(ParseError::at(
+            input,
+            end_of(labeling),
+            ParseErrorKind::Missing("cups, there must be at least 4"),
+        ));
+    }
+    let mut used = vec![false; labels.len() + 1];
+    for (token, label) in &labels {
+        if *label == 0 || *label as usize > labels.len() {
+            return Err(ParseError::at(
+                input,
+                token,
+                ParseErrorKind::Invalid("cup label, it must be between 1 and the number of cups"),
+            ));
+        }
+        if used[*label as usize] {
+            return Err(ParseError::at(
+                input,
+                token,
+                ParseErrorKind::Invalid("cup label, it is already used"),
+            ));
+        }
+        used[*label as usize] = true;
+    }
+    Ok(labels.into_iter().map(|(_, label)| label).collect())
+}
+
+#[aoc_generator(day23)]
+pub fn parse_cup_labeling(input: &str) -> Vec<Cup> {
+    try_parse_cup_labeling(input).unwrap_or_else(|err| panic!("{}", err))
+}
+
+#[aoc(day23, part1)]
+pub fn labels_after_cup_1_after_100_moves(labeling: &[Cup]) -> String {
+    let mut circle = CupCircle::new(labeling, labeling.len());
+    for _ in 0..100 {
+        circle.make_move();
+    }
+    circle
+        .cups_after(1)
+        .map(|cup| cup.to_string())
+        .collect::<String>()
+}
+
+#[aoc(day23, part2)]
+pub fn product_of_cups_holding_the_stars(labeling: &[Cup]) -> u64 {
+    let mut circle = CupCircle::new(labeling, 1_000_000);
+    for _ in 0..10_000_000 {
+        circle.make_move();
+    }
+    circle.cups_after(1).take(2).map(u64::from).product()
+}
+
+#[cfg(test)]
+mod tests;
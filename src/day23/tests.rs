@@ -0,0 +1,185 @@
+use super::*;
+use crate::answers::recorded_answer;
+
+const INPUT: &str = include_str!("../../input/2020/day23.txt");
+
+const EXAMPLE: &str = "389125467";
+
+#[test]
+fn parse_cup_labeling_in_example() {
+    let labeling = parse_cup_labeling(EXAMPLE);
+
+    assert_eq!(labeling, vec![3, 8, 9, 1, 2, 5, 4, 6, 7]);
+}
+
+#[test]
+fn try_parse_cup_labeling_with_invalid_label() {
+    let error = try_parse_cup_labeling("3891a5467\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (1, 5));
+    assert_eq!(error.kind(), &ParseErrorKind::UnexpectedCharacter('a'));
+}
+
+#[test]
+fn try_parse_cup_labeling_with_a_single_cup() {
+    let error = try_parse_cup_labeling("1").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (1, 2));
+    assert_eq!(
+        error.kind(),
+        &ParseErrorKind::Missing("cups, there must be at least 4")
+    );
+}
+
+#[test]
+fn try_parse_cup_labeling_with_two_cups() {
+    let error = try_parse_cup_labeling("12").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (1, 3));
+    assert_eq!(
+        error.kind(),
+        &ParseErrorKind::Missing("cups, there must be at least 4")
+    );
+}
+
+#[test]
+fn try_parse_cup_labeling_with_label_0() {
+    let error = try_parse_cup_labeling("0123").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (1, 1));
+    assert_eq!(
+        error.kind(),
+        &ParseErrorKind::Invalid("cup label, it must be between 1 and the number of cups")
+    );
+}
+
+#[test]
+fn try_parse_cup_labeling_with_duplicate_label() {
+    let error = try_parse_cup_labeling("1123").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (1, 2));
+    assert_eq!(
+        error.kind(),
+        &ParseErrorKind::Invalid("cup label, it is already used")
+    );
+}
+
+#[test]
+fn try_parse_cup_labeling_with_gap() {
+    let error = try_parse_cup_labeling("1359").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (1, 3));
+    assert_eq!(
+        error.kind(),
+        &ParseErrorKind::Invalid("cup label, it must be between 1 and the number of cups")
+    );
+}
+
+#[test]
+fn new_cup_circle_filled_up_to_20_cups() {
+    let labeling = parse_cup_labeling(EXAMPLE);
+
+    let circle = CupCircle::new(&labeling, 20);
+
+    assert_eq!(circle.len(), 20);
+    assert_eq!(circle.current(), 3);
+    assert_eq!(
+        circle.cups_after(3).collect::<Vec<_>>(),
+        vec![8, 9, 1, 2, 5, 4, 6, 7, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20]
+    );
+}
+
+#[test]
+#[should_panic(expected = "labeling is not a permutation of the cups 1 to 2")]
+fn new_cup_circle_with_gap_in_labeling() {
+    CupCircle::new(&[1, 3], 4);
+}
+
+#[test]
+#[should_panic(expected = "labeling is not a permutation of the cups 1 to 4")]
+fn new_cup_circle_with_duplicate_label() {
+    CupCircle::new(&[1, 2, 2, 4], 4);
+}
+
+#[test]
+#[should_panic(expected = "labeling is not a permutation of the cups 1 to 4")]
+fn new_cup_circle_with_label_0() {
+    CupCircle::new(&[0, 1, 2, 3], 4);
+}
+
+#[test]
+#[should_panic(expected = "circle must contain at least 4 cups")]
+fn new_cup_circle_without_cups() {
+    CupCircle::new(&[], 0);
+}
+
+#[test]
+#[should_panic(expected = "circle must contain at least 4 cups")]
+fn new_cup_circle_with_3_cups() {
+    CupCircle::new(&[1, 3, 2], 3);
+}
+
+#[test]
+fn make_first_move_in_example() {
+    let labeling = parse_cup_labeling(EXAMPLE);
+    let mut circle = CupCircle::new(&labeling, labeling.len());
+
+    circle.make_move();
+
+    assert_eq!(circle.current(), 2);
+    assert_eq!(
+        circle.cups_after(3).collect::<Vec<_>>(),
+        vec![2, 8, 9, 1, 5, 4, 6, 7]
+    );
+}
+
+#[test]
+fn labels_after_cup_1_after_10_moves_in_example() {
+    let labeling = parse_cup_labeling(EXAMPLE);
+    let mut circle = CupCircle::new(&labeling, labeling.len());
+
+    for _ in 0..10 {
+        circle.make_move();
+    }
+
+    assert_eq!(
+        circle.cups_after(1).collect::<Vec<_>>(),
+        vec![9, 2, 6, 5, 8, 3, 7, 4]
+    );
+}
+
+#[test]
+fn labels_after_cup_1_after_100_moves_in_example() {
+    let labeling = parse_cup_labeling(EXAMPLE);
+
+    let labels = labels_after_cup_1_after_100_moves(&labeling);
+
+    assert_eq!(labels, "67384529");
+}
+
+#[test]
+fn labels_after_cup_1_after_100_moves_in_puzzle_input() {
+    let labeling = parse_cup_labeling(INPUT);
+
+    let labels = labels_after_cup_1_after_100_moves(&labeling);
+
+    assert_eq!(labels, recorded_answer(23, 1));
+}
+
+#[test]
+fn product_of_cups_holding_the_stars_in_example() {
+    let labeling = parse_cup_labeling(EXAMPLE);
+
+    let product = product_of_cups_holding_the_stars(&labeling);
+
+    assert_eq!(product, 149245887792);
+}
+
+#[test]
+fn product_of_cups_holding_the_stars_in_puzzle_input() {
+    let labeling = parse_cup_labeling(INPUT);
+
+    let product = product_of_cups_holding_the_stars(&labeling);
+
+    assert_eq!(product.to_string(), recorded_answer(23, 2));
+}
@@ -0,0 +1,312 @@
+//! # Day 24: Lobby Layout
+//!
+//! Your raft makes it to the tropical island; it turns out that the small
+//! crab was an excellent navigator. You make your way to the resort.
+//!
+//! As you enter the lobby, you discover a small problem: the floor is being
+//! renovated. You can't even reach the check-in desk until they've finished
+//! installing the new tile floor.
+//!
+//! The tiles are all hexagonal; they need to be arranged in a hex grid with a
+//! very specific color pattern. Not in the mood to wait, you offer to help
+//! figure out the pattern.
+//!
+//! The tiles are all white on one side and black on the other. They start
+//! with the white side facing up. The lobby is large enough to fit whatever
+//! pattern might need to appear there.
+//!
+//! A member of the renovation crew gives you a list of the tiles that need to
+//! be flipped over (your puzzle input). Each line in the list identifies a
+//! single tile that needs to be flipped by giving a series of steps starting
+//! from a reference tile in the very center of the room. (Every line starts
+//! from the same reference tile.)
+//!
+//! Because the tiles are hexagonal, every tile has six neighbors: east,
+//! southeast, southwest, west, northwest, and northeast. These directions are
+//! given in your list, respectively, as `e`, `se`, `sw`, `w`, `nw`, and `ne`.
+//! A tile is identified by a series of these directions with no delimiters;
+//! for example, `esenee` identifies the tile you land on if you start at the
+//! reference tile and then move one tile east, one tile southeast, one tile
+//! northeast, and one tile east.
+//!
+//! Each time a tile is identified, it flips from white to black or from black
+//! to white. Tiles might be flipped more than once. For example, a line like
+//! `esew` flips a tile immediately adjacent to the reference tile, and a line
+//! like `nwwswee` flips the reference tile itself.
+//!
+//! In a larger example with 20 lines of directions, 10 tiles are black after
+//! all of the instructions have been followed.
+//!
+//! Go through the renovation crew's list and determine which tiles they need
+//! to flip. After all of the instructions have been followed, how many tiles
+//! are left with the black side up?
+//!
+//! ## Part 2
+//!
+//! The tile floor in the lobby is meant to be a living art exhibit. Every day,
+//! the tiles are all flipped according to the following rules:
+//!
+//! * Any black tile with zero or more than 2 black tiles immediately adjacent
+//!   to it is flipped to white.
+//! * Any white tile with exactly 2 black tiles immediately adjacent to it is
+//!   flipped to black.
+//!
+//! Here, tiles immediately adjacent means the six tiles directly touching the
+//! tile in question.
+//!
+//! The rules are applied simultaneously to every tile; put another way, it is
+//! first determined which tiles need to be flipped, then they are all flipped
+//! at the same time.
+//!
+//! In the above example, the number of black tiles is 15 after day 1, 12
+//! after day 2 and 2208 after day 100.
+//!
+//! How many tiles will be black after 100 days?
+//!
+//! [Advent of Code 2020 - Day 24](https://adventofcode.com/2020/day/24)
+
+use crate::automaton::{HexLattice, LifeLike, LiveCells, Topology};
+use crate::parse::{end_of, ParseError, ParseErrorKind};
+use std::mem;
+use std::ops::Add;
+use std::str::Chars;
+
+pub type Coord = i32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HexDirection {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+pub const HEX_DIRECTIONS: [HexDirection; 6] = [
+    HexDirection::East,
+    HexDirection::SouthEast,
+    HexDirection::SouthWest,
+    HexDirection::West,
+    HexDirection::NorthWest,
+    HexDirection::NorthEast,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseHexDirectionError {
+    UnexpectedCharacter(char),
+    UnexpectedEndOfInput,
+}
+
+impl HexDirection {
+    /// Splits a series of directions without delimiters into the single
+    /// directions.
+    pub fn tokenize(s: &str) -> Tokens<'_> {
+        Tokens { chars: s.chars() }
+    }
+}
+
+#[derive(Debug)]
+pub struct Tokens<'a> {
+    chars: Chars<'a>,
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Result<HexDirection, ParseHexDirectionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let direction = match self.chars.next()? {
+            'e' => Ok(HexDirection::East),
+            'w' => Ok(HexDirection::West),
+            c @ 's' | c @ 'n' => match (c, self.chars.next()) {
+                ('s', Some('e')) => Ok(HexDirection::SouthEast),
+                ('s', Some('w')) => Ok(HexDirection::SouthWest),
+                ('n', Some('w')) => Ok(HexDirection::NorthWest),
+                ('n', Some('e')) => Ok(HexDirection::NorthEast),
+                (_, Some(other)) => Err(ParseHexDirectionError::UnexpectedCharacter(other)),
+                (_, None) => Err(ParseHexDirectionError::UnexpectedEndOfInput),
+            },
+            other => Err(ParseHexDirectionError::UnexpectedCharacter(other)),
+        };
+        Some(direction)
+    }
+}
+
+/// Position of a tile in a hex grid given in axial coordinates.
+///
+/// The `q` axis points east and the `r` axis points southeast. The third
+/// cube coordinate is implied by the constraint `q + r + s = 0`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HexPosition {
+    pub q: Coord,
+    pub r: Coord,
+}
+
+impl HexPosition {
+    pub fn new(q: Coord, r: Coord) -> Self {
+        Self { q, r }
+    }
+
+    pub fn s(self) -> Coord {
+        -self.q - self.r
+    }
+
+    /// Returns the axial coordinates `[q, r]` of this position.
+    pub fn axial(self) -> [Coord; 2] {
+        [self.q, self.r]
+    }
+
+    /// Returns the cube coordinates `[q, r, s]` of this position.
+    pub fn cube(self) -> [Coord; 3] {
+        [self.q, self.r, self.s()]
+    }
+
+    /// Returns the number of steps it takes to walk from this position to the
+    /// other position.
+    pub fn distance(self, other: Self) -> Coord {
+        ((self.q - other.q).abs() + (self.r - other.r).abs() + (self.s() - other.s()).abs()) / 2
+    }
+
+    pub fn neighbors(self) -> impl Iterator<Item = HexPosition> {
+        HEX_DIRECTIONS
+            .iter()
+            .map(move |direction| self + *direction)
+    }
+}
+
+impl Add<HexDirection> for HexPosition {
+    type Output = Self;
+
+    fn add(self, direction: HexDirection) -> Self::Output {
+        let (dq, dr) = match direction {
+            HexDirection::East => (1, 0),
+            HexDirection::SouthEast => (0, 1),
+            HexDirection::SouthWest => (-1, 1),
+            HexDirection::West => (-1, 0),
+            HexDirection::NorthWest => (0, -1),
+            HexDirection::NorthEast => (1, -1),
+        };
+        Self {
+            q: self.q + dq,
+            r: self.r + dr,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    White,
+    Black,
+}
+
+/// A white tile with exactly 2 adjacent black tiles is flipped to black, a
+/// black tile with zero or more than 2 adjacent black tiles is flipped to
+/// white.
+const LOBBY_LAYOUT: LifeLike = LifeLike::new(&[2], &[1, 2]);
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Floor {
+    black_tiles: LiveCells<[Coord; 2]>,
+    next_gen: LiveCells<[Coord; 2]>,
+}
+
+impl Floor {
+    /// Lays out the floor by flipping the tile identified by each of the
+    /// given paths, starting at the reference tile.
+    pub fn lay_out(tile_paths: &[Vec<HexDirection>]) -> Self {
+        let mut floor = Self::default();
+        for path in tile_paths {
+            let position = path
+                .iter()
+                .fold(HexPosition::default(), |position, direction| {
+                    position + *direction
+                });
+            floor.flip(position);
+        }
+        floor
+    }
+
+    pub fn get(&self, position: HexPosition) -> Color {
+        if self.black_tiles.contains(&position.axial()) {
+            Color::Black
+        } else {
+            Color::White
+        }
+    }
+
+    pub fn flip(&mut self, position: HexPosition) {
+        if !self.black_tiles.remove(&position.axial()) {
+            self.black_tiles.insert(position.axial());
+        }
+    }
+
+    pub fn num_black(&self) -> usize {
+        self.black_tiles.len()
+    }
+
+    pub fn neighbors(&self, position: HexPosition) -> impl Iterator<Item = Color> + '_ {
+        position.neighbors().map(move |neighbor| self.get(neighbor))
+    }
+
+    pub fn evolve(&mut self) {
+        HexLattice.next_generation(&LOBBY_LAYOUT, &self.black_tiles, &mut self.next_gen);
+        mem::swap(&mut self.black_tiles, &mut self.next_gen);
+    }
+}
+
+pub fn try_parse_tile_paths(input: &str) -> Result<Vec<Vec<HexDirection>>, ParseError> {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let mut directions = Vec::new();
+            let mut tokens = HexDirection::tokenize(line);
+            loop {
+                let remaining = tokens.chars.as_str();
+                match tokens.next() {
+                    None => break,
+                    Some(Ok(direction)) => directions.push(direction),
+                    Some(Err(ParseHexDirectionError::UnexpectedCharacter(c))) => {
+                        let consumed = remaining.len() - tokens.chars.as_str().len();
+                        return Err(ParseError::at(
+                            input,
+                            &remaining[consumed - c.len_utf8()..consumed],
+                            ParseErrorKind::UnexpectedCharacter(c),
+                        ));
+                    }
+                    Some(Err(ParseHexDirectionError::UnexpectedEndOfInput)) => {
+                        return Err(ParseError::at(
+                            input,
+                            end_of(line),
+                            ParseErrorKind::Missing("second character of direction"),
+                        ));
+                    }
+                }
+            }
+            Ok(directions)
+        })
+        .collect()
+}
+
+#[aoc_generator(day24)]
+pub fn parse_tile_paths(input: &str) -> Vec<Vec<HexDirection>> {
+    try_parse_tile_paths(input).unwrap_or_else(|err| panic!("{}", err))
+}
+
+#[aoc(day24, part1)]
+pub fn number_of_black_tiles(tile_paths: &[Vec<HexDirection>]) -> usize {
+    Floor::lay_out(tile_paths).num_black()
+}
+
+#[aoc(day24, part2)]
+pub fn number_of_black_tiles_after_100_days(tile_paths: &[Vec<HexDirection>]) -> usize {
+    let mut floor = Floor::lay_out(tile_paths);
+
+    (0..100).for_each(|_| floor.evolve());
+
+    floor.num_black()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,168 @@
+use super::*;
+use crate::answers::recorded_answer;
+
+const INPUT: &str = include_str!("../../input/2020/day24.txt");
+
+const EXAMPLE: &str = "\
+sesenwnenenewseeswwswswwnenewsewsw
+neeenesenwnwwswnenewnwwsewnenwseswesw
+seswneswswsenwwnwse
+nwnwneseeswswnenewneswwnewseswneseene
+swweswneswnenwsewnwneneseenw
+eesenwseswswnenwswnwnwsewwnwsene
+sewnenenenesenwsewnenwwwse
+wenwwweseeeweswwwnwwe
+wsweesenenewnwwnwsenewsenwwsesesenwne
+neeswseenwwswnwswswnw
+nenwswwsewswnenenewsenwsenwnesesenew
+enewnwewneswsewnwswenweswnenwsenwsw
+sweneswneswneneenwnewenewwneswswnese
+swwesenesewenwneswnwwneseswwne
+enesenwswwswneneswsenwnewswseenwsese
+wnwnesenesenenwwnenwsewesewsesesew
+nenewswnwewswnenesenwnesewesw
+eneswnwswnwsenenwnwnwwseeswneewsenese
+neswnwewnwnwseenwseesewsenwsweewe
+wseweeenwnesenwwwswnew
+";
+
+#[test]
+fn tokenize_hex_directions() {
+    let directions = HexDirection::tokenize("esenee").collect::<Result<Vec<_>, _>>();
+
+    assert_eq!(
+        directions,
+        Ok(vec![
+            HexDirection::East,
+            HexDirection::SouthEast,
+            HexDirection::NorthEast,
+            HexDirection::East
+        ])
+    );
+}
+
+#[test]
+fn tokenize_invalid_hex_directions() {
+    let directions = HexDirection::tokenize("esx").collect::<Vec<_>>();
+
+    assert_eq!(
+        directions,
+        vec![
+            Ok(HexDirection::East),
+            Err(ParseHexDirectionError::UnexpectedCharacter('x'))
+        ]
+    );
+
+    let directions = HexDirection::tokenize("ewn").collect::<Vec<_>>();
+
+    assert_eq!(
+        directions,
+        vec![
+            Ok(HexDirection::East),
+            Ok(HexDirection::West),
+            Err(ParseHexDirectionError::UnexpectedEndOfInput)
+        ]
+    );
+}
+
+#[test]
+fn walking_nwwswee_ends_at_the_reference_tile() {
+    let position = HexDirection::tokenize("nwwswee")
+        .map(Result::unwrap)
+        .fold(HexPosition::default(), |position, direction| {
+            position + direction
+        });
+
+    assert_eq!(position, HexPosition::default());
+}
+
+#[test]
+fn walking_esew_ends_next_to_the_reference_tile() {
+    let position = HexDirection::tokenize("esew")
+        .map(Result::unwrap)
+        .fold(HexPosition::default(), |position, direction| {
+            position + direction
+        });
+
+    assert_eq!(position, HexPosition::new(0, 1));
+    assert_eq!(position.distance(HexPosition::default()), 1);
+    assert_eq!(position.cube(), [0, 1, -1]);
+}
+
+#[test]
+fn parse_tile_paths_in_example() {
+    let tile_paths = parse_tile_paths(EXAMPLE);
+
+    assert_eq!(tile_paths.len(), 20);
+    assert_eq!(tile_paths[19].len(), 15);
+}
+
+#[test]
+fn try_parse_tile_paths_with_invalid_direction() {
+    let error = try_parse_tile_paths("esew\nnwwswee\nneswx\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (3, 5));
+    assert_eq!(error.kind(), &ParseErrorKind::UnexpectedCharacter('x'));
+}
+
+#[test]
+fn try_parse_tile_paths_with_incomplete_direction() {
+    let error = try_parse_tile_paths("esew\nnwwswes\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (2, 8));
+    assert_eq!(
+        error.kind(),
+        &ParseErrorKind::Missing("second character of direction")
+    );
+}
+
+#[test]
+fn number_of_black_tiles_in_example() {
+    let tile_paths = parse_tile_paths(EXAMPLE);
+
+    let num_black = number_of_black_tiles(&tile_paths);
+
+    assert_eq!(num_black, 10);
+}
+
+#[test]
+fn number_of_black_tiles_in_puzzle_input() {
+    let tile_paths = parse_tile_paths(INPUT);
+
+    let num_black = number_of_black_tiles(&tile_paths);
+
+    assert_eq!(num_black.to_string(), recorded_answer(24, 1));
+}
+
+#[test]
+fn number_of_black_tiles_after_some_days_in_example() {
+    let tile_paths = parse_tile_paths(EXAMPLE);
+    let mut floor = Floor::lay_out(&tile_paths);
+
+    let num_black = (1..=10)
+        .map(|_| {
+            floor.evolve();
+            floor.num_black()
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(num_black, vec![15, 12, 25, 14, 23, 28, 41, 37, 49, 37]);
+}
+
+#[test]
+fn number_of_black_tiles_after_100_days_in_example() {
+    let tile_paths = parse_tile_paths(EXAMPLE);
+
+    let num_black = number_of_black_tiles_after_100_days(&tile_paths);
+
+    assert_eq!(num_black, 2208);
+}
+
+#[test]
+fn number_of_black_tiles_after_100_days_in_puzzle_input() {
+    let tile_paths = parse_tile_paths(INPUT);
+
+    let num_black = number_of_black_tiles_after_100_days(&tile_paths);
+
+    assert_eq!(num_black.to_string(), recorded_answer(24, 2));
+}
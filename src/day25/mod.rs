@@ -0,0 +1,222 @@
+//! # Day 25: Combo Breaker
+//!
+//! You finally reach the check-in desk. Unfortunately, their registration
+//! systems are currently offline, and they cannot check you in. Noticing the
+//! look on your face, they quickly add that tech support is already on the
+//! way! They even created all the room keys this morning; you can take yours
+//! now and give them your room deposit once the registration system comes back
+//! online.
+//!
+//! The room key is a small RFID card. Your room is on the 25th floor and the
+//! elevators are also temporarily out of service, so it takes what little
+//! energy you have left to even climb the stairs and navigate the halls. You
+//! finally reach the door to your room, swipe your card, and - beep - the
+//! light turns red.
+//!
+//! Examining the card more closely, you discover a phone number for tech
+//! support.
+//!
+//! "Hello! How can we help you today?" You explain the situation.
+//!
+//! "Well, it sounds like the card isn't sending the right command to unlock
+//! the door. If you go back to the check-in desk, surely someone there can
+//! reset it for you." Still catching your breath, you describe the status of
+//! the elevator and the exact number of stairs you just had to climb.
+//!
+//! "I see! Well, your only other option would be to reverse-engineer the
+//! cryptographic handshake the card does with the door and then inject your
+//! own commands into the data stream, but that's definitely impossible." You
+//! thank them for their time.
+//!
+//! The handshake used by the card and the door involves an operation that
+//! transforms a subject number. To transform a subject number, start with the
+//! value 1. Then, a number of times called the loop size, perform the
+//! following steps:
+//!
+//! * Set the value to itself multiplied by the subject number.
+//! * Set the value to the remainder after dividing the value by `20201227`.
+//!
+//! The card always uses a specific, secret loop size when it transforms a
+//! subject number. The door always uses a different, secret loop size.
+//!
+//! The cryptographic handshake works like this:
+//!
+//! * The card transforms the subject number of 7 according to the card's
+//!   secret loop size. The result is called the card's public key.
+//! * The door transforms the subject number of 7 according to the door's
+//!   secret loop size. The result is called the door's public key.
+//! * The card and door use the wireless RFID signal to transmit the two public
+//!   keys (your puzzle input) to the other device. Now, the card has the
+//!   door's public key, and the door has the card's public key. Because you
+//!   can eavesdrop on the signal, you have both public keys, but neither
+//!   device's loop size.
+//! * The card transforms the subject number of the door's public key according
+//!   to the card's loop size. The result is the encryption key.
+//! * The door transforms the subject number of the card's public key according
+//!   to the door's loop size. The result is the same encryption key as the
+//!   card calculated.
+//!
+//! If you can use the two public keys to determine each device's loop size,
+//! you will have enough information to calculate the secret encryption key
+//! that the card and door use to communicate; this would let you send the
+//! unlock command directly to the door!
+//!
+//! For example, suppose you know that the card's public key is `5764801`. With
+//! a little trial and error, you can work out that the card's loop size must
+//! be 8, because transforming the initial subject number of 7 with a loop size
+//! of 8 produces `5764801`.
+//!
+//! Then, suppose you know that the door's public key is `17807724`. By the
+//! same process, you can determine that the door's loop size is 11, because
+//! transforming the initial subject number of 7 with a loop size of 11
+//! produces `17807724`.
+//!
+//! At this point, you can use either device's loop size with the other
+//! device's public key to calculate the encryption key. Transforming the
+//! subject number of `17807724` (the door's public key) with a loop size of 8
+//! (the card's loop size) produces the encryption key, `14897079`.
+//! (Transforming the subject number of `5764801` (the card's public key) with
+//! a loop size of 11 (the door's loop size) produces the same encryption key:
+//! `14897079`.)
+//!
+//! What encryption key is the handshake trying to establish?
+//!
+//! [Advent of Code 2020 - Day 25](https://adventofcode.com/2020/day/25)
+
+use crate::parse::{end_of, parse_number, ParseError, ParseErrorKind};
+use fxhash::FxBuildHasher;
+use hashbrown::HashMap;
+
+pub type Key = u64;
+
+pub const MODULUS: u64 = 20_201_227;
+
+pub const INITIAL_SUBJECT_NUMBER: u64 = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKeys {
+    pub card: Key,
+    pub door: Key,
+}
+
+/// Calculates `a * b % modulus` without overflowing for any modulus.
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    (u128::from(a) * u128::from(b) % u128::from(modulus)) as u64
+}
+
+/// Calculates `base ^ exponent % modulus` by repeated squaring.
+///
+/// # Panics
+///
+/// Panics if the modulus is 0.
+pub fn mod_pow(base: u64, exponent: u64, modulus: u64) -> u64 {
+    assert!(modulus > 0, "modulus must not be 0");
+    if modulus == 1 {
+        return 0;
+    }
+    let mut result = 1;
+    let mut base = base % modulus;
+    let mut exponent = exponent;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Finds the smallest exponent `x` such that `base ^ x % modulus == value`
+/// using the baby-step giant-step algorithm.
+///
+/// The modulus must be a prime number. Returns `None` if there is no such
+/// exponent.
+///
+/// # Panics
+///
+/// Panics if the modulus is 0.
+pub fn discrete_log(base: u64, value: u64, modulus: u64) -> Option<u64> {
+    assert!(modulus > 0, "modulus must not be 0");
+    let value = value % modulus;
+    let steps = (1..)
+        .find(|m| u128::from(*m) * u128::from(*m) >= u128::from(modulus))
+        .unwrap_or(1);
+
+    let mut baby_steps =
+        HashMap::with_capacity_and_hasher(steps as usize, FxBuildHasher::default());
+    let mut power = 1 % modulus;
+    for j in 0..steps {
+        baby_steps.entry(power).or_insert(j);
+        power = mul_mod(power, base, modulus);
+    }
+
+    // base ^ -steps as the inverse of base ^ steps by Fermat's little theorem
+    let giant_step = mod_pow(
+        mod_pow(base, steps, modulus),
+        modulus.saturating_sub(2),
+        modulus,
+    );
+    let mut gamma = value;
+    for i in 0..steps {
+        if let Some(j) = baby_steps.get(&gamma) {
+            return Some(i * steps + j);
+        }
+        gamma = mul_mod(gamma, giant_step, modulus);
+    }
+    None
+}
+
+/// Transforms the subject number according to the loop size.
+pub fn transform(subject_number: u64, loop_size: u64) -> Key {
+    mod_pow(subject_number, loop_size, MODULUS)
+}
+
+/// Determines the secret loop size from the public key.
+pub fn loop_size(public_key: Key) -> Option<u64> {
+    discrete_log(INITIAL_SUBJECT_NUMBER, public_key, MODULUS)
+}
+
+/// Parses the public keys of the card and the door, each of which must be a
+/// remainder of the [`MODULUS`] other than 0.
+pub fn try_parse_public_keys(input: &str) -> Result<PublicKeys, ParseError> {
+    let mut tokens = input.split_whitespace();
+    let mut next_key = |device| {
+        let token = tokens
+            .next()
+            .ok_or_else(|| ParseError::at(input, end_of(input), ParseErrorKind::Missing(device)))?;
+        let key: Key = parse_number(input, token)?;
+        if key == 0 || key >= MODULUS {
+            return Err(ParseError::at(
+                input,
+                token,
+                ParseErrorKind::Invalid("public key, it must be between 1 and the modulus"),
+            ));
+        }
+        Ok(key)
+    };
+    let card = next_key("public key of the card")?;
+    let door = next_key("public key of the door")?;
+    if let Some(extra) = tokens.next() {
+        return Err(ParseError::at(
+            input,
+            extra,
+            ParseErrorKind::UnexpectedCharacter(extra.chars().next().unwrap_or_default()),
+        ));
+    }
+    Ok(PublicKeys { card, door })
+}
+
+#[aoc_generator(day25)]
+pub fn parse_public_keys(input: &str) -> PublicKeys {
+    try_parse_public_keys(input).unwrap_or_else(|err| panic!("{}", err))
+}
+
+#[aoc(day25, part1)]
+pub fn encryption_key(public_keys: &PublicKeys) -> Key {
+    let card_loop_size = loop_size(public_keys.card).expect("no loop size of the card found!");
+    transform(public_keys.door, card_loop_size)
+}
+
+#[cfg(test)]
+mod tests;
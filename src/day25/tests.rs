@@ -0,0 +1,145 @@
+use super::*;
+use crate::answers::recorded_answer;
+use proptest::prelude::*;
+
+const INPUT: &str = include_str!("../../input/2020/day25.txt");
+
+const EXAMPLE: &str = "\
+5764801
+17807724
+";
+
+#[test]
+fn parse_public_keys_in_example() {
+    let public_keys = parse_public_keys(EXAMPLE);
+
+    assert_eq!(
+        public_keys,
+        PublicKeys {
+            card: 5764801,
+            door: 17807724
+        }
+    );
+}
+
+#[test]
+fn try_parse_public_keys_with_missing_door_key() {
+    let error = try_parse_public_keys("5764801\n").unwrap_err();
+
+    assert_eq!(
+        error.kind(),
+        &ParseErrorKind::Missing("public key of the door")
+    );
+}
+
+#[test]
+fn try_parse_public_keys_with_key_not_below_the_modulus() {
+    let error = try_parse_public_keys("5764801\n20201227\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (2, 1));
+    assert_eq!(
+        error.kind(),
+        &ParseErrorKind::Invalid("public key, it must be between 1 and the modulus")
+    );
+}
+
+#[test]
+fn try_parse_public_keys_with_key_0() {
+    let error = try_parse_public_keys("0\n17807724\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (1, 1));
+}
+
+#[test]
+fn try_parse_public_keys_with_extra_key() {
+    let error = try_parse_public_keys("5764801\n17807724\n42\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (3, 1));
+    assert_eq!(error.kind(), &ParseErrorKind::UnexpectedCharacter('4'));
+}
+
+#[test]
+fn mod_pow_of_small_numbers() {
+    assert_eq!(mod_pow(7, 8, MODULUS), 5764801);
+    assert_eq!(mod_pow(2, 10, 1000), 24);
+    assert_eq!(mod_pow(5, 0, 13), 1);
+    assert_eq!(mod_pow(5, 3, 1), 0);
+}
+
+/// The largest prime below 2^64.
+const LARGE_PRIME: u64 = 18_446_744_073_709_551_557;
+
+#[test]
+fn mod_pow_with_modulus_above_2_pow_32() {
+    assert_eq!(mod_pow(2, 64, u64::MAX), 1);
+    assert_eq!(mod_pow(3, LARGE_PRIME - 1, LARGE_PRIME), 1);
+    assert_eq!(mod_pow(u64::MAX, 2, LARGE_PRIME), 3364);
+}
+
+#[test]
+#[should_panic(expected = "modulus must not be 0")]
+fn mod_pow_with_modulus_0() {
+    mod_pow(2, 3, 0);
+}
+
+#[test]
+fn discrete_log_with_modulus_above_2_pow_32() {
+    // the smallest prime above 2^32
+    let modulus = 4_294_967_311;
+    let value = mod_pow(5, 3_000_000_017, modulus);
+
+    let exponent = discrete_log(5, value, modulus).unwrap();
+
+    assert_eq!(mod_pow(5, exponent, modulus), value);
+}
+
+#[test]
+#[should_panic(expected = "modulus must not be 0")]
+fn discrete_log_with_modulus_0() {
+    discrete_log(2, 3, 0);
+}
+
+#[test]
+fn loop_size_of_card_in_example() {
+    assert_eq!(loop_size(5764801), Some(8));
+}
+
+#[test]
+fn loop_size_of_door_in_example() {
+    assert_eq!(loop_size(17807724), Some(11));
+}
+
+#[test]
+fn discrete_log_without_solution() {
+    // 2 is a quadratic residue modulo 7, so its powers never give 3
+    assert_eq!(discrete_log(2, 3, 7), None);
+}
+
+proptest! {
+    #[test]
+    fn discrete_log_reverses_transform(
+        loop_size in 0..MODULUS - 1
+    ) {
+        let public_key = transform(INITIAL_SUBJECT_NUMBER, loop_size);
+
+        prop_assert_eq!(discrete_log(INITIAL_SUBJECT_NUMBER, public_key, MODULUS), Some(loop_size));
+    }
+}
+
+#[test]
+fn encryption_key_in_example() {
+    let public_keys = parse_public_keys(EXAMPLE);
+
+    let key = encryption_key(&public_keys);
+
+    assert_eq!(key, 14897079);
+}
+
+#[test]
+fn encryption_key_in_puzzle_input() {
+    let public_keys = parse_public_keys(INPUT);
+
+    let key = encryption_key(&public_keys);
+
+    assert_eq!(key.to_string(), recorded_answer(25, 1));
+}
@@ -0,0 +1,537 @@
+//! # Two-dimensional grids
+//!
+//! Several puzzles are played on a rectangular map of cells, like the trees on
+//! the toboggan slope (day 3), the seat layout of the waiting area (day 11),
+//! the initial slice of the pocket dimension (day 17) or the pixels of the
+//! satellite image (day 20). `Grid<T>` stores such a map row by row in one
+//! flat vector.
+//!
+//! A grid can be parsed from a char map, where each character is converted
+//! into a cell via `TryFrom<char>`:
+//!
+//! ```
+//! use advent_of_code_2020::grid::{Grid, Position};
+//! use std::convert::TryFrom;
+//!
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+//! enum Cell {
+//!     Open,
+//!     Wall,
+//! }
+//!
+//! impl TryFrom<char> for Cell {
+//!     type Error = char;
+//!
+//!     fn try_from(value: char) -> Result<Self, Self::Error> {
+//!         match value {
+//!             '.' => Ok(Cell::Open),
+//!             '#' => Ok(Cell::Wall),
+//!             _ => Err(value),
+//!         }
+//!     }
+//! }
+//!
+//! let grid = "..#\n#..\n".parse::<Grid<Cell>>().unwrap();
+//!
+//! assert_eq!((grid.width(), grid.height()), (3, 2));
+//! assert_eq!(grid[Position::new(1, 0)], Cell::Wall);
+//! ```
+
+use crate::parse::{ParseError, ParseErrorKind};
+use std::convert::TryFrom;
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+use std::{mem, slice};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Position {
+    pub const fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// All eight directions clockwise, starting with north.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// The four directions along rows and columns clockwise, starting with
+    /// north.
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// Returns the change of the row and of the column when moving one step
+    /// into this direction.
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::North => (-1, 0),
+            Direction::NorthEast => (-1, 1),
+            Direction::East => (0, 1),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (1, 0),
+            Direction::SouthWest => (1, -1),
+            Direction::West => (0, -1),
+            Direction::NorthWest => (-1, -1),
+        }
+    }
+}
+
+/// Rotation clockwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Angle {
+    D90,
+    D180,
+    D270,
+}
+
+/// Horizontal flips swap the left and the right side, vertical flips swap
+/// the top and the bottom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid with every cell set to the given value.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Creates a grid from its cells given row by row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of cells is not `width * height`.
+    pub fn from_cells(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "number of cells does not match a {}x{} grid",
+            width,
+            height
+        );
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Creates a grid from a sequence of rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows differ in length.
+    pub fn from_rows<R>(rows: impl IntoIterator<Item = R>) -> Self
+    where
+        R: IntoIterator<Item = T>,
+    {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for row in rows {
+            let len_before = cells.len();
+            cells.extend(row);
+            let row_len = cells.len() - len_before;
+            assert_eq!(
+                *width.get_or_insert(row_len),
+                row_len,
+                "row {} differs in length",
+                height
+            );
+            height += 1;
+        }
+        Self {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        position.row < self.height && position.col < self.width
+    }
+
+    fn index_of(&self, position: Position) -> usize {
+        position.row * self.width + position.col
+    }
+
+    /// Returns the cell at the given position or `None` if the position is
+    /// outside of the grid.
+    pub fn get(&self, position: Position) -> Option<&T> {
+        if self.contains(position) {
+            Some(&self.cells[self.index_of(position)])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if self.contains(position) {
+            let index = self.index_of(position);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the cell at the given position as if the grid was repeated
+    /// infinitely in both dimensions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the grid is empty.
+    pub fn get_wrapping(&self, position: Position) -> &T {
+        &self[Position::new(position.row % self.height, position.col % self.width)]
+    }
+
+    /// Returns the position one step from the given position into the given
+    /// direction or `None` if that position is outside of the grid.
+    pub fn step(&self, position: Position, direction: Direction) -> Option<Position> {
+        let (d_row, d_col) = direction.offset();
+        let next = Position::new(
+            position.row.checked_add_signed(d_row)?,
+            position.col.checked_add_signed(d_col)?,
+        );
+        Some(next).filter(|next| self.contains(*next))
+    }
+
+    /// Returns the cells adjacent to the given position in all eight
+    /// directions, including the diagonals.
+    pub fn neighbors(&self, position: Position) -> Neighbors<'_, T> {
+        Neighbors {
+            grid: self,
+            position,
+            directions: Direction::ALL.iter(),
+        }
+    }
+
+    /// Returns the cells adjacent to the given position in the four
+    /// directions along its row and column.
+    pub fn orthogonal_neighbors(&self, position: Position) -> Neighbors<'_, T> {
+        Neighbors {
+            grid: self,
+            position,
+            directions: Direction::ORTHOGONAL.iter(),
+        }
+    }
+
+    /// Returns the cells seen from the given position looking into the given
+    /// direction, starting with the nearest one and ending at the border of
+    /// the grid.
+    pub fn ray(&self, position: Position, direction: Direction) -> Ray<'_, T> {
+        Ray {
+            grid: self,
+            position,
+            direction,
+        }
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(col < self.width, "column {} is outside of the grid", col);
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Iterates over all cells row by row.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    /// Iterates over all positions row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| Position::new(row, col)))
+    }
+
+    /// Sets every cell to the given value.
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.cells.fill(value);
+    }
+
+    /// Returns the number of cells equal to the given value.
+    pub fn count(&self, value: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        self.cells.iter().filter(|cell| *cell == value).count()
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    pub fn map_with_position<U>(&self, mut f: impl FnMut(Position, &T) -> U) -> Grid<U> {
+        Grid {
+            cells: self
+                .positions()
+                .zip(&self.cells)
+                .map(|(position, cell)| f(position, cell))
+                .collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    pub fn rotate(&mut self, angle: Angle)
+    where
+        T: Clone,
+    {
+        let (height, width) = (self.height, self.width);
+        let rotated = match angle {
+            Angle::D90 => self.transposed_cells(|row, col| (height - 1 - col, row)),
+            Angle::D180 => self.cells.iter().rev().cloned().collect(),
+            Angle::D270 => self.transposed_cells(|row, col| (col, width - 1 - row)),
+        };
+        if angle != Angle::D180 {
+            mem::swap(&mut self.width, &mut self.height);
+        }
+        self.cells = rotated;
+    }
+
+    /// Collects the cells of the grid with swapped dimensions, where `source`
+    /// returns the position in this grid of each position in the new grid.
+    fn transposed_cells(&self, source: impl Fn(usize, usize) -> (usize, usize)) -> Vec<T>
+    where
+        T: Clone,
+    {
+        (0..self.width)
+            .flat_map(|row| (0..self.height).map(move |col| (row, col)))
+            .map(|(row, col)| {
+                let (src_row, src_col) = source(row, col);
+                self.cells[src_row * self.width + src_col].clone()
+            })
+            .collect()
+    }
+
+    pub fn flip(&mut self, orientation: Orientation) {
+        match orientation {
+            Orientation::Horizontal => {
+                for row in self.cells.chunks_mut(self.width.max(1)) {
+                    row.reverse();
+                }
+            }
+            Orientation::Vertical => {
+                let (width, height) = (self.width, self.height);
+                for row in 0..height / 2 {
+                    for col in 0..width {
+                        self.cells
+                            .swap(row * width + col, (height - 1 - row) * width + col);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Rotates the grid and flips it afterwards.
+    pub fn transform(&mut self, rotate: Option<Angle>, flip: Option<Orientation>)
+    where
+        T: Clone,
+    {
+        if let Some(angle) = rotate {
+            self.rotate(angle);
+        }
+        if let Some(orientation) = flip {
+            self.flip(orientation);
+        }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "position {:?} is outside of the {}x{} grid",
+                position, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(position).unwrap_or_else(|| {
+            panic!(
+                "position {:?} is outside of the {}x{} grid",
+                position, width, height
+            )
+        })
+    }
+}
+
+/// Parses a char map with one row per line. Leading and trailing whitespace
+/// and empty lines are ignored.
+impl<T> FromStr for Grid<T>
+where
+    T: TryFrom<char>,
+{
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+        for line in input.lines().map(str::trim) {
+            if line.is_empty() {
+                continue;
+            }
+            for (index, c) in line.char_indices() {
+                let cell = T::try_from(c).map_err(|_| {
+                    ParseError::at(
+                        input,
+                        &line[index..index + c.len_utf8()],
+                        ParseErrorKind::UnexpectedCharacter(c),
+                    )
+                })?;
+                cells.push(cell);
+            }
+            let row_len = cells.len() - height * width.unwrap_or(0);
+            if *width.get_or_insert(row_len) != row_len {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    ParseErrorKind::Invalid("row width"),
+                ));
+            }
+            height += 1;
+        }
+        Ok(Self {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+}
+
+impl<T> Display for Grid<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Neighbors<'a, T> {
+    grid: &'a Grid<T>,
+    position: Position,
+    directions: slice::Iter<'static, Direction>,
+}
+
+impl<'a, T> Iterator for Neighbors<'a, T> {
+    type Item = (Position, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let grid = self.grid;
+        let position = self.position;
+        self.directions.find_map(|direction| {
+            grid.step(position, *direction)
+                .map(|neighbor| (neighbor, &grid[neighbor]))
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Ray<'a, T> {
+    grid: &'a Grid<T>,
+    position: Position,
+    direction: Direction,
+}
+
+impl<'a, T> Iterator for Ray<'a, T> {
+    type Item = (Position, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.grid.step(self.position, self.direction)?;
+        self.position = next;
+        Some((next, &self.grid[next]))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,167 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Open,
+    Wall,
+}
+
+use Cell::{Open, Wall};
+
+impl TryFrom<char> for Cell {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Open),
+            '#' => Ok(Wall),
+            _ => Err(value),
+        }
+    }
+}
+
+impl Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Open => f.write_str("."),
+            Wall => f.write_str("#"),
+        }
+    }
+}
+
+const EXAMPLE: &str = "\
+..#
+#..
+.##
+#.#
+";
+
+fn numbers(width: usize, height: usize) -> Grid<usize> {
+    Grid::from_cells(width, height, (0..width * height).collect())
+}
+
+#[test]
+fn parse_grid_from_char_map() {
+    let grid = Grid::<Cell>::from_str(EXAMPLE).unwrap();
+
+    assert_eq!(
+        grid,
+        Grid::from_rows(vec![
+            [Open, Open, Wall],
+            [Wall, Open, Open],
+            [Open, Wall, Wall],
+            [Wall, Open, Wall],
+        ])
+    );
+}
+
+#[test]
+fn parse_grid_with_unexpected_character() {
+    let error = Grid::<Cell>::from_str("..#\n#x.\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (2, 2));
+    assert_eq!(error.kind(), &ParseErrorKind::UnexpectedCharacter('x'));
+}
+
+#[test]
+fn parse_grid_with_rows_of_different_width() {
+    let error = Grid::<Cell>::from_str("..#\n#.\n").unwrap_err();
+
+    assert_eq!((error.line(), error.column()), (2, 1));
+    assert_eq!(error.kind(), &ParseErrorKind::Invalid("row width"));
+}
+
+#[test]
+fn display_grid_as_char_map() {
+    let grid = Grid::<Cell>::from_str(EXAMPLE).unwrap();
+
+    assert_eq!(grid.to_string(), EXAMPLE);
+}
+
+#[test]
+fn bounds_checked_and_wrapping_access() {
+    let grid = numbers(3, 2);
+
+    assert_eq!(grid.get(Position::new(1, 2)), Some(&5));
+    assert_eq!(grid.get(Position::new(2, 0)), None);
+    assert_eq!(grid.get(Position::new(0, 3)), None);
+    assert_eq!(grid.get_wrapping(Position::new(3, 7)), &4);
+}
+
+#[test]
+fn neighbors_of_corner_and_inner_cell() {
+    let grid = numbers(3, 3);
+
+    let corner = grid
+        .neighbors(Position::new(0, 0))
+        .map(|(_, cell)| *cell)
+        .collect::<Vec<_>>();
+    let inner = grid
+        .neighbors(Position::new(1, 1))
+        .map(|(_, cell)| *cell)
+        .collect::<Vec<_>>();
+    let orthogonal = grid
+        .orthogonal_neighbors(Position::new(1, 1))
+        .map(|(_, cell)| *cell)
+        .collect::<Vec<_>>();
+
+    assert_eq!(corner, vec![1, 4, 3]);
+    assert_eq!(inner, vec![1, 2, 5, 8, 7, 6, 3, 0]);
+    assert_eq!(orthogonal, vec![1, 5, 7, 3]);
+}
+
+#[test]
+fn cast_rays_along_row_column_and_diagonal() {
+    let grid = numbers(4, 4);
+    let ray = |position, direction| {
+        grid.ray(position, direction)
+            .map(|(_, cell)| *cell)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(ray(Position::new(1, 1), Direction::East), vec![6, 7]);
+    assert_eq!(ray(Position::new(1, 1), Direction::North), vec![1]);
+    assert_eq!(ray(Position::new(1, 1), Direction::SouthEast), vec![10, 15]);
+    assert_eq!(
+        ray(Position::new(3, 0), Direction::NorthEast),
+        vec![9, 6, 3]
+    );
+    assert_eq!(ray(Position::new(0, 0), Direction::West), vec![]);
+}
+
+#[test]
+fn rows_and_columns() {
+    let grid = numbers(3, 2);
+
+    assert_eq!(grid.row(1), &[3, 4, 5]);
+    assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![1, 4]);
+    assert_eq!(grid.rows().count(), 2);
+}
+
+#[test]
+fn rotate_non_square_grid() {
+    let mut grid = numbers(3, 2);
+
+    grid.rotate(Angle::D90);
+    assert_eq!(grid, Grid::from_rows(vec![[3, 0], [4, 1], [5, 2]]));
+
+    grid.rotate(Angle::D90);
+    assert_eq!(grid, Grid::from_rows(vec![[5, 4, 3], [2, 1, 0]]));
+
+    grid.rotate(Angle::D270);
+    assert_eq!(grid, Grid::from_rows(vec![[3, 0], [4, 1], [5, 2]]));
+
+    grid.rotate(Angle::D180);
+    assert_eq!(grid, Grid::from_rows(vec![[2, 5], [1, 4], [0, 3]]));
+}
+
+#[test]
+fn flip_grid_horizontal_and_vertical() {
+    let mut grid = numbers(3, 3);
+
+    grid.flip(Orientation::Horizontal);
+    assert_eq!(grid, Grid::from_rows(vec![[2, 1, 0], [5, 4, 3], [8, 7, 6]]));
+
+    grid.flip(Orientation::Vertical);
+    assert_eq!(grid, Grid::from_rows(vec![[8, 7, 6], [5, 4, 3], [2, 1, 0]]));
+}
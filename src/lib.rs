@@ -38,6 +38,9 @@
 #[macro_use]
 extern crate aoc_runner_derive;
 
+pub mod answers;
+pub mod assignment;
+pub mod automaton;
 pub mod day01;
 pub mod day02;
 pub mod day03;
@@ -58,5 +61,16 @@ pub mod day17;
 pub mod day18;
 pub mod day19;
 pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod grid;
+pub mod parse;
+pub mod registry;
+pub mod verify;
+
+pub use registry::{solver, solvers};
 
 aoc_lib! { year = 2020 }
@@ -0,0 +1,128 @@
+//! # Parsing puzzle input
+//!
+//! Every day comes with a `try_parse_*` function that turns the puzzle input
+//! into the day's data structures or reports a [`ParseError`] pointing at the
+//! offending part of the input. The `#[aoc_generator]` functions are built on
+//! top of them and panic with the error's message when the input is invalid.
+
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+/// The reason why some input could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// A number could not be parsed.
+    InvalidNumber(ParseIntError),
+    /// A character that is not allowed at this position.
+    UnexpectedCharacter(char),
+    /// A required element is missing.
+    Missing(&'static str),
+    /// An element is present but does not denote a valid value.
+    Invalid(&'static str),
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::InvalidNumber(err) => write!(f, "not a valid number: {}", err),
+            ParseErrorKind::UnexpectedCharacter(c) => write!(f, "unexpected character {:?}", c),
+            ParseErrorKind::Missing(what) => write!(f, "missing {}", what),
+            ParseErrorKind::Invalid(what) => write!(f, "not a valid {}", what),
+        }
+    }
+}
+
+/// An error that occurred while parsing puzzle input.
+///
+/// It carries the line and column (both starting at 1) where the offending
+/// snippet of the input starts, so bad input can be reported without
+/// aborting the process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    snippet: String,
+    kind: ParseErrorKind,
+}
+
+impl ParseError {
+    /// Creates an error for the given `snippet`, which must be a subslice of
+    /// `input`. Line and column are derived from the position of the snippet
+    /// within the input.
+    pub fn at(input: &str, snippet: &str, kind: ParseErrorKind) -> Self {
+        let offset = (snippet.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+        debug_assert!(
+            offset <= input.len(),
+            "snippet {:?} is not part of the input",
+            snippet
+        );
+        let offset = offset.min(input.len());
+        let preceding = &input[..offset];
+        let line_start = preceding.rfind('\n').map_or(0, |index| index + 1);
+        Self {
+            line: preceding.matches('\n').count() + 1,
+            column: preceding[line_start..].chars().count() + 1,
+            snippet: snippet.to_string(),
+            kind,
+        }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn snippet(&self) -> &str {
+        &self.snippet
+    }
+
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )?;
+        if !self.snippet.is_empty() {
+            write!(f, " in {:?}", self.snippet)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            ParseErrorKind::InvalidNumber(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Parses the `token`, a subslice of `input`, as a number.
+pub fn parse_number<T>(input: &str, token: &str) -> Result<T, ParseError>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    T::from_str(token)
+        .map_err(|err| ParseError::at(input, token, ParseErrorKind::InvalidNumber(err)))
+}
+
+/// Returns the empty subslice at the end of `s`, used to point at elements
+/// that are missing at the end of a line.
+pub fn end_of(s: &str) -> &str {
+    &s[s.len()..]
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,65 @@
+use super::*;
+
+#[test]
+fn parse_error_at_snippet_in_first_line() {
+    let input = "12 34 5x6\n78";
+
+    let error = ParseError::at(input, &input[6..9], ParseErrorKind::Invalid("token"));
+
+    assert_eq!(error.line(), 1);
+    assert_eq!(error.column(), 7);
+    assert_eq!(error.snippet(), "5x6");
+}
+
+#[test]
+fn parse_error_at_snippet_in_third_line() {
+    let input = "abc\n\ndéf ghi\n";
+
+    let error = ParseError::at(input, &input[10..13], ParseErrorKind::Invalid("token"));
+
+    assert_eq!(error.line(), 3);
+    assert_eq!(error.column(), 5);
+    assert_eq!(error.snippet(), "ghi");
+}
+
+#[test]
+fn parse_error_at_end_of_line() {
+    let input = "first\nsecond";
+    let line = input.lines().nth(1).unwrap();
+
+    let error = ParseError::at(input, end_of(line), ParseErrorKind::Missing("value"));
+
+    assert_eq!(error.line(), 2);
+    assert_eq!(error.column(), 7);
+    assert_eq!(error.snippet(), "");
+}
+
+#[test]
+fn display_parse_error_with_snippet() {
+    let input = "1\n2\nthree";
+
+    let error = parse_number::<u32>(input, &input[4..]).unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "line 3, column 1: not a valid number: invalid digit found in string in \"three\""
+    );
+}
+
+#[test]
+fn display_parse_error_without_snippet() {
+    let input = "mask =";
+
+    let error = ParseError::at(input, end_of(input), ParseErrorKind::Missing("mask value"));
+
+    assert_eq!(error.to_string(), "line 1, column 7: missing mask value");
+}
+
+#[test]
+fn parse_number_within_input() {
+    let input = "id: 42";
+
+    let number = parse_number::<u16>(input, &input[4..]);
+
+    assert_eq!(number, Ok(42));
+}
@@ -0,0 +1,407 @@
+//! # Registry of all solutions
+//!
+//! Lists every day of the calendar together with its title, its generator
+//! and the solvers of its parts. The expected answers for the puzzle input in
+//! `input/2020` are looked up in the [recorded answers](crate::answers).
+//! Runners, benchmark harnesses and test generators can iterate over all
+//! solutions without knowing the modules of the single days.
+
+use crate::answers::Answers;
+use crate::parse::ParseError;
+use crate::*;
+use std::any::Any;
+use std::fmt::{self, Debug};
+
+/// The puzzle input of a day after it has been parsed by the generator.
+pub type Generated = Box<dyn Any>;
+
+/// Parses the puzzle input of a day.
+pub type Generator = fn(&str) -> Result<Generated, ParseError>;
+
+/// Solves a part of a day's puzzle from the generated input and formats the
+/// answer.
+pub type Runner = fn(&dyn Any) -> String;
+
+/// Formats the result of a solver as the answer to a puzzle.
+pub trait Answer {
+    fn to_answer(&self) -> String;
+}
+
+macro_rules! impl_answer_for_display {
+    ($($ty:ty),*) => {
+        $(
+            impl Answer for $ty {
+                fn to_answer(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_for_display!(i32, i64, u32, u64, usize, String);
+
+impl<T> Answer for Option<T>
+where
+    T: Answer,
+{
+    fn to_answer(&self) -> String {
+        self.as_ref()
+            .map_or_else(|| String::from("none"), Answer::to_answer)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Part {
+    /// The name of the function solving this part.
+    pub name: &'static str,
+    pub run: Runner,
+    /// Whether the solver is known to take much longer than the others on
+    /// the puzzle input, so verification skips it by default.
+    pub slow: bool,
+}
+
+impl Debug for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Part")
+            .field("name", &self.name)
+            .field("slow", &self.slow)
+            .finish()
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub day: u8,
+    pub title: &'static str,
+    pub generator: Generator,
+    pub parts: &'static [Part],
+}
+
+impl Debug for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Solution")
+            .field("day", &self.day)
+            .field("title", &self.title)
+            .field("parts", &self.parts)
+            .finish()
+    }
+}
+
+impl Solution {
+    /// Returns the part with the given number, starting at 1.
+    pub fn part(&self, number: u8) -> Option<&'static Part> {
+        usize::from(number)
+            .checked_sub(1)
+            .and_then(|index| self.parts.get(index))
+    }
+
+    /// Returns the answer to the puzzle input recorded for the part with the
+    /// given number, if it is known.
+    pub fn expected(&self, part: u8) -> Option<&'static str> {
+        Answers::recorded().get(self.day, part)
+    }
+
+    /// The path of the puzzle input relative to the root of the repository.
+    pub fn input_path(&self) -> String {
+        format!("input/2020/day{}.txt", self.day)
+    }
+
+    /// Parses the input and solves the part with the given number.
+    ///
+    /// Returns `None` if the day has no such part.
+    pub fn solve(&self, part: u8, input: &str) -> Option<Result<String, ParseError>> {
+        let part = self.part(part)?;
+        Some((self.generator)(input).map(|generated| (part.run)(&*generated)))
+    }
+}
+
+fn boxed<T: Any>(generated: T) -> Generated {
+    Box::new(generated)
+}
+
+fn downcast<T: Any>(generated: &dyn Any) -> &T {
+    generated
+        .downcast_ref()
+        .expect("generated input does not belong to this solution")
+}
+
+macro_rules! is_slow {
+    () => {
+        false
+    };
+    (slow) => {
+        true
+    };
+}
+
+/// Registers a day. Parts marked with `#[slow]` are skipped by default when
+/// verifying the answers.
+macro_rules! solution {
+    (
+        day: $day:expr,
+        title: $title:expr,
+        generator: $generator:path => $generated:ty,
+        parts: [$($(#[$slow:ident])? $part:path),* $(,)?] $(,)?
+    ) => {
+        Solution {
+            day: $day,
+            title: $title,
+            generator: |input| $generator(input).map(boxed),
+            parts: &[$(
+                Part {
+                    name: stringify!($part),
+                    run: |generated| $part(downcast::<$generated>(generated)).to_answer(),
+                    slow: is_slow!($($slow)?),
+                }
+            ),*],
+        }
+    };
+}
+
+/// The bag rules of day 7 borrow from the input, so the generator only
+/// validates the input and the parts parse it again.
+fn validate_bag_rules(input: &str) -> Result<String, ParseError> {
+    day07::try_parse_bag_rules(input).map(|_| input.to_string())
+}
+
+static SOLUTIONS: [Solution; 25] = [
+    solution! {
+        day: 1,
+        title: "Report Repair",
+        generator: day01::try_parse => Vec<i32>,
+        parts: [
+            day01::product_of_two_incorrect_expenses,
+            day01::product_of_three_incorrect_expenses,
+        ],
+    },
+    solution! {
+        day: 2,
+        title: "Password Philosophy",
+        generator: day02::try_parse => Vec<(day02::Policy, String)>,
+        parts: [
+            day02::count_valid_passwords,
+            day02::count_valid_passwords_part2,
+        ],
+    },
+    solution! {
+        day: 3,
+        title: "Toboggan Trajectory",
+        generator: day03::try_parse => day03::Map,
+        parts: [
+            day03::count_trees_on_slope_r3d1,
+            day03::product_of_trees_on_multiple_slopes,
+        ],
+    },
+    solution! {
+        day: 4,
+        title: "Passport Processing",
+        generator: day04::try_parse => Vec<std::collections::HashMap<String, String>>,
+        parts: [
+            day04::count_valid_passports,
+            day04::count_valid_passports2,
+        ],
+    },
+    solution! {
+        day: 5,
+        title: "Binary Boarding",
+        generator: day05::try_parse_boarding_passes => Vec<String>,
+        parts: [
+            day05::highest_seat_id_on_a_boarding_pass,
+            day05::find_free_seat,
+        ],
+    },
+    solution! {
+        day: 6,
+        title: "Custom Customs",
+        generator: day06::try_parse_compliance_list => Vec<day06::Group>,
+        parts: [
+            day06::sum_of_anyone_is_compliant,
+            day06::sum_of_everyone_is_compliant,
+        ],
+    },
+    solution! {
+        day: 7,
+        title: "Handy Haversacks",
+        generator: validate_bag_rules => String,
+        parts: [
+            day07::part1_number_of_bag_colors_containing_shiny_gold_bags,
+            day07::part2_number_of_bags_inside_shiny_gold_bag,
+        ],
+    },
+    solution! {
+        day: 8,
+        title: "Handheld Halting",
+        generator: day08::try_parse_program => day08::Program,
+        parts: [
+            day08::accumulator_value_before_second_run,
+            day08::accumulator_value_after_fixing_the_endless_loop,
+        ],
+    },
+    solution! {
+        day: 9,
+        title: "Encoding Error",
+        generator: day09::try_parse_xmas_code => day09::XmasCode,
+        parts: [
+            day09::first_invalid_number,
+            day09::encryption_weakness,
+        ],
+    },
+    solution! {
+        day: 10,
+        title: "Adapter Array",
+        generator: day10::try_parse_adapter_joltages => Vec<day10::Adapter>,
+        parts: [
+            day10::joltage_differences_in_adapter_chain,
+            day10::number_of_possible_adapter_arrangements,
+        ],
+    },
+    solution! {
+        day: 11,
+        title: "Seating System",
+        generator: day11::try_parse_initial_seat_layout => day11::SeatLayout,
+        parts: [
+            day11::number_of_occupied_seats_part1,
+            day11::number_of_occupied_seats_part2,
+        ],
+    },
+    solution! {
+        day: 12,
+        title: "Rain Risk",
+        generator: day12::try_parse_navigation_instructions => Vec<day12::Navigate>,
+        parts: [
+            day12::part1_distance_to_final_position,
+            day12::part2_distance_to_final_position,
+        ],
+    },
+    solution! {
+        day: 13,
+        title: "Shuttle Search",
+        generator: day13::try_parse_bus_service_notes => day13::BusServiceNotes,
+        parts: [
+            day13::earliest_bus_result,
+            #[slow]
+            day13::earliest_time_buses_depart_in_sequence,
+        ],
+    },
+    solution! {
+        day: 14,
+        title: "Docking Data",
+        generator: day14::try_parse_init_program => day14::Program,
+        parts: [
+            day14::sum_of_all_values_in_memory_v1,
+            day14::sum_of_all_values_in_memory_v2,
+        ],
+    },
+    solution! {
+        day: 15,
+        title: "Rambunctious Recitation",
+        generator: day15::try_parse_starting_numbers => Vec<u32>,
+        parts: [
+            day15::determine_the_2020th_number_spoken,
+            day15::determine_the_30millionsth_number_spoken,
+        ],
+    },
+    solution! {
+        day: 16,
+        title: "Ticket Translation",
+        generator: day16::try_parse_ticket_notes => day16::TicketNotes,
+        parts: [
+            day16::ticket_scanning_error_rate,
+            day16::product_of_departure_values_in_my_ticket,
+        ],
+    },
+    solution! {
+        day: 17,
+        title: "Conway Cubes",
+        generator: day17::try_parse_conway_cubes => day17::PocketDimension<3>,
+        parts: [
+            day17::num_active_cubes_after_6_cycle_boot,
+            day17::num_active_hypercubes_after_6_cycle_boot,
+        ],
+    },
+    solution! {
+        day: 18,
+        title: "Operation Order",
+        generator: day18::try_parse_math_homework => Vec<String>,
+        parts: [
+            day18::sum_of_math_results_with_equal_precedence,
+            day18::sum_of_math_results_with_custom_precedence,
+        ],
+    },
+    solution! {
+        day: 19,
+        title: "Monster Messages",
+        generator: day19::try_parse_monster_messages => day19::MonsterMessages,
+        parts: [
+            day19::count_matching_messages,
+            day19::count_matching_messages_patched_rules,
+        ],
+    },
+    solution! {
+        day: 20,
+        title: "Jurassic Jigsaw",
+        generator: day20::try_parse_image_tiles => Vec<day20::Tile>,
+        parts: [
+            day20::checksum_of_reassembled_image,
+            day20::water_roughness,
+        ],
+    },
+    solution! {
+        day: 21,
+        title: "Allergen Assessment",
+        generator: day21::try_parse_food_list => Vec<day21::Food>,
+        parts: [
+            day21::number_of_appearances_of_allergen_free_ingredients,
+            day21::canonical_dangerous_ingredient_list,
+        ],
+    },
+    solution! {
+        day: 22,
+        title: "Crab Combat",
+        generator: day22::try_parse_decks => day22::Decks,
+        parts: [
+            day22::winning_players_score_in_combat,
+            day22::winning_players_score_in_recursive_combat,
+        ],
+    },
+    solution! {
+        day: 23,
+        title: "Crab Cups",
+        generator: day23::try_parse_cup_labeling => Vec<day23::Cup>,
+        parts: [
+            day23::labels_after_cup_1_after_100_moves,
+            day23::product_of_cups_holding_the_stars,
+        ],
+    },
+    solution! {
+        day: 24,
+        title: "Lobby Layout",
+        generator: day24::try_parse_tile_paths => Vec<Vec<day24::HexDirection>>,
+        parts: [
+            day24::number_of_black_tiles,
+            day24::number_of_black_tiles_after_100_days,
+        ],
+    },
+    solution! {
+        day: 25,
+        title: "Combo Breaker",
+        generator: day25::try_parse_public_keys => day25::PublicKeys,
+        parts: [
+            day25::encryption_key,
+        ],
+    },
+];
+
+/// Returns the solutions of all days in the order of the calendar.
+pub fn solvers() -> &'static [Solution] {
+    &SOLUTIONS
+}
+
+/// Returns the solution of the given day.
+pub fn solver(day: u8) -> Option<&'static Solution> {
+    SOLUTIONS.iter().find(|solution| solution.day == day)
+}
+
+#[cfg(test)]
+mod tests;
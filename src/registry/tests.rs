@@ -0,0 +1,78 @@
+use super::*;
+
+#[test]
+fn solvers_cover_every_day_of_the_calendar_in_order() {
+    let days = solvers()
+        .iter()
+        .map(|solution| solution.day)
+        .collect::<Vec<_>>();
+
+    assert_eq!(days, (1..=25).collect::<Vec<_>>());
+}
+
+#[test]
+fn every_day_has_two_parts_except_the_last_one() {
+    for solution in solvers() {
+        let expected_parts = if solution.day == 25 { 1 } else { 2 };
+
+        assert_eq!(solution.parts.len(), expected_parts, "day {}", solution.day);
+    }
+}
+
+#[test]
+fn part_names_refer_to_the_solving_functions() {
+    let solution = solver(1).unwrap();
+
+    assert_eq!(
+        solution.part(1).map(|part| part.name),
+        Some("day01::product_of_two_incorrect_expenses")
+    );
+    assert_eq!(solution.part(0).map(|part| part.name), None);
+    assert_eq!(solution.part(3).map(|part| part.name), None);
+}
+
+#[test]
+fn solve_part2_of_day1_for_example() {
+    let solution = solver(1).unwrap();
+
+    let answer = solution.solve(2, "1721\n979\n366\n299\n675\n1456\n");
+
+    assert_eq!(answer, Some(Ok(String::from("241861950"))));
+}
+
+#[test]
+fn solve_day7_which_borrows_from_the_input() {
+    let solution = solver(7).unwrap();
+
+    let answer = solution.solve(
+        1,
+        "bright white bags contain 1 shiny gold bag.\nshiny gold bags contain no other bags.\n",
+    );
+
+    assert_eq!(answer, Some(Ok(String::from("1"))));
+}
+
+#[test]
+fn solve_with_invalid_input() {
+    let solution = solver(9).unwrap();
+
+    let answer = solution.solve(1, "35\n20\nfifteen\n");
+
+    assert_eq!(
+        answer.map(|result| result.map_err(|err| (err.line(), err.column()))),
+        Some(Err((3, 1)))
+    );
+}
+
+#[test]
+fn solve_part_that_does_not_exist() {
+    let solution = solver(25).unwrap();
+
+    assert_eq!(solution.solve(2, "5764801\n17807724\n"), None);
+}
+
+#[test]
+fn answer_of_optional_result() {
+    assert_eq!(Some(295u32).to_answer(), "295");
+    assert_eq!(None::<u32>.to_answer(), "none");
+}
@@ -0,0 +1,267 @@
+//! # Verification against recorded answers
+//!
+//! Runs registered solvers on their puzzle input and compares the answers
+//! with the [recorded answers](crate::answers). Each part is solved in a
+//! thread of its own, so a solver that does not finish within the timeout is
+//! reported as timed out while the verification moves on to the next part.
+//!
+//! A timed out solver keeps running in the background and slows down the
+//! parts verified after it. Parts that are known to be slow are therefore
+//! skipped unless they are explicitly included.
+
+use crate::answers::Answers;
+use crate::registry::{Generator, Runner, Solution};
+use std::fmt::{self, Display, Write};
+use std::fs;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    /// The answer matches the recorded one.
+    Pass { answer: String },
+    /// The answer differs from the recorded one.
+    Fail { expected: String, actual: String },
+    /// There is no recorded answer to compare the answer with.
+    Unrecorded { answer: String },
+    /// The solver did not finish within the timeout.
+    Timeout,
+    /// The input could not be read or parsed, or the solver panicked.
+    Error(String),
+    /// The part is known to be slow and was not run.
+    Skipped,
+}
+
+impl Status {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::Pass { .. } => "pass",
+            Status::Fail { .. } => "fail",
+            Status::Unrecorded { .. } => "unrecorded",
+            Status::Timeout => "timeout",
+            Status::Error(_) => "error",
+            Status::Skipped => "skipped",
+        }
+    }
+
+    /// Whether this status counts as a failed verification.
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Status::Fail { .. } | Status::Timeout | Status::Error(_)
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verification {
+    pub day: u8,
+    pub part: u8,
+    pub status: Status,
+    /// The time it took to parse the input and solve the part.
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    pub verifications: Vec<Verification>,
+}
+
+impl Report {
+    /// Whether no part failed, timed out or ran into an error.
+    pub fn is_success(&self) -> bool {
+        self.verifications
+            .iter()
+            .all(|verification| !verification.status.is_failure())
+    }
+
+    fn count(&self, label: &str) -> usize {
+        self.verifications
+            .iter()
+            .filter(|verification| verification.status.label() == label)
+            .count()
+    }
+
+    /// Formats the report as a JSON array with one object per part.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("[");
+        for (index, verification) in self.verifications.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            write!(
+                json,
+                "\n  {{\"day\": {}, \"part\": {}, \"status\": \"{}\", \"elapsed_us\": {}",
+                verification.day,
+                verification.part,
+                verification.status.label(),
+                verification.elapsed.as_micros()
+            )
+            .unwrap();
+            let fields: &[(&str, &str)] = match &verification.status {
+                Status::Pass { answer } => &[("answer", answer)],
+                Status::Fail { expected, actual } => &[("expected", expected), ("answer", actual)],
+                Status::Unrecorded { answer } => &[("answer", answer)],
+                Status::Timeout | Status::Skipped => &[],
+                Status::Error(message) => &[("error", message)],
+            };
+            for (name, value) in fields {
+                write!(json, ", \"{}\": {}", name, json_string(value)).unwrap();
+            }
+            json.push('}');
+        }
+        json.push_str("\n]\n");
+        json
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>3}  {:>4}  {:<10}  {:>14}  answer",
+            "day", "part", "status", "elapsed"
+        )?;
+        for verification in &self.verifications {
+            let details = match &verification.status {
+                Status::Pass { answer } => answer.clone(),
+                Status::Fail { expected, actual } => {
+                    format!("{} (expected {})", actual, expected)
+                }
+                Status::Unrecorded { answer } => answer.clone(),
+                Status::Timeout | Status::Skipped => String::new(),
+                Status::Error(message) => message.clone(),
+            };
+            writeln!(
+                f,
+                "{:>3}  {:>4}  {:<10}  {:>14}  {}",
+                verification.day,
+                verification.part,
+                verification.status.label(),
+                format!("{:?}", verification.elapsed),
+                details
+            )?;
+        }
+        write!(
+            f,
+            "{} passed, {} failed, {} timed out, {} errors, {} unrecorded",
+            self.count("pass"),
+            self.count("fail"),
+            self.count("timeout"),
+            self.count("error"),
+            self.count("unrecorded")
+        )?;
+        let skipped = self.count("skipped");
+        if skipped > 0 {
+            write!(f, ", {} skipped", skipped)?;
+        }
+        Ok(())
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            _ if c.is_control() => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            _ => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Parses the input and solves one part in a separate thread, waiting at
+/// most `timeout` for the answer.
+fn solve_with_timeout(
+    generator: Generator,
+    run: Runner,
+    input: String,
+    timeout: Duration,
+) -> (Status, Duration) {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let start = Instant::now();
+        let answer = generator(&input).map(|generated| run(&*generated));
+        // the receiver is gone if the verification has given up on us
+        let _ = sender.send((answer, start.elapsed()));
+    });
+    match receiver.recv_timeout(timeout) {
+        Ok((Ok(answer), elapsed)) => (Status::Unrecorded { answer }, elapsed),
+        Ok((Err(err), elapsed)) => (Status::Error(format!("invalid input: {}", err)), elapsed),
+        Err(RecvTimeoutError::Timeout) => (Status::Timeout, timeout),
+        Err(RecvTimeoutError::Disconnected) => (
+            Status::Error(String::from("solver panicked")),
+            Duration::default(),
+        ),
+    }
+}
+
+/// Runs every part of the given solutions on the puzzle input and compares
+/// the answers with the given recorded answers.
+///
+/// Parts marked as [slow](crate::registry::Part::slow) are skipped.
+pub fn verify(solutions: &[Solution], answers: &Answers, timeout: Duration) -> Report {
+    verify_parts(solutions, answers, timeout, false)
+}
+
+/// Verifies all parts like [`verify`] including the slow ones.
+pub fn verify_including_slow(
+    solutions: &[Solution],
+    answers: &Answers,
+    timeout: Duration,
+) -> Report {
+    verify_parts(solutions, answers, timeout, true)
+}
+
+fn verify_parts(
+    solutions: &[Solution],
+    answers: &Answers,
+    timeout: Duration,
+    include_slow: bool,
+) -> Report {
+    let mut verifications = Vec::new();
+    for solution in solutions {
+        let input = fs::read_to_string(solution.input_path());
+        for (index, part) in solution.parts.iter().enumerate() {
+            let number = index as u8 + 1;
+            let (status, elapsed) = match &input {
+                _ if part.slow && !include_slow => (Status::Skipped, Duration::default()),
+                Ok(input) => {
+                    solve_with_timeout(solution.generator, part.run, input.clone(), timeout)
+                }
+                Err(err) => (
+                    Status::Error(format!("cannot read input: {}", err)),
+                    Duration::default(),
+                ),
+            };
+            let status = match (status, answers.get(solution.day, number)) {
+                (Status::Unrecorded { answer }, Some(expected)) if answer == expected => {
+                    Status::Pass { answer }
+                }
+                (Status::Unrecorded { answer }, Some(expected)) => Status::Fail {
+                    expected: expected.to_string(),
+                    actual: answer,
+                },
+                (status, _) => status,
+            };
+            verifications.push(Verification {
+                day: solution.day,
+                part: number,
+                status,
+                elapsed,
+            });
+        }
+    }
+    Report { verifications }
+}
+
+#[cfg(test)]
+mod tests;
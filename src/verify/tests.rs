@@ -0,0 +1,172 @@
+use super::*;
+use crate::registry::{solver, Part};
+use std::str::FromStr;
+
+fn day1() -> Solution {
+    *solver(1).unwrap()
+}
+
+#[test]
+fn verify_day1_against_recorded_answers() {
+    let report = verify(&[day1()], Answers::recorded(), Duration::from_secs(10));
+
+    assert!(report.is_success());
+    assert_eq!(report.verifications.len(), 2);
+    assert_eq!(
+        report.verifications[0].status,
+        Status::Pass {
+            answer: String::from("788739")
+        }
+    );
+    assert_eq!(report.verifications[1].status.label(), "pass");
+}
+
+#[test]
+fn verify_day1_against_wrong_and_missing_answers() {
+    let answers = Answers::from_str("[day1]\npart1 = \"1\"\n").unwrap();
+
+    let report = verify(&[day1()], &answers, Duration::from_secs(10));
+
+    assert!(!report.is_success());
+    assert_eq!(
+        report.verifications[0].status,
+        Status::Fail {
+            expected: String::from("1"),
+            actual: String::from("788739")
+        }
+    );
+    assert_eq!(
+        report.verifications[1].status,
+        Status::Unrecorded {
+            answer: String::from("178724430")
+        }
+    );
+}
+
+#[test]
+fn verify_solver_that_does_not_finish_in_time() {
+    let solution = Solution {
+        parts: &[Part {
+            name: "sleep",
+            run: |_| {
+                thread::sleep(Duration::from_secs(2));
+                String::new()
+            },
+            slow: false,
+        }],
+        ..day1()
+    };
+
+    let report = verify(&[solution], Answers::recorded(), Duration::from_millis(10));
+
+    assert!(!report.is_success());
+    assert_eq!(report.verifications[0].status, Status::Timeout);
+    assert_eq!(report.verifications[0].elapsed, Duration::from_millis(10));
+}
+
+#[test]
+fn verify_skips_slow_parts_unless_included() {
+    let solution = Solution {
+        parts: &[Part {
+            name: "slow",
+            run: |_| String::from("788739"),
+            slow: true,
+        }],
+        ..day1()
+    };
+
+    let skipped = verify(&[solution], Answers::recorded(), Duration::from_secs(10));
+    let included = verify_including_slow(&[solution], Answers::recorded(), Duration::from_secs(10));
+
+    assert!(skipped.is_success());
+    assert_eq!(skipped.verifications[0].status, Status::Skipped);
+    assert_eq!(included.verifications[0].status.label(), "pass");
+}
+
+#[test]
+fn verify_solver_that_panics() {
+    let solution = Solution {
+        parts: &[Part {
+            name: "panic",
+            run: |_| panic!("no solution"),
+            slow: false,
+        }],
+        ..day1()
+    };
+
+    let report = verify(&[solution], Answers::recorded(), Duration::from_secs(10));
+
+    assert_eq!(
+        report.verifications[0].status,
+        Status::Error(String::from("solver panicked"))
+    );
+}
+
+fn example_report() -> Report {
+    Report {
+        verifications: vec![
+            Verification {
+                day: 1,
+                part: 1,
+                status: Status::Pass {
+                    answer: String::from("788739"),
+                },
+                elapsed: Duration::from_micros(42),
+            },
+            Verification {
+                day: 13,
+                part: 2,
+                status: Status::Timeout,
+                elapsed: Duration::from_secs(10),
+            },
+            Verification {
+                day: 21,
+                part: 2,
+                status: Status::Fail {
+                    expected: String::from("a,b"),
+                    actual: String::from("b,a"),
+                },
+                elapsed: Duration::from_millis(3),
+            },
+        ],
+    }
+}
+
+#[test]
+fn format_report_as_table() {
+    let table = example_report().to_string();
+
+    assert_eq!(
+        table.lines().collect::<Vec<_>>(),
+        [
+            "day  part  status             elapsed  answer",
+            "  1     1  pass                  42µs  788739",
+            " 13     2  timeout                10s  ",
+            " 21     2  fail                   3ms  b,a (expected a,b)",
+            "1 passed, 1 failed, 1 timed out, 0 errors, 0 unrecorded",
+        ]
+    );
+}
+
+#[test]
+fn format_report_as_json() {
+    let json = example_report().to_json();
+
+    assert_eq!(
+        json,
+        r#"[
+  {"day": 1, "part": 1, "status": "pass", "elapsed_us": 42, "answer": "788739"},
+  {"day": 13, "part": 2, "status": "timeout", "elapsed_us": 10000000},
+  {"day": 21, "part": 2, "status": "fail", "elapsed_us": 3000, "expected": "a,b", "answer": "b,a"}
+]
+"#
+    );
+}
+
+#[test]
+fn escape_json_strings() {
+    assert_eq!(
+        json_string("say \"hi\"\n\\\u{1}"),
+        r#""say \"hi\"\n\\\u0001""#
+    );
+}